@@ -1,187 +1,723 @@
 use crate::pages::homepage::GenerateParams;
+use crate::security::CsrfClient;
+use crate::text::{detect::LanguageSpan, normalize::NormalizeOptions};
 #[cfg(not(target_arch = "wasm32"))]
-use base64::{engine::general_purpose, Engine as _};
+use crate::{
+    audio,
+    jobs::JobEvent,
+    pages::homepage::VoiceParams,
+    text::detect,
+    text::lexicon::{self as lexicon_rules, AppliedEntry, LexiconEntry},
+    text::normalize,
+    text::ssml::{self, Segment},
+};
 #[cfg(not(target_arch = "wasm32"))]
-use leptos::logging::debug_log;
+use dashscope::DashScope;
+use language::SynthesisLanguage;
 use leptos::prelude::*;
 #[cfg(not(target_arch = "wasm32"))]
-use reqwest::Client;
+use provider::{SynthesisRequest, SynthesizedAudio, TtsProvider};
 use serde::{Deserialize, Serialize};
 
+pub mod cloning;
+#[cfg(not(target_arch = "wasm32"))]
+mod dashscope;
+pub mod history;
+pub mod language;
+pub mod lexicon;
+pub mod mixer;
+pub mod presets;
+#[cfg(not(target_arch = "wasm32"))]
+mod provider;
+pub mod share;
+pub mod theme;
+pub mod timeline;
+pub mod voicefilter;
+
 #[derive(Clone, Serialize, Deserialize)]
 pub struct VoiceOption {
     pub id: String,
     pub name: String,
     pub desc: String,
+    /// 支持的语言，为空表示只支持普通语言 (不含方言)
+    #[serde(default)]
+    pub languages: Vec<SynthesisLanguage>,
+}
+
+impl VoiceOption {
+    pub fn supports(&self, language: SynthesisLanguage) -> bool {
+        language::voice_supports(&self.languages, language)
+    }
 }
-#[server]
+
+/// 给用户看的错误文案：服务端主动返回的错误 (如声线不支持所选语言) 只保留原文，
+/// 不带 server_fn 加的 "error running server function" 前缀
+pub fn error_message(e: &ServerFnError) -> String {
+    match e {
+        ServerFnError::ServerError(message) => message.clone(),
+        e => e.to_string(),
+    }
+}
+
+#[server(client = CsrfClient)]
 pub async fn get_voices() -> Result<Vec<VoiceOption>, ServerFnError> {
     // 这里是服务器端代码
     // 模拟数据库查询，返回硬编码数据
+    use SynthesisLanguage::*;
+    let voice = |id: &str, name: &str, desc: &str, languages: &[SynthesisLanguage]| VoiceOption {
+        id: id.to_string(),
+        name: name.to_string(),
+        desc: desc.to_string(),
+        languages: languages.to_vec(),
+    };
     let voices = vec![
-        VoiceOption {
-            id: "Cherry".to_string(),
-            name: "芊悦".to_string(),
-            desc: "阳光积极、亲切自然小姐姐。".to_string(),
-        },
-        VoiceOption {
-            id: "Ethan".to_string(),
-            name: "晨煦".to_string(),
-            desc: "标准普通话，带部分北方口音。阳光、温暖、活力、朝气。".to_string(),
-        },
-        VoiceOption {
-            id: "Elias".to_string(),
-            name: "墨讲师".to_string(),
-            desc: "既保持学科严谨性，又通过叙事技巧将复杂知识转化为可消化的认知模块。".to_string(),
-        },
+        voice(
+            "Cherry",
+            "芊悦",
+            "阳光积极、亲切自然小姐姐。",
+            &[Chinese, English, Japanese, Korean],
+        ),
+        voice(
+            "Ethan",
+            "晨煦",
+            "标准普通话，带部分北方口音。阳光、温暖、活力、朝气。",
+            &[Chinese, English, Japanese, Korean],
+        ),
+        voice(
+            "Elias",
+            "墨讲师",
+            "既保持学科严谨性，又通过叙事技巧将复杂知识转化为可消化的认知模块。",
+            &[Chinese, English],
+        ),
+        // 方言声线
+        voice("Rocky", "阿强", "幽默风趣的粤语男声。", &[Cantonese]),
+        voice("Kiki", "阿清", "甜美的港妹闺蜜，粤语女声。", &[Cantonese]),
+        voice(
+            "Sunny",
+            "晴儿",
+            "甜到心里的川妹子，四川话女声。",
+            &[Sichuanese],
+        ),
+        voice(
+            "Eric",
+            "程川",
+            "跳脱市井的成都男子，四川话男声。",
+            &[Sichuanese],
+        ),
+        voice(
+            "Jada",
+            "上海-阿珍",
+            "风风火火的沪上阿姐，上海话女声。",
+            &[Shanghainese],
+        ),
     ];
 
     Ok(voices)
 }
 
+/// 单次请求给 DashScope 的最大字符数，超过后按句子分段合成再拼接
 #[cfg(not(target_arch = "wasm32"))]
-#[derive(Serialize)]
-struct DashScopeRequest {
-    model: String,
-    input: DashScopeInput,
-    parameters: DashScopeParameters,
-}
+const MAX_CHUNK_CHARS: usize = 300;
 
+/// 波形概览的柱数
 #[cfg(not(target_arch = "wasm32"))]
-#[derive(Serialize)]
-struct DashScopeInput {
+const PEAK_BUCKETS: usize = 120;
+
+/// 语谱图的列数与频带数
+#[cfg(not(target_arch = "wasm32"))]
+const SPECTROGRAM_COLUMNS: usize = 240;
+#[cfg(not(target_arch = "wasm32"))]
+const SPECTROGRAM_BANDS: usize = 64;
+
+/// 合成结果：存入片段库的音频地址，以及用于绘制波形的时长和峰值
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GeneratedAudio {
+    /// 片段库中的 ID，用于按需获取语谱图等
+    pub clip_id: String,
+    pub src: String,
+    /// 时长 (秒)，无法解码时为 0
+    pub duration: f32,
+    /// 波形峰值 (0 ~ 255)，无法解码时为空
+    pub peaks: Vec<u8>,
+    pub alignment: Alignment,
+}
+
+/// 对齐到音频时间轴上的一段文本 (秒)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TimedText {
+    pub text: String,
+    pub start: f32,
+    pub end: f32,
+}
+
+/// 文本与音频的对齐信息，用于播放时高亮正在朗读的句子或词
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Alignment {
+    /// 句子级时间戳：分段的起止时间是准确的，分段内按字数估算
+    pub sentences: Vec<TimedText>,
+    /// 词级时间戳，仅在服务商提供时才有
+    pub words: Vec<TimedText>,
+}
+
+impl Alignment {
+    /// 所有时间戳平移 `offset` 秒并限制在 `0..=duration` 内，去掉平移后为空的片段
+    pub fn shift(&mut self, offset: f32, duration: f32) {
+        for spans in [&mut self.sentences, &mut self.words] {
+            for span in spans.iter_mut() {
+                span.start = (span.start + offset).clamp(0.0, duration);
+                span.end = (span.end + offset).clamp(0.0, duration);
+            }
+            spans.retain(|span| span.end > span.start);
+        }
+    }
+}
+
+/// 语谱图：`columns` 列 × `bands` 个对数频带，能量量化为 0 ~ 255，按列存放
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Spectrogram {
+    pub columns: usize,
+    pub bands: usize,
+    pub data: Vec<u8>,
+}
+
+// --- 生成任务：先创建任务拿到 ID，浏览器订阅进度后再调用 generate_audio ---
+#[server(client = CsrfClient)]
+pub async fn create_job() -> Result<String, ServerFnError> {
+    Ok(crate::jobs::create())
+}
+
+// --- 新增：生成音频 API ---
+#[server(client = CsrfClient)]
+pub async fn generate_audio(params: GenerateParams) -> Result<GeneratedAudio, ServerFnError> {
+    let job_id = params.job_id.clone();
+    let report = move |event: JobEvent| {
+        if let Some(id) = &job_id {
+            crate::jobs::publish(id, event);
+        }
+    };
+
+    let result = synthesize_text(&params, &report).await;
+    match &result {
+        Ok(_) => report(JobEvent::Finished),
+        Err(e) => report(JobEvent::Failed {
+            message: error_message(e),
+        }),
+    }
+    let (mut audio, mut alignment) = result?;
+
+    // 服务商直接返回的非 WAV 音频不做后期处理，也不绘制波形
+    let (duration, peaks) = match audio::decode_wav(&audio.bytes) {
+        Ok(pcm) if params.post.any_enabled() => {
+            let options = params.post.clone();
+            let (pcm, offset) =
+                tokio::task::spawn_blocking(move || audio::post::process(&pcm, &options))
+                    .await
+                    .map_err(|e| -> ServerFnError {
+                        ServerFnError::ServerError(format!("后期处理失败: {}", e))
+                    })?;
+            alignment.shift(-offset, pcm.duration());
+            audio.bytes = audio::encode_wav(&pcm);
+            audio.content_type = "audio/wav";
+            (pcm.duration(), pcm.peaks(PEAK_BUCKETS))
+        }
+        Ok(pcm) => (pcm.duration(), pcm.peaks(PEAK_BUCKETS)),
+        Err(_) => (0.0, Vec::new()),
+    };
+
+    let clip_id = crate::clips::insert(audio.content_type, audio.bytes);
+    // 历史只是方便复用，记录失败不影响本次生成
+    if let Err(e) = history::record(&clip_id, &params.text, &params.voice_id, duration).await {
+        leptos::logging::warn!("记录生成历史失败: {}", e);
+    }
+    Ok(GeneratedAudio {
+        src: crate::clips::clip_url(&clip_id),
+        clip_id,
+        duration,
+        peaks,
+        alignment,
+    })
+}
+
+/// 预览自动识别的语言分段：与合成时一样先经过 SSML、发音词典和文本规范化，
+/// 返回的片段文本可原样作为 [`GenerateParams::language_overrides`] 修正语言
+#[server(client = CsrfClient)]
+pub async fn detect_language_segments(
     text: String,
-    voice: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    language_type: Option<String>,
+    normalize: NormalizeOptions,
+) -> Result<Vec<LanguageSpan>, ServerFnError> {
+    let lexicon = lexicon::current_entries().await?;
+    let plan = plan_segments(&text, &lexicon, &normalize)?;
+    Ok(plan
+        .segments
+        .iter()
+        .flat_map(|segment| match segment {
+            Segment::Speech { text, .. } => detect::segment(text),
+            Segment::Break(_) => Vec::new(),
+        })
+        .collect())
 }
 
-#[cfg(not(target_arch = "wasm32"))]
-#[derive(Serialize)]
-struct DashScopeParameters {
-    // 这里的参数根据模型不同而不同，qwen3-tts-flash 文档主要强调 input
-    // 我们可以预留 sample_rate 或 format，但在文档示例中未强制要求
+/// 计算片段库中音频的语谱图，供结果卡片按需展示
+#[server(client = CsrfClient)]
+pub async fn get_spectrogram(clip_id: String) -> Result<Spectrogram, ServerFnError> {
+    let clip = crate::clips::get(&clip_id).ok_or_else(|| -> ServerFnError {
+        ServerFnError::ServerError("音频不存在或已过期，请重新生成".to_string())
+    })?;
+    let pcm = decode_clip(&clip.bytes)?;
+    let data = tokio::task::spawn_blocking(move || {
+        audio::fft::spectrogram(&pcm, SPECTROGRAM_COLUMNS, SPECTROGRAM_BANDS, 80.0)
+    })
+    .await
+    .map_err(|e| -> ServerFnError {
+        ServerFnError::ServerError(format!("计算语谱图失败: {}", e))
+    })?;
+    Ok(Spectrogram {
+        columns: SPECTROGRAM_COLUMNS,
+        bands: SPECTROGRAM_BANDS,
+        data,
+    })
 }
 
+/// 合成计划
 #[cfg(not(target_arch = "wasm32"))]
-#[derive(Deserialize, Debug)]
-struct DashScopeResponse {
-    // status_code 在 HTTP 层处理，这里解析 body 里的字段
-    code: Option<String>,
-    message: Option<String>,
-    _request_id: Option<String>,
-    output: Option<DashScopeOutput>,
+struct Plan {
+    /// 依次送去合成的分段与停顿
+    segments: Vec<Segment>,
+    /// 原文 (SSML 渲染后、词典替换前) 按句切分的结果，用于对齐高亮
+    sentences: Vec<String>,
+    /// 与 `segments` 中的 Speech 分段一一对应，见 [`crate::text::Chunk::sentences`]
+    spans: Vec<Vec<(usize, usize)>>,
+    applied: Vec<AppliedEntry>,
 }
 
+/// 把输入文本展开成合成计划：SSML 按标签渲染，按句切分后依次应用发音词典和文本规范化，
+/// 再合并成不超过长度上限的分段
 #[cfg(not(target_arch = "wasm32"))]
-#[derive(Deserialize, Debug)]
-struct DashScopeOutput {
-    audio: Option<DashScopeAudio>,
+fn plan_segments(
+    text: &str,
+    lexicon: &[LexiconEntry],
+    options: &NormalizeOptions,
+) -> Result<Plan, ServerFnError> {
+    let segments = if ssml::looks_like_ssml(text) {
+        ssml::parse(text).map_err(|e| -> ServerFnError {
+            ServerFnError::ServerError(format!("SSML 格式错误: {}", e))
+        })?
+    } else {
+        vec![Segment::Speech {
+            text: text.to_string(),
+            rate: 1.0,
+            pitch: 0.0,
+        }]
+    };
+
+    let mut plan = Plan {
+        segments: Vec::new(),
+        sentences: Vec::new(),
+        spans: Vec::new(),
+        applied: Vec::new(),
+    };
+    for segment in segments {
+        match segment {
+            Segment::Speech { text, rate, pitch } => {
+                let mut spoken = Vec::new();
+                for sentence in crate::text::split_sentences(&text) {
+                    let (rewritten, hits) = lexicon_rules::apply(&sentence, lexicon);
+                    let rewritten = normalize::normalize(&rewritten, options);
+                    for hit in hits {
+                        match plan.applied.iter_mut().find(|a| a.word == hit.word) {
+                            Some(a) => a.count += hit.count,
+                            None => plan.applied.push(hit),
+                        }
+                    }
+                    if !rewritten.trim().is_empty() {
+                        spoken.push(rewritten);
+                        plan.sentences.push(sentence);
+                    }
+                }
+                // 分段里的句子序号相对本段，换算成全局序号
+                let base = plan.sentences.len() - spoken.len();
+                for chunk in crate::text::chunk_sentences(&spoken, MAX_CHUNK_CHARS) {
+                    plan.segments.push(Segment::Speech {
+                        text: chunk.text,
+                        rate,
+                        pitch,
+                    });
+                    plan.spans.push(
+                        chunk
+                            .sentences
+                            .into_iter()
+                            .map(|(index, chars)| (base + index, chars))
+                            .collect(),
+                    );
+                }
+            }
+            pause => plan.segments.push(pause),
+        }
+    }
+    Ok(plan)
 }
 
+/// 把一个分段的时长按各句字数分给其中的句子，更新每句的起止时间
 #[cfg(not(target_arch = "wasm32"))]
-#[derive(Deserialize, Debug)]
-struct DashScopeAudio {
-    url: Option<String>,
+fn align_sentences(
+    times: &mut [Option<(f32, f32)>],
+    span: &[(usize, usize)],
+    start: f32,
+    duration: f32,
+) {
+    let total = span.iter().map(|&(_, chars)| chars).sum::<usize>().max(1) as f32;
+    let mut offset = 0;
+    for &(index, chars) in span {
+        let from = start + duration * offset as f32 / total;
+        offset += chars;
+        let to = start + duration * offset as f32 / total;
+        if let Some(time) = times.get_mut(index) {
+            *time = Some(match *time {
+                // 被硬切到多个分段的长句：合并起止时间
+                Some((begin, _)) => (begin, to),
+                None => (from, to),
+            });
+        }
+    }
 }
 
-// --- 新增：生成音频 API ---
-#[server]
-pub async fn generate_audio(params: GenerateParams) -> Result<String, ServerFnError> {
-    let api_key = std::env::var("ALIYUN_API_KEY").unwrap_or("".into());
-    debug_log!("使用阿里云 API Key: {}", &api_key);
-
-    let url =
-        "https://dashscope.aliyuncs.com/api/v1/services/aigc/multimodal-generation/generation";
-
-    // 1. 构造请求 Payload
-    // 注意：阿里云 Qwen-TTS 模型暂时可能忽略 pitch/speed/emotion 参数，
-    // 这里我们仅传递核心的 text 和 voice
-    let request_body = DashScopeRequest {
-        model: "qwen3-tts-flash".to_string(),
-        input: DashScopeInput {
-            text: params.text,
-            voice: params.voice_id,
-            language_type: Some("Auto".to_string()),
-        },
-        parameters: DashScopeParameters {},
+/// 分段合成一段文本 (支持 SSML)，多段时拼接为一个 WAV，同时给出文本对齐信息。
+/// 参数中的语速、音高叠加到每一段 (包括 SSML `prosody` 指定的值) 之上
+#[cfg(not(target_arch = "wasm32"))]
+async fn synthesize_text(
+    params: &GenerateParams,
+    report: &impl Fn(JobEvent),
+) -> Result<(SynthesizedAudio, Alignment), ServerFnError> {
+    let lexicon = lexicon::current_entries().await?;
+    let Plan {
+        segments,
+        sentences,
+        spans,
+        ..
+    } = plan_segments(&params.text, &lexicon, &params.normalize)?;
+    let base_rate = params.speed.clamp(0.5, 2.0);
+    let base_pitch = params.pitch.clamp(-12.0, 12.0);
+    let total = spans.len();
+    if total == 0 {
+        return Err(ServerFnError::ServerError("输入文本为空".to_string()));
+    }
+    check_language(&params.voice_id, params.language).await?;
+    report(JobEvent::Started { total });
+
+    let provider = DashScope::from_env();
+    let caps = provider.capabilities();
+    let router = LanguageRouter {
+        params,
+        custom_voice: cloning::is_custom_voice(&params.voice_id).await?,
+        voices: get_voices().await?,
     };
 
-    let client = Client::new();
-
-    // 2. 发送 POST 请求到阿里云
-    let response = client
-        .post(url)
-        .header("Authorization", format!("Bearer {}", api_key)) // 注意：阿里云是 Bearer Space Token
-        .header("Content-Type", "application/json")
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(|e| -> ServerFnError {
-            ServerFnError::ServerError(format!("Request failed: {}", e))
-        })?;
+    // 合成结果：音频及服务商未能处理、需要本地补上的语速/音高
+    enum Rendered {
+        Speech {
+            audio: SynthesizedAudio,
+            rate: f32,
+            pitch: f32,
+        },
+        Pause(f32),
+    }
 
-    // 检查 HTTP 状态码
-    if !response.status().is_success() {
-        let status = response.status();
-        let text = response.text().await.unwrap_or_default();
-        return Err(ServerFnError::ServerError(format!(
-            "API HTTP Error {}: {}",
-            status, text
-        )));
+    let mut rendered = Vec::with_capacity(segments.len());
+    let mut index = 0;
+    for segment in segments {
+        let (text, rate, pitch) = match segment {
+            Segment::Speech { text, rate, pitch } => (text, rate * base_rate, pitch + base_pitch),
+            Segment::Break(secs) => {
+                rendered.push(Rendered::Pause(secs));
+                continue;
+            }
+        };
+        report(JobEvent::ChunkStarted { index });
+        match synthesize_parts(&provider, router.parts(&text), rate, pitch).await {
+            Ok(audio) => {
+                report(JobEvent::ChunkDone { index });
+                rendered.push(Rendered::Speech {
+                    audio,
+                    rate: if caps.rate { 1.0 } else { rate },
+                    pitch: if caps.pitch { 0.0 } else { pitch },
+                });
+            }
+            Err(e) => {
+                report(JobEvent::ChunkFailed {
+                    index,
+                    message: error_message(&e),
+                });
+                return Err(e);
+            }
+        }
+        index += 1;
     }
 
-    // 3. 解析 JSON 响应
-    let dash_res: DashScopeResponse = response.json().await.map_err(|e| -> ServerFnError {
-        ServerFnError::ServerError(format!("Parse JSON failed: {}", e))
-    })?;
+    let mut sentence_times = vec![None; sentences.len()];
+    let mut words = Vec::new();
+    let into_alignment = |times: Vec<Option<(f32, f32)>>, words| Alignment {
+        sentences: sentences
+            .iter()
+            .zip(times)
+            .filter_map(|(text, time)| {
+                time.map(|(start, end)| TimedText {
+                    text: text.clone(),
+                    start,
+                    end,
+                })
+            })
+            .collect(),
+        words,
+    };
 
-    // 检查业务错误码 (code 字段非空通常表示错误)
-    if let Some(code) = &dash_res.code {
-        if !code.is_empty() {
-            let msg = dash_res.message.unwrap_or_default();
-            return Err(ServerFnError::ServerError(format!(
-                "DashScope Error {}: {}",
-                code, msg
-            )));
+    // 只有一段且无需处理时原样返回，否则解码为 PCM 处理后拼接成一个 WAV
+    if let [Rendered::Speech { rate, pitch, .. }] = rendered.as_slice() {
+        if *rate == 1.0 && *pitch == 0.0 {
+            if let Some(Rendered::Speech { audio, .. }) = rendered.pop() {
+                // 非 WAV 音频无法得知时长，此时不给出句子时间戳
+                if let Ok(pcm) = audio::decode_wav(&audio.bytes) {
+                    align_sentences(&mut sentence_times, &spans[0], 0.0, pcm.duration());
+                }
+                let words = audio.words.clone();
+                return Ok((audio, into_alignment(sentence_times, words)));
+            }
         }
     }
 
-    // 4. 获取音频 URL 并下载
-    // 阿里云非流式接口返回的是一个临时的 OSS URL
-    if let Some(output) = dash_res.output {
-        if let Some(audio) = output.audio {
-            if let Some(audio_url) = audio.url {
-                // 后端下载音频文件，避免前端跨域问题，并保持接口返回格式一致
-                let audio_resp =
-                    client
-                        .get(&audio_url)
-                        .send()
-                        .await
-                        .map_err(|e| -> ServerFnError {
-                            ServerFnError::ServerError(format!("Download audio failed: {}", e))
-                        })?;
-
-                let audio_bytes = audio_resp.bytes().await.map_err(|e| -> ServerFnError {
-                    ServerFnError::ServerError(format!("Read audio bytes failed: {}", e))
-                })?;
-
-                // 5. 转换为 Base64 Data URI
-                // 阿里云返回的 URL 通常包含扩展名，或者默认为 wav
-                let content_type = if audio_url.contains(".mp3") {
-                    "audio/mp3"
-                } else {
-                    "audio/wav"
-                };
-                let base64_data = general_purpose::STANDARD.encode(&audio_bytes);
-                let data_uri = format!("data:{};base64,{}", content_type, base64_data);
-
-                return Ok(data_uri);
+    let merge_err = |e: audio::AudioError| -> ServerFnError {
+        ServerFnError::ServerError(format!("Merge audio failed: {}", e))
+    };
+    let mut merged: Option<audio::Pcm> = None;
+    let mut pending_pause = 0.0;
+    let mut spans = spans.iter();
+    for part in rendered {
+        let (audio, rate, pitch) = match part {
+            Rendered::Pause(secs) => {
+                pending_pause += secs;
+                continue;
             }
+            Rendered::Speech { audio, rate, pitch } => (audio, rate, pitch),
+        };
+        let pcm = audio::dsp::pitch_shift(
+            &audio::dsp::time_stretch(&decode_clip(&audio.bytes)?, rate),
+            pitch,
+        );
+        let pause = audio::Pcm::silence(pcm.sample_rate, pcm.channels, pending_pause);
+        pending_pause = 0.0;
+        let merged =
+            merged.get_or_insert_with(|| audio::Pcm::silence(pcm.sample_rate, pcm.channels, 0.0));
+        merged.append(&pause).map_err(merge_err)?;
+
+        // 本段在拼接结果中的起点；本地变速后词级时间戳按倍率缩放
+        let start = merged.duration();
+        if let Some(span) = spans.next() {
+            align_sentences(&mut sentence_times, span, start, pcm.duration());
         }
+        words.extend(audio.words.into_iter().map(|word| TimedText {
+            start: start + word.start / rate,
+            end: start + word.end / rate,
+            ..word
+        }));
+        merged.append(&pcm).map_err(merge_err)?;
+    }
+    let Some(mut merged) = merged else {
+        return Err(ServerFnError::ServerError("没有可拼接的音频".to_string()));
+    };
+    // 结尾的停顿同样保留
+    let tail = audio::Pcm::silence(merged.sample_rate, merged.channels, pending_pause);
+    merged.append(&tail).map_err(merge_err)?;
+
+    let audio = SynthesizedAudio {
+        bytes: audio::encode_wav(&merged),
+        content_type: "audio/wav",
+        words: Vec::new(),
+    };
+    Ok((audio, into_alignment(sentence_times, words)))
+}
+
+/// 分段内单一语言的一次合成请求
+#[cfg(not(target_arch = "wasm32"))]
+struct Part {
+    text: String,
+    voice: String,
+    custom_voice: bool,
+    language: SynthesisLanguage,
+}
+
+/// 决定每个分段用什么语言、由哪个声线朗读
+#[cfg(not(target_arch = "wasm32"))]
+struct LanguageRouter<'a> {
+    params: &'a GenerateParams,
+    custom_voice: bool,
+    /// 声线库，用于查询声线支持的语言
+    voices: Vec<VoiceOption>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl LanguageRouter<'_> {
+    /// 声线能否朗读某种语言；方言声线也能朗读中文文本
+    fn can_speak(&self, voice_id: &str, language: SynthesisLanguage) -> bool {
+        let languages = self
+            .voices
+            .iter()
+            .find(|v| v.id == voice_id)
+            .map(|v| v.languages.as_slice())
+            .unwrap_or_default();
+        language::voice_supports(languages, language)
+            || (language == SynthesisLanguage::Chinese && languages.iter().any(|l| l.is_dialect()))
     }
 
-    Err(ServerFnError::ServerError(
-        "No audio URL found in response".to_string(),
-    ))
+    /// 指定了语言时整段使用该语言；自动识别时按语言切开，用户修正过的片段以修正为准。
+    /// 所选声线不会的语言交给声线库中第一个支持它的声线，都不支持时仍用原声线自动识别
+    fn parts(&self, text: &str) -> Vec<Part> {
+        let params = self.params;
+        let own = |text: String, language| Part {
+            text,
+            voice: params.voice_id.clone(),
+            custom_voice: self.custom_voice,
+            language,
+        };
+        if params.language != SynthesisLanguage::Auto {
+            return vec![own(text.to_string(), params.language)];
+        }
+        detect::segment(text)
+            .into_iter()
+            .map(|span| {
+                let language = params
+                    .language_overrides
+                    .iter()
+                    .find(|o| o.text == span.text)
+                    .map_or(span.language, |o| o.language);
+                if language == SynthesisLanguage::Auto || self.can_speak(&params.voice_id, language)
+                {
+                    return own(span.text, language);
+                }
+                match self.voices.iter().find(|v| v.supports(language)) {
+                    Some(voice) => Part {
+                        text: span.text,
+                        voice: voice.id.clone(),
+                        custom_voice: false,
+                        language,
+                    },
+                    None => own(span.text, SynthesisLanguage::Auto),
+                }
+            })
+            .collect()
+    }
+}
+
+/// 依次合成一个分段内的各语言片段；多于一段时解码后拼接为一个 WAV，词级时间戳随之平移
+#[cfg(not(target_arch = "wasm32"))]
+async fn synthesize_parts(
+    provider: &DashScope,
+    parts: Vec<Part>,
+    rate: f32,
+    pitch: f32,
+) -> Result<SynthesizedAudio, ServerFnError> {
+    let single = parts.len() == 1;
+    let mut merged: Option<audio::Pcm> = None;
+    let mut words = Vec::new();
+    for part in parts {
+        let request = SynthesisRequest {
+            text: &part.text,
+            voice: &part.voice,
+            custom_voice: part.custom_voice,
+            language_type: Some(part.language.language_type()),
+            rate,
+            pitch,
+        };
+        let audio = provider.synthesize(request).await?;
+        if single {
+            return Ok(audio);
+        }
+        let pcm = decode_clip(&audio.bytes)?;
+        let merged =
+            merged.get_or_insert_with(|| audio::Pcm::silence(pcm.sample_rate, pcm.channels, 0.0));
+        let start = merged.duration();
+        words.extend(audio.words.into_iter().map(|word| TimedText {
+            start: start + word.start,
+            end: start + word.end,
+            ..word
+        }));
+        merged.append(&pcm).map_err(|e| -> ServerFnError {
+            ServerFnError::ServerError(format!("Merge audio failed: {}", e))
+        })?;
+    }
+    let Some(merged) = merged else {
+        return Err(ServerFnError::ServerError("没有可合成的文本".to_string()));
+    };
+    Ok(SynthesizedAudio {
+        bytes: audio::encode_wav(&merged),
+        content_type: "audio/wav",
+        words,
+    })
+}
+
+/// 合成前检查声线与语言的组合：方言只能由对应的方言声线合成。
+/// 不在声线库中的声线 (如复刻声线) 只支持普通语言
+#[cfg(not(target_arch = "wasm32"))]
+async fn check_language(voice_id: &str, language: SynthesisLanguage) -> Result<(), ServerFnError> {
+    let voices = get_voices().await?;
+    let languages = voices
+        .iter()
+        .find(|v| v.id == voice_id)
+        .map(|v| v.languages.as_slice())
+        .unwrap_or_default();
+    if language::voice_supports(languages, language) {
+        Ok(())
+    } else {
+        Err(ServerFnError::ServerError(format!(
+            "所选声线不支持「{}」，请换用对应的声线",
+            language.native_name().unwrap_or(language.language_type())
+        )))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn decode_clip(bytes: &[u8]) -> Result<audio::Pcm, ServerFnError> {
+    audio::decode_wav(bytes).map_err(|e| -> ServerFnError {
+        ServerFnError::ServerError(format!("Decode audio failed: {}", e))
+    })
+}
+
+/// 依次拼接多段音频，段与段之间插入 `gap_secs` 秒静音
+#[cfg(not(target_arch = "wasm32"))]
+fn concat_pcm(parts: &[audio::Pcm], gap_secs: f32) -> Result<audio::Pcm, ServerFnError> {
+    let Some(first) = parts.first() else {
+        return Err(ServerFnError::ServerError("没有可拼接的音频".to_string()));
+    };
+    let merge_err = |e: audio::AudioError| -> ServerFnError {
+        ServerFnError::ServerError(format!("Merge audio failed: {}", e))
+    };
+    let gap = audio::Pcm::silence(first.sample_rate, first.channels, gap_secs);
+    let mut merged = first.clone();
+    for part in &parts[1..] {
+        merged.append(&gap).map_err(merge_err)?;
+        merged.append(part).map_err(merge_err)?;
+    }
+    Ok(merged)
+}
+
+// --- 对白模式：逐句合成，再按间隔拼接成一条音轨 ---
+#[server(client = CsrfClient)]
+pub async fn generate_dialogue_line(
+    voice_id: String,
+    text: String,
+) -> Result<String, ServerFnError> {
+    let params = GenerateParams::new(text, voice_id, VoiceParams::default());
+    let (audio, _) = synthesize_text(&params, &|_| {}).await?;
+    Ok(crate::clips::insert(audio.content_type, audio.bytes))
+}
+
+#[server(client = CsrfClient)]
+pub async fn mix_dialogue(clip_ids: Vec<String>, gap_ms: u32) -> Result<String, ServerFnError> {
+    let mut pcms = Vec::with_capacity(clip_ids.len());
+    for id in &clip_ids {
+        let clip = crate::clips::get(id).ok_or_else(|| -> ServerFnError {
+            ServerFnError::ServerError(format!("音频片段 {} 不存在或已过期", id))
+        })?;
+        pcms.push(decode_clip(&clip.bytes)?);
+    }
+    let mixed = concat_pcm(&pcms, gap_ms as f32 / 1000.0)?;
+    Ok(crate::clips::insert("audio/wav", audio::encode_wav(&mixed)))
 }
@@ -0,0 +1,167 @@
+//! 声音复刻：用户上传 (或录制) 参考音频并确认授权后注册自定义声线，按用户保存
+#[cfg(not(target_arch = "wasm32"))]
+use super::{
+    dashscope::DashScope,
+    provider::{EnrollmentRequest, TtsProvider},
+};
+use crate::security::CsrfClient;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{clips, session, store};
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 参考音频的时长范围 (秒)
+pub const MIN_SAMPLE_SECS: f32 = 5.0;
+pub const MAX_SAMPLE_SECS: f32 = 60.0;
+
+/// 声线名称的最大字符数
+pub const MAX_VOICE_NAME_CHARS: usize = 16;
+
+/// 每个用户最多保存的自定义声线数
+#[cfg(not(target_arch = "wasm32"))]
+const MAX_CUSTOM_VOICES: usize = 5;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CustomVoice {
+    /// 服务商返回的声线 ID，合成时直接使用
+    pub id: String,
+    pub name: String,
+    /// 用户确认授权的时间 (Unix 秒)
+    pub consented_at: u64,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn voices_file() -> Result<String, ServerFnError> {
+    Ok(format!("voices/users/{}", session::user_id().await?))
+}
+
+/// 当前用户的自定义声线中是否有 `voice_id`
+#[cfg(not(target_arch = "wasm32"))]
+pub(super) async fn is_custom_voice(voice_id: &str) -> Result<bool, ServerFnError> {
+    let voices: Vec<CustomVoice> = store::read(&voices_file().await?).await;
+    Ok(voices.iter().any(|v| v.id == voice_id))
+}
+
+#[server(client = CsrfClient)]
+pub async fn list_custom_voices() -> Result<Vec<CustomVoice>, ServerFnError> {
+    Ok(store::read(&voices_file().await?).await)
+}
+
+/// 当前服务商是否支持声音复刻
+#[server(client = CsrfClient)]
+pub async fn voice_cloning_available() -> Result<bool, ServerFnError> {
+    Ok(DashScope::from_env().capabilities().voice_cloning)
+}
+
+/// 用片段库中的参考音频注册自定义声线。`consent` 表示用户已确认本人声音或已获得授权
+#[server(client = CsrfClient)]
+pub async fn enroll_custom_voice(
+    clip_id: String,
+    name: String,
+    consent: bool,
+) -> Result<CustomVoice, ServerFnError> {
+    if !consent {
+        return Err(ServerFnError::ServerError(
+            "请先确认这是你本人的声音，或已获得声音主人的授权".to_string(),
+        ));
+    }
+    let name = name.trim().to_string();
+    if name.is_empty() || name.chars().count() > MAX_VOICE_NAME_CHARS {
+        return Err(ServerFnError::ServerError(format!(
+            "声线名称需为 1~{} 个字符",
+            MAX_VOICE_NAME_CHARS
+        )));
+    }
+
+    let file = voices_file().await?;
+    let voices: Vec<CustomVoice> = store::read(&file).await;
+    if voices.len() >= MAX_CUSTOM_VOICES {
+        return Err(too_many_voices());
+    }
+
+    let clip = clips::get(&clip_id).ok_or_else(|| -> ServerFnError {
+        ServerFnError::ServerError("参考音频不存在或已过期，请重新上传".to_string())
+    })?;
+    let duration = super::decode_clip(&clip.bytes)?.duration();
+    if !(MIN_SAMPLE_SECS..=MAX_SAMPLE_SECS).contains(&duration) {
+        return Err(ServerFnError::ServerError(format!(
+            "参考音频需为 {}~{} 秒，当前 {:.1} 秒",
+            MIN_SAMPLE_SECS, MAX_SAMPLE_SECS, duration
+        )));
+    }
+
+    let provider = DashScope::from_env();
+    if !provider.capabilities().voice_cloning {
+        return Err(ServerFnError::ServerError(
+            "当前服务商不支持声音复刻".to_string(),
+        ));
+    }
+    // 服务商侧的名称只允许字母数字，用随机串代替用户填写的名称
+    let provider_name = format!("u{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+    let id = provider
+        .enroll_voice(EnrollmentRequest {
+            name: &provider_name,
+            audio: &clip.bytes,
+            content_type: clip.content_type,
+        })
+        .await?;
+
+    let voice = CustomVoice {
+        id,
+        name,
+        consented_at: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default(),
+    };
+    // 注册期间可能有并发请求加入了声线，写入前再检查一次数量
+    let saved = voice.clone();
+    let result = store::update(&file, move |voices: &mut Vec<CustomVoice>| {
+        if voices.len() >= MAX_CUSTOM_VOICES {
+            return Err(too_many_voices());
+        }
+        voices.push(saved);
+        Ok(())
+    })
+    .await
+    .map_err(save_failed)
+    .and_then(|result| result);
+    if let Err(e) = result {
+        // 没保存下来的声线也要从服务商处删掉，否则用户再也看不到、删不掉它
+        if let Err(cleanup) = provider.delete_voice(&voice.id).await {
+            leptos::logging::warn!("删除未保存的声线 {} 失败: {}", voice.id, cleanup);
+        }
+        return Err(e);
+    }
+    Ok(voice)
+}
+
+/// 删除自定义声线 (同时从服务商处删除)
+#[server(client = CsrfClient)]
+pub async fn delete_custom_voice(id: String) -> Result<(), ServerFnError> {
+    let file = voices_file().await?;
+    let voices: Vec<CustomVoice> = store::read(&file).await;
+    if !voices.iter().any(|v| v.id == id) {
+        return Err(ServerFnError::ServerError("声线不存在".to_string()));
+    }
+    DashScope::from_env().delete_voice(&id).await?;
+    store::update(&file, move |voices: &mut Vec<CustomVoice>| {
+        voices.retain(|v| v.id != id);
+        Ok(())
+    })
+    .await
+    .map_err(save_failed)?
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn too_many_voices() -> ServerFnError {
+    ServerFnError::ServerError(format!(
+        "最多保存 {} 个自定义声线，请先删除不用的声线",
+        MAX_CUSTOM_VOICES
+    ))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn save_failed(e: std::io::Error) -> ServerFnError {
+    ServerFnError::ServerError(format!("保存声线失败: {}", e))
+}
@@ -0,0 +1,286 @@
+//! 阿里云 DashScope (通义千问 TTS) 接口封装
+use super::provider::{
+    Capabilities, EnrollmentRequest, SynthesisRequest, SynthesizedAudio, TtsProvider,
+};
+use base64::{engine::general_purpose, Engine as _};
+use leptos::logging::debug_log;
+use leptos::prelude::ServerFnError;
+use reqwest::Client;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+const GENERATION_URL: &str =
+    "https://dashscope.aliyuncs.com/api/v1/services/aigc/multimodal-generation/generation";
+const CUSTOMIZATION_URL: &str =
+    "https://dashscope.aliyuncs.com/api/v1/services/audio/tts/customization";
+
+/// 系统声线使用的合成模型
+const TTS_MODEL: &str = "qwen3-tts-flash";
+/// 声音复刻 (注册自定义声线) 模型
+const ENROLLMENT_MODEL: &str = "qwen-voice-enrollment";
+
+#[derive(Serialize)]
+struct DashScopeRequest {
+    model: String,
+    input: DashScopeInput,
+    parameters: DashScopeParameters,
+}
+
+#[derive(Serialize)]
+struct DashScopeInput {
+    text: String,
+    voice: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language_type: Option<String>,
+}
+
+#[derive(Serialize)]
+struct DashScopeParameters {
+    // 这里的参数根据模型不同而不同，qwen3-tts-flash 文档主要强调 input
+    // 我们可以预留 sample_rate 或 format，但在文档示例中未强制要求
+}
+
+#[derive(Deserialize, Debug)]
+struct DashScopeResponse {
+    // status_code 在 HTTP 层处理，这里解析 body 里的字段
+    code: Option<String>,
+    message: Option<String>,
+    _request_id: Option<String>,
+    output: Option<DashScopeOutput>,
+}
+
+#[derive(Deserialize, Debug)]
+struct DashScopeOutput {
+    audio: Option<DashScopeAudio>,
+}
+
+#[derive(Deserialize, Debug)]
+struct DashScopeAudio {
+    url: Option<String>,
+}
+
+#[derive(Serialize)]
+struct EnrollmentBody<'a> {
+    model: &'static str,
+    input: EnrollmentInput<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+enum EnrollmentInput<'a> {
+    Create {
+        target_model: &'a str,
+        preferred_name: &'a str,
+        audio: EnrollmentAudio,
+    },
+    Delete {
+        voice: &'a str,
+    },
+}
+
+#[derive(Serialize)]
+struct EnrollmentAudio {
+    /// Data URI 形式的参考音频
+    data: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct EnrollmentResponse {
+    code: Option<String>,
+    message: Option<String>,
+    output: Option<EnrollmentOutput>,
+}
+
+#[derive(Deserialize, Debug)]
+struct EnrollmentOutput {
+    voice: Option<String>,
+}
+
+pub struct DashScope {
+    client: Client,
+    api_key: String,
+    /// 复刻声线的合成模型，未配置时不提供声音复刻
+    vc_model: Option<String>,
+}
+
+impl DashScope {
+    /// 从环境变量 `ALIYUN_API_KEY` 读取密钥，`DASHSCOPE_VC_MODEL` 读取复刻声线的合成模型
+    pub fn from_env() -> Self {
+        let api_key = std::env::var("ALIYUN_API_KEY").unwrap_or("".into());
+        debug_log!("使用阿里云 API Key: {}", &api_key);
+        DashScope {
+            client: Client::new(),
+            api_key,
+            vc_model: std::env::var("DASHSCOPE_VC_MODEL")
+                .ok()
+                .filter(|m| !m.is_empty()),
+        }
+    }
+
+    fn vc_model(&self) -> Result<&str, ServerFnError> {
+        self.vc_model.as_deref().ok_or_else(|| {
+            ServerFnError::ServerError("未配置 DASHSCOPE_VC_MODEL，无法使用复刻声线".to_string())
+        })
+    }
+}
+
+impl TtsProvider for DashScope {
+    // qwen3-tts-flash 不支持 SSML，也不接受语速/音高参数
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            voice_cloning: self.vc_model.is_some(),
+            ..Capabilities::default()
+        }
+    }
+
+    async fn synthesize(
+        &self,
+        request: SynthesisRequest<'_>,
+    ) -> Result<SynthesizedAudio, ServerFnError> {
+        // 复刻声线只能用注册时指定的模型合成
+        let model = if request.custom_voice {
+            self.vc_model()?
+        } else {
+            TTS_MODEL
+        };
+        synthesize(&self.client, &self.api_key, model, &request).await
+    }
+
+    async fn enroll_voice(&self, request: EnrollmentRequest<'_>) -> Result<String, ServerFnError> {
+        let data = format!(
+            "data:{};base64,{}",
+            request.content_type,
+            general_purpose::STANDARD.encode(request.audio)
+        );
+        let body = EnrollmentBody {
+            model: ENROLLMENT_MODEL,
+            input: EnrollmentInput::Create {
+                target_model: self.vc_model()?,
+                preferred_name: request.name,
+                audio: EnrollmentAudio { data },
+            },
+        };
+        let response: EnrollmentResponse =
+            post_json(&self.client, &self.api_key, CUSTOMIZATION_URL, &body).await?;
+        check_code(response.code, response.message)?;
+        response
+            .output
+            .and_then(|o| o.voice)
+            .ok_or_else(|| ServerFnError::ServerError("No voice found in response".to_string()))
+    }
+
+    async fn delete_voice(&self, voice: &str) -> Result<(), ServerFnError> {
+        let body = EnrollmentBody {
+            model: ENROLLMENT_MODEL,
+            input: EnrollmentInput::Delete { voice },
+        };
+        let response: EnrollmentResponse =
+            post_json(&self.client, &self.api_key, CUSTOMIZATION_URL, &body).await?;
+        check_code(response.code, response.message)
+    }
+}
+
+/// POST JSON 并解析 JSON 响应，HTTP 层面的错误直接返回
+async fn post_json<T: Serialize, R: DeserializeOwned>(
+    client: &Client,
+    api_key: &str,
+    url: &str,
+    body: &T,
+) -> Result<R, ServerFnError> {
+    let response = client
+        .post(url)
+        .header("Authorization", format!("Bearer {}", api_key)) // 注意：阿里云是 Bearer Space Token
+        .header("Content-Type", "application/json")
+        .json(body)
+        .send()
+        .await
+        .map_err(|e| -> ServerFnError {
+            ServerFnError::ServerError(format!("Request failed: {}", e))
+        })?;
+
+    // 检查 HTTP 状态码
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(ServerFnError::ServerError(format!(
+            "API HTTP Error {}: {}",
+            status, text
+        )));
+    }
+
+    response.json().await.map_err(|e| -> ServerFnError {
+        ServerFnError::ServerError(format!("Parse JSON failed: {}", e))
+    })
+}
+
+/// 检查业务错误码 (code 字段非空通常表示错误)
+fn check_code(code: Option<String>, message: Option<String>) -> Result<(), ServerFnError> {
+    match code {
+        Some(code) if !code.is_empty() => Err(ServerFnError::ServerError(format!(
+            "DashScope Error {}: {}",
+            code,
+            message.unwrap_or_default()
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// 调用 DashScope 合成一段文本，并把返回的临时音频下载下来
+async fn synthesize(
+    client: &Client,
+    api_key: &str,
+    model: &str,
+    request: &SynthesisRequest<'_>,
+) -> Result<SynthesizedAudio, ServerFnError> {
+    // 1. 构造请求 Payload
+    // 注意：阿里云 Qwen-TTS 模型暂时可能忽略 pitch/speed/emotion 参数，
+    // 这里我们仅传递核心的 text 和 voice
+    let request_body = DashScopeRequest {
+        model: model.to_string(),
+        input: DashScopeInput {
+            text: request.text.to_string(),
+            voice: request.voice.to_string(),
+            language_type: request.language_type.map(str::to_string),
+        },
+        parameters: DashScopeParameters {},
+    };
+
+    // 2. 发送 POST 请求到阿里云，3. 解析 JSON 响应
+    let dash_res: DashScopeResponse =
+        post_json(client, api_key, GENERATION_URL, &request_body).await?;
+    check_code(dash_res.code, dash_res.message)?;
+
+    // 4. 获取音频 URL 并下载
+    // 阿里云非流式接口返回的是一个临时的 OSS URL
+    let Some(audio_url) = dash_res.output.and_then(|o| o.audio).and_then(|a| a.url) else {
+        return Err(ServerFnError::ServerError(
+            "No audio URL found in response".to_string(),
+        ));
+    };
+
+    // 后端下载音频文件，避免前端跨域问题，并保持接口返回格式一致
+    let audio_resp = client
+        .get(&audio_url)
+        .send()
+        .await
+        .map_err(|e| -> ServerFnError {
+            ServerFnError::ServerError(format!("Download audio failed: {}", e))
+        })?;
+
+    let audio_bytes = audio_resp.bytes().await.map_err(|e| -> ServerFnError {
+        ServerFnError::ServerError(format!("Read audio bytes failed: {}", e))
+    })?;
+
+    // 阿里云返回的 URL 通常包含扩展名，或者默认为 wav
+    let content_type = if audio_url.contains(".mp3") {
+        "audio/mp3"
+    } else {
+        "audio/wav"
+    };
+
+    // 接口不返回词级时间戳
+    Ok(SynthesizedAudio {
+        bytes: audio_bytes.to_vec(),
+        content_type,
+        words: Vec::new(),
+    })
+}
@@ -0,0 +1,73 @@
+//! 生成历史：记录当前用户最近合成的片段，供时间轴等页面复用
+use crate::security::CsrfClient;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{clips, session, store};
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 每个用户保留的历史条数
+#[cfg(not(target_arch = "wasm32"))]
+const MAX_HISTORY: usize = 50;
+
+/// 历史中保存的文本摘要长度 (字符)
+#[cfg(not(target_arch = "wasm32"))]
+const EXCERPT_CHARS: usize = 40;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub clip_id: String,
+    /// 合成文本的开头部分
+    pub text: String,
+    pub voice_id: String,
+    pub duration: f32,
+    /// 生成时间 (Unix 秒)
+    pub created_at: u64,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn history_file() -> Result<String, ServerFnError> {
+    Ok(format!("history/users/{}", session::user_id().await?))
+}
+
+/// 记录一次生成，最新的排在最前
+#[cfg(not(target_arch = "wasm32"))]
+pub(super) async fn record(
+    clip_id: &str,
+    text: &str,
+    voice_id: &str,
+    duration: f32,
+) -> Result<(), ServerFnError> {
+    let file = history_file().await?;
+    let mut entries: Vec<HistoryEntry> = store::load(&file);
+    let mut excerpt: String = text.chars().take(EXCERPT_CHARS).collect();
+    if text.chars().count() > EXCERPT_CHARS {
+        excerpt.push('…');
+    }
+    entries.insert(
+        0,
+        HistoryEntry {
+            clip_id: clip_id.to_string(),
+            text: excerpt,
+            voice_id: voice_id.to_string(),
+            duration,
+            created_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+        },
+    );
+    entries.truncate(MAX_HISTORY);
+    store::save(&file, &entries).map_err(|e| -> ServerFnError {
+        ServerFnError::ServerError(format!("保存历史失败: {}", e))
+    })
+}
+
+/// 最近生成且音频仍可取回的片段
+#[server(client = CsrfClient)]
+pub async fn list_recent_clips() -> Result<Vec<HistoryEntry>, ServerFnError> {
+    let entries: Vec<HistoryEntry> = store::load(&history_file().await?);
+    Ok(entries
+        .into_iter()
+        .filter(|e| clips::contains(&e.clip_id))
+        .collect())
+}
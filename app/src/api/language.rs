@@ -0,0 +1,111 @@
+//! 合成语言与方言
+//!
+//! 普通语言直接作为 DashScope 的 `language_type` 传入；方言没有单独的 `language_type`，
+//! 由专门的方言声线实现，因此选择方言时必须搭配支持该方言的声线。
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SynthesisLanguage {
+    /// 由服务商自动识别
+    #[default]
+    Auto,
+    Chinese,
+    English,
+    Japanese,
+    Korean,
+    /// 粤语
+    Cantonese,
+    /// 四川话
+    Sichuanese,
+    /// 上海话
+    Shanghainese,
+}
+
+impl SynthesisLanguage {
+    pub const ALL: [SynthesisLanguage; 8] = [
+        SynthesisLanguage::Auto,
+        SynthesisLanguage::Chinese,
+        SynthesisLanguage::English,
+        SynthesisLanguage::Japanese,
+        SynthesisLanguage::Korean,
+        SynthesisLanguage::Cantonese,
+        SynthesisLanguage::Sichuanese,
+        SynthesisLanguage::Shanghainese,
+    ];
+
+    /// 没有声明支持语言的声线 (如复刻声线) 默认支持的语言
+    pub const STANDARD: [SynthesisLanguage; 5] = [
+        SynthesisLanguage::Auto,
+        SynthesisLanguage::Chinese,
+        SynthesisLanguage::English,
+        SynthesisLanguage::Japanese,
+        SynthesisLanguage::Korean,
+    ];
+
+    /// 用于查询参数和下拉框的取值
+    pub fn code(self) -> &'static str {
+        match self {
+            SynthesisLanguage::Auto => "auto",
+            SynthesisLanguage::Chinese => "zh",
+            SynthesisLanguage::English => "en",
+            SynthesisLanguage::Japanese => "ja",
+            SynthesisLanguage::Korean => "ko",
+            SynthesisLanguage::Cantonese => "yue",
+            SynthesisLanguage::Sichuanese => "sc",
+            SynthesisLanguage::Shanghainese => "wuu",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<SynthesisLanguage> {
+        SynthesisLanguage::ALL
+            .into_iter()
+            .find(|language| language.code() == code)
+    }
+
+    /// 用该语言自身书写的名称；自动识别没有固定名称，由界面文案提供
+    pub fn native_name(self) -> Option<&'static str> {
+        match self {
+            SynthesisLanguage::Auto => None,
+            SynthesisLanguage::Chinese => Some("中文"),
+            SynthesisLanguage::English => Some("English"),
+            SynthesisLanguage::Japanese => Some("日本語"),
+            SynthesisLanguage::Korean => Some("한국어"),
+            SynthesisLanguage::Cantonese => Some("粵語"),
+            SynthesisLanguage::Sichuanese => Some("四川话"),
+            SynthesisLanguage::Shanghainese => Some("上海话"),
+        }
+    }
+
+    /// 是否为方言，方言只能由对应的方言声线合成
+    pub fn is_dialect(self) -> bool {
+        matches!(
+            self,
+            SynthesisLanguage::Cantonese
+                | SynthesisLanguage::Sichuanese
+                | SynthesisLanguage::Shanghainese
+        )
+    }
+
+    /// 传给 DashScope 的 `language_type`；方言按中文传入，口音由声线决定
+    pub fn language_type(self) -> &'static str {
+        match self {
+            SynthesisLanguage::Auto => "Auto",
+            SynthesisLanguage::English => "English",
+            SynthesisLanguage::Japanese => "Japanese",
+            SynthesisLanguage::Korean => "Korean",
+            SynthesisLanguage::Chinese
+            | SynthesisLanguage::Cantonese
+            | SynthesisLanguage::Sichuanese
+            | SynthesisLanguage::Shanghainese => "Chinese",
+        }
+    }
+}
+
+/// 声线是否支持某种语言；`languages` 为空表示只支持普通语言
+pub fn voice_supports(languages: &[SynthesisLanguage], language: SynthesisLanguage) -> bool {
+    if languages.is_empty() {
+        SynthesisLanguage::STANDARD.contains(&language)
+    } else {
+        language == SynthesisLanguage::Auto || languages.contains(&language)
+    }
+}
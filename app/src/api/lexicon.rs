@@ -0,0 +1,126 @@
+//! 发音词典的增删查，以及合成文本预览
+use crate::security::CsrfClient;
+use crate::text::{
+    lexicon::AppliedEntry, lexicon::LexiconEntry, normalize::NormalizeOptions, ssml::Segment,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{session, store, text::lexicon};
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LexiconScope {
+    /// 当前用户自己的词典
+    User,
+    /// 所有用户共享的词典
+    Global,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LexiconView {
+    pub user: Vec<LexiconEntry>,
+    pub global: Vec<LexiconEntry>,
+    /// 当前用户是否为管理员，可以编辑全局词典
+    pub global_editable: bool,
+}
+
+/// 实际送去合成的内容
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TextPreview {
+    pub segments: Vec<Segment>,
+    pub applied: Vec<AppliedEntry>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+const GLOBAL_LEXICON: &str = "lexicon/global";
+
+#[cfg(not(target_arch = "wasm32"))]
+fn user_lexicon(user_id: &str) -> String {
+    format!("lexicon/users/{}", user_id)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn lexicon_file(scope: LexiconScope) -> Result<String, ServerFnError> {
+    match scope {
+        LexiconScope::User => Ok(user_lexicon(&session::user_id().await?)),
+        LexiconScope::Global if session::is_admin().await => Ok(GLOBAL_LEXICON.to_string()),
+        LexiconScope::Global => Err(ServerFnError::ServerError(
+            "全局词典为只读，请联系管理员修改".to_string(),
+        )),
+    }
+}
+
+/// 在同一个锁内读取、修改并写回词典
+#[cfg(not(target_arch = "wasm32"))]
+async fn update_lexicon(
+    scope: LexiconScope,
+    f: impl FnOnce(&mut Vec<LexiconEntry>) + Send + 'static,
+) -> Result<(), ServerFnError> {
+    store::update(&lexicon_file(scope).await?, move |entries| {
+        f(entries);
+        Ok::<_, ServerFnError>(())
+    })
+    .await
+    .map_err(|e| -> ServerFnError {
+        ServerFnError::ServerError(format!("保存词典失败: {}", e))
+    })?
+}
+
+/// 当前用户生效的词典 (用户词典优先于全局词典)
+#[cfg(not(target_arch = "wasm32"))]
+pub(super) async fn current_entries() -> Result<Vec<LexiconEntry>, ServerFnError> {
+    let user: Vec<LexiconEntry> = store::read(&user_lexicon(&session::user_id().await?)).await;
+    let global: Vec<LexiconEntry> = store::read(GLOBAL_LEXICON).await;
+    Ok(lexicon::merge(&user, &global))
+}
+
+#[server(client = CsrfClient)]
+pub async fn get_lexicon() -> Result<LexiconView, ServerFnError> {
+    Ok(LexiconView {
+        user: store::read(&user_lexicon(&session::user_id().await?)).await,
+        global: store::read(GLOBAL_LEXICON).await,
+        global_editable: session::is_admin().await,
+    })
+}
+
+/// 输入管理员令牌 (`EARDO_ADMIN_TOKEN`) 后才能编辑全局词典
+#[server(client = CsrfClient)]
+pub async fn unlock_global_lexicon(token: String) -> Result<(), ServerFnError> {
+    session::sign_in_admin(token.trim()).await
+}
+
+/// 新增或更新词条 (按词匹配)
+#[server(client = CsrfClient)]
+pub async fn save_lexicon_entry(
+    scope: LexiconScope,
+    entry: LexiconEntry,
+) -> Result<(), ServerFnError> {
+    let entry = lexicon::normalize_entry(entry)
+        .map_err(|e| -> ServerFnError { ServerFnError::ServerError(e) })?;
+    update_lexicon(scope, move |entries| {
+        match entries.iter_mut().find(|e| e.word == entry.word) {
+            Some(existing) => *existing = entry,
+            None => entries.push(entry),
+        }
+    })
+    .await
+}
+
+#[server(client = CsrfClient)]
+pub async fn delete_lexicon_entry(scope: LexiconScope, word: String) -> Result<(), ServerFnError> {
+    update_lexicon(scope, move |entries| entries.retain(|e| e.word != word)).await
+}
+
+/// 预览经过 SSML 渲染、词典替换和文本规范化后真正送去合成的文本
+#[server(client = CsrfClient)]
+pub async fn preview_text(
+    text: String,
+    normalize: NormalizeOptions,
+) -> Result<TextPreview, ServerFnError> {
+    let entries = current_entries().await?;
+    let plan = super::plan_segments(&text, &entries, &normalize)?;
+    Ok(TextPreview {
+        segments: plan.segments,
+        applied: plan.applied,
+    })
+}
@@ -0,0 +1,46 @@
+//! 背景音乐：把合成结果与内置或上传的音乐混成一条
+use super::voicefilter::AudioClip;
+use crate::audio::mixer::{BuiltinBed, MixSettings};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::clips;
+use crate::security::CsrfClient;
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 背景音乐来源
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum MusicSource {
+    Builtin(BuiltinBed),
+    /// 通过 `upload_recording` 上传到片段库的音乐。
+    /// 上传接口只接受 WAV (见 [`crate::audio::decode_wav`])，压缩格式需先在客户端转换
+    Clip(String),
+}
+
+/// 把片段库中的人声与背景音乐混音，结果作为新片段返回
+#[server(client = CsrfClient)]
+pub async fn mix_background_music(
+    voice_clip_id: String,
+    music: MusicSource,
+    settings: MixSettings,
+) -> Result<AudioClip, ServerFnError> {
+    let load = |id: &str| {
+        clips::get(id).ok_or_else(|| -> ServerFnError {
+            ServerFnError::ServerError("音频不存在或已过期，请重新生成或上传".to_string())
+        })
+    };
+    let voice = super::decode_clip(&load(&voice_clip_id)?.bytes)?;
+    let music = match music {
+        MusicSource::Builtin(bed) => bed.render(voice.sample_rate),
+        MusicSource::Clip(id) => super::decode_clip(&load(&id)?.bytes)?,
+    };
+
+    // 混音计算量较大，放到阻塞线程池里执行
+    let mixed =
+        tokio::task::spawn_blocking(move || crate::audio::mixer::mix(&voice, &music, &settings))
+            .await
+            .map_err(|e| -> ServerFnError {
+                ServerFnError::ServerError(format!("混音失败: {}", e))
+            })?;
+
+    Ok(super::voicefilter::store_pcm(&mixed))
+}
@@ -0,0 +1,184 @@
+//! 声线预设：保存一组 "声线 + 参数"，按用户存储，可设为默认或通过链接分享
+use crate::pages::homepage::VoiceParams;
+use crate::security::CsrfClient;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{session, store};
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct VoicePreset {
+    pub name: String,
+    pub voice_id: String,
+    pub params: VoiceParams,
+    /// 打开首页时自动应用，每个用户最多一个
+    #[serde(default)]
+    pub is_default: bool,
+}
+
+/// 预设名的最大字符数
+pub const MAX_PRESET_NAME_CHARS: usize = 32;
+
+/// 分享链接中携带预设令牌的查询参数名
+pub const SHARE_QUERY_KEY: &str = "preset";
+
+/// 每个用户最多保存的预设数
+#[cfg(not(target_arch = "wasm32"))]
+const MAX_PRESETS: usize = 50;
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn presets_file() -> Result<String, ServerFnError> {
+    Ok(format!("presets/users/{}", session::user_id().await?))
+}
+
+/// 分享出去的预设按令牌单独存放，与分享者后续的修改无关
+#[cfg(not(target_arch = "wasm32"))]
+fn shared_file(token: &str) -> String {
+    format!("presets/shared/{}", token)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn validate_name(name: &str) -> Result<String, ServerFnError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(ServerFnError::ServerError("预设名不能为空".to_string()));
+    }
+    if name.chars().count() > MAX_PRESET_NAME_CHARS {
+        return Err(ServerFnError::ServerError(format!(
+            "预设名不能超过 {} 个字符",
+            MAX_PRESET_NAME_CHARS
+        )));
+    }
+    Ok(name.to_string())
+}
+
+/// 在同一个锁内读取、修改并写回当前用户的预设
+#[cfg(not(target_arch = "wasm32"))]
+async fn update_presets<R: Send + 'static>(
+    f: impl FnOnce(&mut Vec<VoicePreset>) -> Result<R, ServerFnError> + Send + 'static,
+) -> Result<R, ServerFnError> {
+    store::update(&presets_file().await?, f)
+        .await
+        .map_err(|e| -> ServerFnError {
+            ServerFnError::ServerError(format!("保存预设失败: {}", e))
+        })?
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn not_found(name: &str) -> ServerFnError {
+    ServerFnError::ServerError(format!("预设「{}」不存在", name))
+}
+
+#[server(client = CsrfClient)]
+pub async fn list_voice_presets() -> Result<Vec<VoicePreset>, ServerFnError> {
+    Ok(store::read(&presets_file().await?).await)
+}
+
+/// 保存预设，同名时覆盖 (保留原来的默认标记)
+#[server(client = CsrfClient)]
+pub async fn save_voice_preset(preset: VoicePreset) -> Result<(), ServerFnError> {
+    let name = validate_name(&preset.name)?;
+    update_presets(move |presets| {
+        match presets.iter_mut().find(|p| p.name == name) {
+            Some(existing) => {
+                *existing = VoicePreset {
+                    name,
+                    is_default: existing.is_default,
+                    ..preset
+                }
+            }
+            None => {
+                if presets.len() >= MAX_PRESETS {
+                    return Err(ServerFnError::ServerError(format!(
+                        "最多保存 {} 个预设，请先删除不用的预设",
+                        MAX_PRESETS
+                    )));
+                }
+                presets.push(VoicePreset {
+                    name,
+                    is_default: false,
+                    ..preset
+                });
+            }
+        }
+        Ok(())
+    })
+    .await
+}
+
+#[server(client = CsrfClient)]
+pub async fn rename_voice_preset(name: String, new_name: String) -> Result<(), ServerFnError> {
+    let new_name = validate_name(&new_name)?;
+    update_presets(move |presets| {
+        if new_name != name && presets.iter().any(|p| p.name == new_name) {
+            return Err(ServerFnError::ServerError(format!(
+                "已存在名为「{}」的预设",
+                new_name
+            )));
+        }
+        let preset = presets
+            .iter_mut()
+            .find(|p| p.name == name)
+            .ok_or_else(|| not_found(&name))?;
+        preset.name = new_name;
+        Ok(())
+    })
+    .await
+}
+
+#[server(client = CsrfClient)]
+pub async fn delete_voice_preset(name: String) -> Result<(), ServerFnError> {
+    update_presets(move |presets| {
+        presets.retain(|p| p.name != name);
+        Ok(())
+    })
+    .await
+}
+
+/// 设为默认预设；传入 `None` 取消默认
+#[server(client = CsrfClient)]
+pub async fn set_default_voice_preset(name: Option<String>) -> Result<(), ServerFnError> {
+    update_presets(move |presets| {
+        if let Some(name) = &name {
+            if !presets.iter().any(|p| &p.name == name) {
+                return Err(not_found(name));
+            }
+        }
+        for preset in presets.iter_mut() {
+            preset.is_default = name.as_ref() == Some(&preset.name);
+        }
+        Ok(())
+    })
+    .await
+}
+
+/// 生成分享令牌：保存预设的一份快照，打开 `/?preset=令牌` 即可使用
+#[server(client = CsrfClient)]
+pub async fn share_voice_preset(name: String) -> Result<String, ServerFnError> {
+    let presets: Vec<VoicePreset> = store::read(&presets_file().await?).await;
+    let preset = presets
+        .into_iter()
+        .find(|p| p.name == name)
+        .ok_or_else(|| not_found(&name))?;
+    let token = uuid::Uuid::new_v4().simple().to_string()[..16].to_string();
+    let snapshot = VoicePreset {
+        is_default: false,
+        ..preset
+    };
+    store::save(&shared_file(&token), &Some(snapshot)).map_err(|e| -> ServerFnError {
+        ServerFnError::ServerError(format!("生成分享链接失败: {}", e))
+    })?;
+    Ok(token)
+}
+
+#[server(client = CsrfClient)]
+pub async fn get_shared_preset(token: String) -> Result<VoicePreset, ServerFnError> {
+    // 令牌会拼进文件路径，只接受十六进制字符
+    let valid = !token.is_empty() && token.chars().all(|c| c.is_ascii_hexdigit());
+    let preset: Option<VoicePreset> = if valid {
+        store::load(&shared_file(&token))
+    } else {
+        None
+    };
+    preset.ok_or_else(|| ServerFnError::ServerError("分享的预设不存在或链接有误".to_string()))
+}
@@ -0,0 +1,77 @@
+//! TTS 服务商抽象
+//!
+//! 各服务商对语速、音高等参数的支持程度不同，调用方根据 [`Capabilities`]
+//! 决定把参数交给服务商，还是在拿到音频后自行用 DSP 处理。
+use super::TimedText;
+use leptos::prelude::ServerFnError;
+use std::future::Future;
+
+/// 一次合成请求
+pub struct SynthesisRequest<'a> {
+    pub text: &'a str,
+    pub voice: &'a str,
+    /// `voice` 是否为声音复刻得到的自定义声线
+    pub custom_voice: bool,
+    pub language_type: Option<&'a str>,
+    /// 语速倍率，仅在 [`Capabilities::rate`] 为真时生效
+    // 目前接入的服务商都不支持，留给原生支持语速/音高的服务商读取
+    #[allow(dead_code)]
+    pub rate: f32,
+    /// 音高偏移 (半音)，仅在 [`Capabilities::pitch`] 为真时生效
+    #[allow(dead_code)]
+    pub pitch: f32,
+}
+
+/// 合成得到的音频
+pub struct SynthesizedAudio {
+    pub bytes: Vec<u8>,
+    pub content_type: &'static str,
+    /// 词级时间戳 (相对本段音频开头)，服务商不提供时为空
+    pub words: Vec<TimedText>,
+}
+
+/// 声音复刻请求：用参考音频注册一个自定义声线
+pub struct EnrollmentRequest<'a> {
+    /// 服务商侧的声线名前缀，只含小写字母、数字
+    pub name: &'a str,
+    pub audio: &'a [u8],
+    pub content_type: &'a str,
+}
+
+/// 服务商原生支持的能力
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Capabilities {
+    pub rate: bool,
+    pub pitch: bool,
+    /// 是否支持用参考音频复刻声音
+    pub voice_cloning: bool,
+}
+
+pub trait TtsProvider: Send + Sync {
+    fn capabilities(&self) -> Capabilities;
+
+    fn synthesize(
+        &self,
+        request: SynthesisRequest<'_>,
+    ) -> impl Future<Output = Result<SynthesizedAudio, ServerFnError>> + Send;
+
+    /// 注册自定义声线，返回可用于合成的声线 ID。
+    /// 仅在 [`Capabilities::voice_cloning`] 为真时可用
+    fn enroll_voice(
+        &self,
+        request: EnrollmentRequest<'_>,
+    ) -> impl Future<Output = Result<String, ServerFnError>> + Send {
+        let _ = request;
+        async { Err(unsupported()) }
+    }
+
+    /// 删除自定义声线
+    fn delete_voice(&self, voice: &str) -> impl Future<Output = Result<(), ServerFnError>> + Send {
+        let _ = voice;
+        async { Err(unsupported()) }
+    }
+}
+
+fn unsupported() -> ServerFnError {
+    ServerFnError::ServerError("当前服务商不支持声音复刻".to_string())
+}
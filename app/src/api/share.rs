@@ -0,0 +1,538 @@
+//! 公开分享：把生成的片段发布到 `/s/{id}`，可附带原文、有效期和访问密码；
+//! 同时提供 `/embed/{id}` 嵌入播放器使用的 `<iframe>` 代码和 oEmbed 接口
+use crate::i18n::Label;
+use crate::security::CsrfClient;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{audio, clips, session, store};
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+use server_fn::codec::GetUrl;
+
+/// 分享页展示的原文最大字符数
+pub const MAX_SHARE_TEXT_CHARS: usize = 2000;
+
+/// 访问密码的最大字符数
+pub const MAX_SHARE_PASSWORD_CHARS: usize = 64;
+
+/// 可选的有效期 (小时, 显示名)，`None` 表示永久有效
+pub const SHARE_EXPIRY_OPTIONS: [(Option<u32>, Label); 4] = [
+    (None, |t| t.expiry_never),
+    (Some(24), |t| t.expiry_day),
+    (Some(24 * 7), |t| t.expiry_week),
+    (Some(24 * 30), |t| t.expiry_month),
+];
+
+/// 分享页的地址
+pub fn share_url(id: &str) -> String {
+    format!("/s/{}", id)
+}
+
+/// 嵌入播放器的地址
+pub fn embed_url(id: &str) -> String {
+    format!("/embed/{}", id)
+}
+
+/// oEmbed 接口的地址，分享页地址作为查询参数需要百分号编码
+pub fn oembed_url(page_url: &str) -> String {
+    format!(
+        "/api/oembed?url={}&format=json",
+        encode_query_value(page_url)
+    )
+}
+
+/// 对查询参数的值做百分号编码，只保留 RFC 3986 中的非保留字符
+fn encode_query_value(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// 嵌入播放器的默认尺寸 (像素)
+pub const EMBED_WIDTH: u32 = 480;
+pub const EMBED_HEIGHT: u32 = 120;
+
+/// 嵌入用的 `<iframe>` 代码，`src` 为嵌入播放器的完整地址
+pub fn embed_snippet(src: &str, width: Option<u32>, height: u32) -> String {
+    let width = width.map_or_else(|| "100%".to_string(), |w| w.to_string());
+    format!(
+        r#"<iframe src="{}" width="{}" height="{}" frameborder="0" allow="autoplay" loading="lazy" title="耳朵语音播放器"></iframe>"#,
+        src, width, height
+    )
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ShareOptions {
+    /// 是否在分享页展示原文
+    pub include_text: bool,
+    pub expires_in_hours: Option<u32>,
+    /// 访问密码，为空表示不设密码
+    pub password: String,
+}
+
+/// 分享页看到的内容
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SharedClipView {
+    pub clip_id: String,
+    /// 音频的完整地址，用于 OpenGraph 标签
+    pub audio_url: String,
+    /// 分享页的完整地址
+    pub page_url: String,
+    pub content_type: String,
+    pub text: Option<String>,
+    pub voice_name: String,
+    pub duration: f32,
+    pub peaks: Vec<u8>,
+    /// 距离过期还有多少小时 (向上取整)
+    pub expires_in_hours: Option<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum SharedClipLookup {
+    /// 需要输入密码 (或密码错误)
+    Locked {
+        wrong_password: bool,
+    },
+    Ready(SharedClipView),
+}
+
+/// 磁盘上保存的分享记录
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct SharedClip {
+    clip_id: String,
+    content_type: String,
+    text: Option<String>,
+    voice_name: String,
+    duration: f32,
+    peaks: Vec<u8>,
+    created_at: u64,
+    expires_at: Option<u64>,
+    password: Option<SharePassword>,
+    /// 片段是否由这次分享保存到磁盘；是的话分享过期时一并删除，
+    /// 否则片段还被其他地方 (如时间轴工程) 引用，只删除分享记录
+    #[serde(default)]
+    owns_clip: bool,
+}
+
+/// 访问密码的 PBKDF2-HMAC-SHA256 哈希，盐和哈希都是十六进制
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct SharePassword {
+    salt: String,
+    iterations: u32,
+    hash: String,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn share_file(id: &str) -> String {
+    format!("shares/{}", id)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// 新建分享时 PBKDF2 的迭代次数；记录里保存了各自的次数，调高后旧分享仍可验证
+#[cfg(not(target_arch = "wasm32"))]
+const PBKDF2_ITERATIONS: u32 = 200_000;
+
+/// PBKDF2-HMAC-SHA256 (RFC 8018)，输出 32 字节
+#[cfg(not(target_arch = "wasm32"))]
+fn pbkdf2_sha256(password: &[u8], salt: &[u8], iterations: u32) -> [u8; 32] {
+    let mut output = [0u8; 32];
+    pbkdf2::pbkdf2::<hmac::Hmac<sha2::Sha256>>(password, salt, iterations, &mut output)
+        .expect("HMAC 接受任意长度的密钥");
+    output
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// 哈希计算量较大，放到阻塞线程中执行，不占用异步运行时
+#[cfg(not(target_arch = "wasm32"))]
+async fn hash_password(
+    salt: String,
+    password: String,
+    iterations: u32,
+) -> Result<String, ServerFnError> {
+    tokio::task::spawn_blocking(move || {
+        to_hex(&pbkdf2_sha256(
+            password.as_bytes(),
+            salt.as_bytes(),
+            iterations,
+        ))
+    })
+    .await
+    .map_err(|e| -> ServerFnError {
+        ServerFnError::ServerError(format!("校验密码失败: {}", e))
+    })
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn verify_password(stored: &SharePassword, password: &str) -> Result<bool, ServerFnError> {
+    let hash = hash_password(stored.salt.clone(), password.to_string(), stored.iterations).await?;
+    Ok(crate::security::constant_time_eq(
+        hash.as_bytes(),
+        stored.hash.as_bytes(),
+    ))
+}
+
+/// 同一客户端对同一分享在锁定时间内最多可输错的密码次数
+#[cfg(not(target_arch = "wasm32"))]
+const MAX_PASSWORD_FAILURES: u32 = 5;
+/// 输错次数达到上限后的锁定时间，从第一次输错开始计算
+#[cfg(not(target_arch = "wasm32"))]
+const PASSWORD_LOCKOUT: std::time::Duration = std::time::Duration::from_secs(15 * 60);
+
+/// 输错次数按 (分享 ID, 客户端地址) 分别计数，只锁定猜密码的那个客户端，
+/// 不会因为别人反复输错就把知道密码的访客也挡在外面
+#[cfg(not(target_arch = "wasm32"))]
+type FailureKey = (String, Option<std::net::IpAddr>);
+
+/// 各客户端的 (输错次数, 第一次输错的时间)，保存在内存中，服务重启后清零
+#[cfg(not(target_arch = "wasm32"))]
+static PASSWORD_FAILURES: std::sync::LazyLock<
+    std::sync::Mutex<std::collections::HashMap<FailureKey, (u32, std::time::Instant)>>,
+> = std::sync::LazyLock::new(Default::default);
+
+/// 锁定中时返回还需等待的分钟数
+#[cfg(not(target_arch = "wasm32"))]
+fn password_lockout(id: &str, client: Option<std::net::IpAddr>) -> Option<u64> {
+    let mut failures = PASSWORD_FAILURES.lock().unwrap();
+    failures.retain(|_, (_, since)| since.elapsed() < PASSWORD_LOCKOUT);
+    let (count, since) = failures.get(&(id.to_string(), client))?;
+    (*count >= MAX_PASSWORD_FAILURES)
+        .then(|| (PASSWORD_LOCKOUT - since.elapsed()).as_secs().div_ceil(60))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn record_password_failure(id: &str, client: Option<std::net::IpAddr>) {
+    let mut failures = PASSWORD_FAILURES.lock().unwrap();
+    let entry = failures
+        .entry((id.to_string(), client))
+        .or_insert((0, std::time::Instant::now()));
+    entry.0 += 1;
+}
+
+/// 删除过期的分享记录，以及只被该分享引用的片段
+#[cfg(not(target_arch = "wasm32"))]
+fn remove_share(id: &str, record: &SharedClip) {
+    if record.owns_clip {
+        if let Err(e) = clips::remove(&record.clip_id) {
+            leptos::logging::warn!("删除过期分享的音频 {} 失败: {}", record.clip_id, e);
+            return;
+        }
+    }
+    if let Err(e) = store::remove(&share_file(id)) {
+        leptos::logging::warn!("删除过期分享 {} 失败: {}", id, e);
+    }
+}
+
+/// 清理所有已过期的分享，在发布新分享时顺带执行
+#[cfg(not(target_arch = "wasm32"))]
+fn sweep_expired_shares() {
+    let now = now_secs();
+    for id in store::list("shares") {
+        let record: Option<SharedClip> = store::load(&share_file(&id));
+        if let Some(record) = record.filter(|r| r.expires_at.is_some_and(|at| at <= now)) {
+            remove_share(&id, &record);
+        }
+    }
+}
+
+/// 是否有未过期的分享引用了该片段，其他地方删除片段前需要确认
+#[cfg(not(target_arch = "wasm32"))]
+pub(super) fn references_clip(clip_id: &str) -> bool {
+    let now = now_secs();
+    store::list("shares").iter().any(|id| {
+        let record: Option<SharedClip> = store::load(&share_file(id));
+        record.is_some_and(|r| r.clip_id == clip_id && r.expires_at.is_none_or(|at| at > now))
+    })
+}
+
+/// 对外访问的站点地址：优先使用 `EARDO_PUBLIC_URL`；
+/// 未配置时只信任 `EARDO_ALLOWED_HOSTS` (逗号分隔) 中列出的 Host 请求头。
+/// 两者都不满足时返回空字符串，地址退化为站内相对路径，
+/// 避免伪造的 Host 请求头把分享页、oEmbed 里的链接指向其他站点
+#[cfg(not(target_arch = "wasm32"))]
+async fn public_origin() -> String {
+    if let Ok(url) = std::env::var("EARDO_PUBLIC_URL") {
+        return url.trim_end_matches('/').to_string();
+    }
+    let Ok(allowed) = std::env::var("EARDO_ALLOWED_HOSTS") else {
+        return String::new();
+    };
+    let headers: http::HeaderMap = leptos_axum::extract().await.unwrap_or_default();
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    let Some(host) = header("host").filter(|host| {
+        allowed
+            .split(',')
+            .any(|allowed| allowed.trim().eq_ignore_ascii_case(host))
+    }) else {
+        return String::new();
+    };
+    let scheme = match header("x-forwarded-proto") {
+        Some("https") => "https",
+        _ => "http",
+    };
+    format!("{}://{}", scheme, host)
+}
+
+/// 声线的显示名：内置声线或当前用户复刻的声线，都找不到时用 ID
+#[cfg(not(target_arch = "wasm32"))]
+async fn voice_display_name(voice_id: &str) -> String {
+    if let Ok(voices) = super::get_voices().await {
+        if let Some(voice) = voices.into_iter().find(|v| v.id == voice_id) {
+            return voice.name;
+        }
+    }
+    if let Ok(voices) = super::cloning::list_custom_voices().await {
+        if let Some(voice) = voices.into_iter().find(|v| v.id == voice_id) {
+            return voice.name;
+        }
+    }
+    voice_id.to_string()
+}
+
+/// 发布分享，返回分享 ID；片段会保存到磁盘，不受内存淘汰影响
+#[server(client = CsrfClient)]
+pub async fn create_share(
+    clip_id: String,
+    text: String,
+    voice_id: String,
+    options: ShareOptions,
+) -> Result<String, ServerFnError> {
+    let password = options.password.trim();
+    if password.chars().count() > MAX_SHARE_PASSWORD_CHARS {
+        return Err(ServerFnError::ServerError(format!(
+            "访问密码不能超过 {} 个字符",
+            MAX_SHARE_PASSWORD_CHARS
+        )));
+    }
+    sweep_expired_shares();
+    let clip = clips::get(&clip_id).ok_or_else(|| -> ServerFnError {
+        ServerFnError::ServerError("音频不存在或已过期，请重新生成".to_string())
+    })?;
+    let owns_clip = !clips::is_persisted(&clip_id);
+    clips::persist(&clip_id).map_err(|e| -> ServerFnError {
+        ServerFnError::ServerError(format!("保存音频失败: {}", e))
+    })?;
+    let (duration, peaks) = match audio::decode_wav(&clip.bytes) {
+        Ok(pcm) => (pcm.duration(), pcm.peaks(super::PEAK_BUCKETS)),
+        Err(_) => (0.0, Vec::new()),
+    };
+
+    let password = if password.is_empty() {
+        None
+    } else {
+        let salt = uuid::Uuid::new_v4().simple().to_string();
+        let hash = hash_password(salt.clone(), password.to_string(), PBKDF2_ITERATIONS).await?;
+        Some(SharePassword {
+            salt,
+            iterations: PBKDF2_ITERATIONS,
+            hash,
+        })
+    };
+    let now = now_secs();
+    let record = SharedClip {
+        clip_id,
+        content_type: clip.content_type.to_string(),
+        text: options
+            .include_text
+            .then(|| text.chars().take(MAX_SHARE_TEXT_CHARS).collect()),
+        voice_name: voice_display_name(&voice_id).await,
+        duration,
+        peaks,
+        created_at: now,
+        expires_at: options
+            .expires_in_hours
+            .map(|hours| now + u64::from(hours) * 3600),
+        password,
+        owns_clip,
+    };
+    let id = uuid::Uuid::new_v4().simple().to_string()[..12].to_string();
+    store::save(&share_file(&id), &Some(record)).map_err(|e| -> ServerFnError {
+        ServerFnError::ServerError(format!("发布分享失败: {}", e))
+    })?;
+    Ok(id)
+}
+
+/// 读取分享；设了密码时需要传入正确的密码
+#[server(client = CsrfClient)]
+pub async fn get_shared_clip(
+    id: String,
+    password: Option<String>,
+) -> Result<SharedClipLookup, ServerFnError> {
+    let not_found = || ServerFnError::ServerError("分享不存在或已被删除".to_string());
+    // ID 会拼进文件路径，只接受十六进制字符
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(not_found());
+    }
+    let record: Option<SharedClip> = store::load(&share_file(&id));
+    let record = record.ok_or_else(not_found)?;
+    let now = now_secs();
+    if record.expires_at.is_some_and(|at| at <= now) {
+        remove_share(&id, &record);
+        return Err(ServerFnError::ServerError("分享已过期".to_string()));
+    }
+    if let Some(stored) = &record.password {
+        let Some(password) = password else {
+            return Ok(SharedClipLookup::Locked {
+                wrong_password: false,
+            });
+        };
+        let client = session::client_ip().await;
+        if let Some(minutes) = password_lockout(&id, client) {
+            return Err(ServerFnError::ServerError(format!(
+                "密码错误次数过多，请 {} 分钟后再试",
+                minutes
+            )));
+        }
+        if !verify_password(stored, password.trim()).await? {
+            record_password_failure(&id, client);
+            return Ok(SharedClipLookup::Locked {
+                wrong_password: true,
+            });
+        }
+    }
+
+    let origin = public_origin().await;
+    Ok(SharedClipLookup::Ready(SharedClipView {
+        audio_url: format!("{}{}", origin, clips::clip_url(&record.clip_id)),
+        page_url: format!("{}{}", origin, share_url(&id)),
+        clip_id: record.clip_id,
+        content_type: record.content_type,
+        text: record.text,
+        voice_name: record.voice_name,
+        duration: record.duration,
+        peaks: record.peaks,
+        expires_in_hours: record.expires_at.map(|at| (at - now).div_ceil(3600)),
+    }))
+}
+
+/// oEmbed 响应 (rich 类型)，见 <https://oembed.com>
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OEmbedResponse {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub version: String,
+    pub title: String,
+    pub provider_name: String,
+    pub provider_url: String,
+    pub html: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// 从分享页或嵌入页的地址中取出分享 ID
+#[cfg(not(target_arch = "wasm32"))]
+fn share_id_from_url(url: &str) -> Option<String> {
+    ["/s/", "/embed/"].iter().find_map(|prefix| {
+        let (_, rest) = url.split_once(prefix)?;
+        let id: String = rest.chars().take_while(char::is_ascii_hexdigit).collect();
+        (!id.is_empty()).then_some(id)
+    })
+}
+
+/// oEmbed 接口：`GET /api/oembed?url=分享页地址[&maxwidth=..&maxheight=..]`
+#[server(prefix = "/api", endpoint = "oembed", input = GetUrl)]
+pub async fn oembed(
+    url: String,
+    maxwidth: Option<u32>,
+    maxheight: Option<u32>,
+) -> Result<OEmbedResponse, ServerFnError> {
+    let id = share_id_from_url(&url).ok_or_else(|| -> ServerFnError {
+        ServerFnError::ServerError("不是有效的分享地址".to_string())
+    })?;
+    let title = match get_shared_clip(id.clone(), None).await? {
+        SharedClipLookup::Ready(clip) => format!("{} 的语音", clip.voice_name),
+        SharedClipLookup::Locked { .. } => "受密码保护的语音".to_string(),
+    };
+    let width = maxwidth.map_or(EMBED_WIDTH, |max| EMBED_WIDTH.min(max));
+    let height = maxheight.map_or(EMBED_HEIGHT, |max| EMBED_HEIGHT.min(max));
+    let origin = public_origin().await;
+    Ok(OEmbedResponse {
+        kind: "rich".to_string(),
+        version: "1.0".to_string(),
+        title,
+        provider_name: "耳朵".to_string(),
+        html: embed_snippet(
+            &format!("{}{}", origin, embed_url(&id)),
+            Some(width),
+            height,
+        ),
+        provider_url: origin,
+        width,
+        height,
+    })
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oembed_url_encodes_page_url() {
+        assert_eq!(
+            oembed_url("https://example.com/s/abc?x=1&y=2#top"),
+            "/api/oembed?url=https%3A%2F%2Fexample.com%2Fs%2Fabc%3Fx%3D1%26y%3D2%23top&format=json"
+        );
+    }
+
+    // RFC 7914 第 11 节给出的 PBKDF2-HMAC-SHA256 测试向量
+    #[test]
+    fn pbkdf2_matches_reference_vectors() {
+        assert_eq!(
+            to_hex(&pbkdf2_sha256(b"password", b"salt", 1)),
+            "120fb6cffcf8b32c43e7225256c4f837a86548c92ccc35480805987cb70be17b"
+        );
+        assert_eq!(
+            to_hex(&pbkdf2_sha256(b"password", b"salt", 2)),
+            "ae4d0c95af6b46d32d0adff928f06dd02a303f8ef3c251dfd6e2d85a95474c43"
+        );
+        assert_eq!(
+            to_hex(&pbkdf2_sha256(b"password", b"salt", 4096)),
+            "c5e478d59288c841aa530db6845c4c8d962893a001ce4e11a4963873aa98134a"
+        );
+    }
+
+    #[test]
+    fn long_keys_are_hashed_first() {
+        let long = [b'k'; 100];
+        let hashed = {
+            use sha2::{Digest, Sha256};
+            Sha256::digest(long)
+        };
+        assert_eq!(
+            pbkdf2_sha256(&long, b"salt", 3),
+            pbkdf2_sha256(&hashed, b"salt", 3)
+        );
+    }
+
+    #[test]
+    fn lockout_after_repeated_failures() {
+        let id = "lockout-test";
+        let guesser = Some(std::net::IpAddr::from([192, 0, 2, 1]));
+        let visitor = Some(std::net::IpAddr::from([192, 0, 2, 2]));
+        for _ in 0..MAX_PASSWORD_FAILURES - 1 {
+            record_password_failure(id, guesser);
+        }
+        assert_eq!(password_lockout(id, guesser), None);
+        record_password_failure(id, guesser);
+        assert_eq!(password_lockout(id, guesser), Some(15));
+        // 其他客户端不受影响
+        assert_eq!(password_lockout(id, visitor), None);
+    }
+}
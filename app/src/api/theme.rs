@@ -0,0 +1,28 @@
+//! 主题设置：按用户保存，同时写入 Cookie 供 SSR 直接读取
+use crate::security::CsrfClient;
+use crate::theme::Theme;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{session, store, theme::THEME_COOKIE};
+use leptos::prelude::*;
+
+#[cfg(not(target_arch = "wasm32"))]
+fn theme_file(user_id: &str) -> String {
+    format!("themes/users/{}", user_id)
+}
+
+/// 请求对应用户保存过的主题；没有用户 Cookie 或没保存过时返回 `None`
+#[cfg(feature = "ssr")]
+pub fn stored_theme(headers: &http::HeaderMap) -> Option<Theme> {
+    let user_id = session::read_user_id(headers)?;
+    store::load(&theme_file(&user_id))
+}
+
+#[server(client = CsrfClient)]
+pub async fn save_theme(theme: Theme) -> Result<(), ServerFnError> {
+    let file = theme_file(&session::user_id().await?);
+    store::save(&file, &Some(theme)).map_err(|e| -> ServerFnError {
+        ServerFnError::ServerError(format!("保存主题失败: {}", e))
+    })?;
+    session::set_cookie(THEME_COOKIE, &theme.to_cookie(), false);
+    Ok(())
+}
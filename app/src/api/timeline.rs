@@ -0,0 +1,517 @@
+//! 时间轴工程：多轨摆放生成的片段，保存工程并在服务端渲染成 WAV / MP3
+//!
+//! 导出 MP3 需要服务器上装有带 libmp3lame 的 ffmpeg：默认在 PATH 中查找，
+//! 也可以用 `EARDO_FFMPEG` 指定可执行文件路径。没有 ffmpeg 时仍可导出 WAV。
+use super::voicefilter::AudioClip;
+use crate::i18n::Messages;
+use crate::security::CsrfClient;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{audio, clips, session, store};
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 工程文件格式版本，格式不兼容地变化时递增
+pub const PROJECT_VERSION: u32 = 1;
+
+/// 工程名的最大字符数
+pub const MAX_PROJECT_NAME_CHARS: usize = 32;
+
+/// 每个用户最多保存的工程数
+#[cfg(not(target_arch = "wasm32"))]
+const MAX_PROJECTS: usize = 20;
+
+/// 单个工程的片段总数上限
+#[cfg(not(target_arch = "wasm32"))]
+const MAX_ITEMS: usize = 200;
+
+/// 渲染结果的最大时长 (秒)
+#[cfg(not(target_arch = "wasm32"))]
+const MAX_RENDER_SECS: f32 = 30.0 * 60.0;
+
+/// 时间轴上的一个片段 (时间单位：秒)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TimelineItem {
+    /// 片段库中的音频 ID
+    pub clip_id: String,
+    /// 显示名称，一般为合成文本的开头
+    pub label: String,
+    /// 素材的完整时长
+    pub source_duration: f32,
+    /// 在时间轴上的起点
+    pub start: f32,
+    /// 从素材的哪个位置开始 (裁掉开头)
+    pub offset: f32,
+    /// 截取的长度 (裁掉结尾)
+    pub length: f32,
+    pub fade_in: f32,
+    pub fade_out: f32,
+    pub gain_db: f32,
+}
+
+impl TimelineItem {
+    pub fn new(clip_id: String, label: String, duration: f32, start: f32) -> Self {
+        TimelineItem {
+            clip_id,
+            label,
+            source_duration: duration,
+            start,
+            offset: 0.0,
+            length: duration,
+            fade_in: 0.0,
+            fade_out: 0.0,
+            gain_db: 0.0,
+        }
+    }
+
+    pub fn end(&self) -> f32 {
+        self.start + self.length
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TimelineTrack {
+    pub name: String,
+    pub gain_db: f32,
+    pub muted: bool,
+    pub items: Vec<TimelineItem>,
+}
+
+impl TimelineTrack {
+    pub fn new(name: String) -> Self {
+        TimelineTrack {
+            name,
+            gain_db: 0.0,
+            muted: false,
+            items: Vec::new(),
+        }
+    }
+
+    /// 轨道上最后一个片段的终点
+    pub fn end(&self) -> f32 {
+        self.items.iter().map(TimelineItem::end).fold(0.0, f32::max)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TimelineProject {
+    pub version: u32,
+    /// 首次保存时由服务端分配
+    pub id: Option<String>,
+    pub name: String,
+    pub tracks: Vec<TimelineTrack>,
+    /// 最后保存时间 (Unix 秒)
+    pub updated_at: u64,
+}
+
+impl TimelineProject {
+    /// 新建的空工程：一条人声轨、一条音效轨，名称按界面语言
+    pub fn new(t: &'static Messages) -> Self {
+        TimelineProject {
+            version: PROJECT_VERSION,
+            id: None,
+            name: t.untitled_project.to_string(),
+            tracks: vec![
+                TimelineTrack::new(t.track_voice.to_string()),
+                TimelineTrack::new(t.track_effects.to_string()),
+            ],
+            updated_at: 0,
+        }
+    }
+
+    /// 整个工程的时长
+    pub fn duration(&self) -> f32 {
+        self.tracks
+            .iter()
+            .map(TimelineTrack::end)
+            .fold(0.0, f32::max)
+    }
+}
+
+/// 工程列表中的一项
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ProjectSummary {
+    pub id: String,
+    pub name: String,
+    pub updated_at: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RenderFormat {
+    Wav,
+    Mp3,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn projects_file(user_id: &str) -> String {
+    format!("timeline/users/{}", user_id)
+}
+
+/// 该用户的工程保存时才写到磁盘的片段 ID；只记在服务端，不随工程发给浏览器
+#[cfg(not(target_arch = "wasm32"))]
+fn owned_clips_file(user_id: &str) -> String {
+    format!("timeline/owned/{}", user_id)
+}
+
+#[server(client = CsrfClient)]
+pub async fn list_timeline_projects() -> Result<Vec<ProjectSummary>, ServerFnError> {
+    let projects: Vec<TimelineProject> =
+        store::read(&projects_file(&session::user_id().await?)).await;
+    Ok(projects
+        .into_iter()
+        .filter_map(|p| {
+            Some(ProjectSummary {
+                id: p.id?,
+                name: p.name,
+                updated_at: p.updated_at,
+            })
+        })
+        .collect())
+}
+
+#[server(client = CsrfClient)]
+pub async fn load_timeline_project(id: String) -> Result<TimelineProject, ServerFnError> {
+    let projects: Vec<TimelineProject> =
+        store::read(&projects_file(&session::user_id().await?)).await;
+    projects
+        .into_iter()
+        .find(|p| p.id.as_deref() == Some(id.as_str()))
+        .ok_or_else(|| ServerFnError::ServerError("工程不存在".to_string()))
+}
+
+/// 校验时间参数，修正为合法范围
+#[cfg(not(target_arch = "wasm32"))]
+fn sanitize(project: &mut TimelineProject) -> Result<(), ServerFnError> {
+    let count: usize = project.tracks.iter().map(|t| t.items.len()).sum();
+    if count > MAX_ITEMS {
+        return Err(ServerFnError::ServerError(format!(
+            "一个工程最多放 {} 个片段",
+            MAX_ITEMS
+        )));
+    }
+    for track in &mut project.tracks {
+        track.gain_db = track.gain_db.clamp(-60.0, 12.0);
+        for item in &mut track.items {
+            item.start = item.start.max(0.0);
+            item.offset = item.offset.clamp(0.0, item.source_duration.max(0.0));
+            item.length = item
+                .length
+                .clamp(0.0, (item.source_duration - item.offset).max(0.0));
+            item.fade_in = item.fade_in.clamp(0.0, item.length);
+            item.fade_out = item.fade_out.clamp(0.0, item.length);
+            item.gain_db = item.gain_db.clamp(-60.0, 12.0);
+        }
+    }
+    if project.duration() > MAX_RENDER_SECS {
+        return Err(ServerFnError::ServerError(format!(
+            "工程总时长不能超过 {} 分钟",
+            MAX_RENDER_SECS / 60.0
+        )));
+    }
+    Ok(())
+}
+
+/// 解码工程用到的素材，每个只解码一次
+#[cfg(not(target_arch = "wasm32"))]
+fn decode_sources<'a>(
+    items: impl IntoIterator<Item = &'a TimelineItem>,
+) -> Result<Vec<(String, audio::Pcm)>, ServerFnError> {
+    let mut sources: Vec<(String, audio::Pcm)> = Vec::new();
+    for item in items {
+        if sources.iter().any(|(id, _)| *id == item.clip_id) {
+            continue;
+        }
+        let clip = clips::get(&item.clip_id).ok_or_else(|| -> ServerFnError {
+            ServerFnError::ServerError(format!("素材「{}」不存在或已过期", item.label))
+        })?;
+        sources.push((item.clip_id.clone(), super::decode_clip(&clip.bytes)?));
+    }
+    Ok(sources)
+}
+
+/// 素材时长以服务端解码的结果为准，不信任浏览器传来的 `source_duration`，再重新校验时间参数
+#[cfg(not(target_arch = "wasm32"))]
+fn apply_durations(
+    project: &mut TimelineProject,
+    sources: &[(String, audio::Pcm)],
+) -> Result<(), ServerFnError> {
+    for item in project.tracks.iter_mut().flat_map(|t| &mut t.items) {
+        if let Some((_, pcm)) = sources.iter().find(|(id, _)| *id == item.clip_id) {
+            item.source_duration = pcm.duration();
+        }
+    }
+    sanitize(project)
+}
+
+/// 删除该用户的工程不再引用、也没有被分享引用的片段
+#[cfg(not(target_arch = "wasm32"))]
+async fn release_clips(user_id: &str) {
+    let projects_file = projects_file(user_id);
+    let result = store::update(
+        &owned_clips_file(user_id),
+        move |owned: &mut Vec<String>| {
+            let projects: Vec<TimelineProject> = store::load(&projects_file);
+            owned.retain(|clip_id| {
+                let in_use = projects
+                    .iter()
+                    .flat_map(|p| &p.tracks)
+                    .flat_map(|t| &t.items)
+                    .any(|item| item.clip_id == *clip_id)
+                    || super::share::references_clip(clip_id);
+                if in_use {
+                    return true;
+                }
+                match clips::remove(clip_id) {
+                    Ok(()) => false,
+                    Err(e) => {
+                        leptos::logging::warn!("删除时间轴素材 {} 失败: {}", clip_id, e);
+                        true
+                    }
+                }
+            });
+            Ok::<_, ServerFnError>(())
+        },
+    )
+    .await;
+    if let Err(e) = result {
+        leptos::logging::warn!("保存时间轴素材记录失败: {}", e);
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn save_failed(e: std::io::Error) -> ServerFnError {
+    ServerFnError::ServerError(format!("保存工程失败: {}", e))
+}
+
+/// 保存工程：同时把引用的片段保存到磁盘，避免被内存淘汰。返回保存后的工程 (含 ID)
+///
+/// 由本次保存写到磁盘的片段记为该用户所有，工程删除或不再引用它们时一并删除。
+#[server(client = CsrfClient)]
+pub async fn save_timeline_project(
+    project: TimelineProject,
+) -> Result<TimelineProject, ServerFnError> {
+    let mut project = project;
+    project.name = project.name.trim().to_string();
+    if project.name.is_empty() || project.name.chars().count() > MAX_PROJECT_NAME_CHARS {
+        return Err(ServerFnError::ServerError(format!(
+            "工程名需为 1~{} 个字符",
+            MAX_PROJECT_NAME_CHARS
+        )));
+    }
+    sanitize(&mut project)?;
+    let sources = decode_sources(project.tracks.iter().flat_map(|t| &t.items))?;
+    apply_durations(&mut project, &sources)?;
+
+    let user_id = session::user_id().await?;
+    let mut newly_persisted = Vec::new();
+    for item in project.tracks.iter().flat_map(|t| &t.items) {
+        if !clips::is_persisted(&item.clip_id) {
+            newly_persisted.push(item.clip_id.clone());
+        }
+        clips::persist(&item.clip_id).map_err(|e| -> ServerFnError {
+            ServerFnError::ServerError(format!("保存素材「{}」失败: {}", item.label, e))
+        })?;
+    }
+    store::update(
+        &owned_clips_file(&user_id),
+        move |owned: &mut Vec<String>| {
+            for clip_id in newly_persisted {
+                if !owned.contains(&clip_id) {
+                    owned.push(clip_id);
+                }
+            }
+            Ok::<_, ServerFnError>(())
+        },
+    )
+    .await
+    .map_err(save_failed)??;
+
+    project.version = PROJECT_VERSION;
+    project.updated_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let project = store::update(
+        &projects_file(&user_id),
+        move |projects: &mut Vec<TimelineProject>| -> Result<TimelineProject, ServerFnError> {
+            let existing = project
+                .id
+                .as_ref()
+                .and_then(|id| projects.iter().position(|p| p.id.as_ref() == Some(id)));
+            match existing {
+                Some(index) => projects[index] = project.clone(),
+                None => {
+                    if projects.len() >= MAX_PROJECTS {
+                        return Err(ServerFnError::ServerError(format!(
+                            "最多保存 {} 个工程，请先删除不用的工程",
+                            MAX_PROJECTS
+                        )));
+                    }
+                    project.id = Some(uuid::Uuid::new_v4().simple().to_string());
+                    projects.push(project.clone());
+                }
+            }
+            Ok(project)
+        },
+    )
+    .await
+    .map_err(save_failed)??;
+    // 覆盖保存时可能移除了片段
+    release_clips(&user_id).await;
+    Ok(project)
+}
+
+#[server(client = CsrfClient)]
+pub async fn delete_timeline_project(id: String) -> Result<(), ServerFnError> {
+    let user_id = session::user_id().await?;
+    store::update(
+        &projects_file(&user_id),
+        move |projects: &mut Vec<TimelineProject>| {
+            projects.retain(|p| p.id.as_deref() != Some(id.as_str()));
+            Ok::<_, ServerFnError>(())
+        },
+    )
+    .await
+    .map_err(|e| -> ServerFnError {
+        ServerFnError::ServerError(format!("删除工程失败: {}", e))
+    })??;
+    release_clips(&user_id).await;
+    Ok(())
+}
+
+/// 用 ffmpeg 把 WAV 转成 MP3；可执行文件路径由 `EARDO_FFMPEG` 指定，默认在 PATH 中查找
+#[cfg(not(target_arch = "wasm32"))]
+async fn encode_mp3(wav: Vec<u8>) -> Result<Vec<u8>, ServerFnError> {
+    use std::process::Stdio;
+    use tokio::io::AsyncWriteExt;
+
+    let ffmpeg = std::env::var("EARDO_FFMPEG").unwrap_or_else(|_| "ffmpeg".to_string());
+    let mut child = tokio::process::Command::new(ffmpeg)
+        .args([
+            "-hide_banner",
+            "-loglevel",
+            "error",
+            "-f",
+            "wav",
+            "-i",
+            "pipe:0",
+        ])
+        .args([
+            "-codec:a",
+            "libmp3lame",
+            "-b:a",
+            "192k",
+            "-f",
+            "mp3",
+            "pipe:1",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| -> ServerFnError {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                ServerFnError::ServerError(
+                    "服务器未找到 ffmpeg (可用 EARDO_FFMPEG 指定路径)，暂时只能导出 WAV"
+                        .to_string(),
+                )
+            } else {
+                ServerFnError::ServerError(format!("启动 ffmpeg 失败: {}", e))
+            }
+        })?;
+    // 边写入边读取，避免管道写满互相等待
+    let mut stdin = child.stdin.take().expect("stdin 已设置为管道");
+    let writer = tokio::spawn(async move { stdin.write_all(&wav).await });
+    let output = child
+        .wait_with_output()
+        .await
+        .map_err(|e| -> ServerFnError {
+            ServerFnError::ServerError(format!("MP3 编码失败: {}", e))
+        })?;
+    let _ = writer.await;
+    if !output.status.success() {
+        return Err(ServerFnError::ServerError(format!(
+            "MP3 编码失败: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(output.stdout)
+}
+
+/// 在服务端渲染工程，结果存入片段库
+#[server(client = CsrfClient)]
+pub async fn render_timeline(
+    project: TimelineProject,
+    format: RenderFormat,
+) -> Result<AudioClip, ServerFnError> {
+    let mut project = project;
+    sanitize(&mut project)?;
+
+    // 解码用到的素材；输出格式取第一个素材的采样率
+    let sources = decode_sources(
+        project
+            .tracks
+            .iter()
+            .filter(|t| !t.muted)
+            .flat_map(|t| &t.items),
+    )?;
+    apply_durations(&mut project, &sources)?;
+    let items: Vec<(&TimelineItem, f32)> = project
+        .tracks
+        .iter()
+        .filter(|t| !t.muted)
+        .flat_map(|t| t.items.iter().map(move |item| (item, t.gain_db)))
+        .collect();
+    let Some(sample_rate) = sources.first().map(|(_, pcm)| pcm.sample_rate) else {
+        return Err(ServerFnError::ServerError(
+            "时间轴上没有可渲染的片段".to_string(),
+        ));
+    };
+    let channels = sources
+        .iter()
+        .map(|(_, pcm)| pcm.channels)
+        .max()
+        .unwrap_or(1)
+        .min(2);
+    let items: Vec<(TimelineItem, f32)> = items
+        .into_iter()
+        .map(|(item, track_gain)| (item.clone(), track_gain))
+        .collect();
+
+    // 混音计算量较大，放到阻塞线程池里执行
+    let mixed = tokio::task::spawn_blocking(move || {
+        let sources: Vec<(String, audio::Pcm)> = sources
+            .into_iter()
+            .map(|(id, pcm)| (id, audio::timeline::conform(&pcm, sample_rate, channels)))
+            .collect();
+        let placements: Vec<audio::timeline::Placement> = items
+            .iter()
+            .filter_map(|(item, track_gain)| {
+                let (_, source) = sources.iter().find(|(id, _)| *id == item.clip_id)?;
+                Some(audio::timeline::Placement {
+                    source,
+                    start: item.start,
+                    offset: item.offset,
+                    length: item.length,
+                    fade_in: item.fade_in,
+                    fade_out: item.fade_out,
+                    gain: 10f32.powf((item.gain_db + track_gain) / 20.0),
+                })
+            })
+            .collect();
+        audio::timeline::render(&placements, sample_rate, channels)
+    })
+    .await
+    .map_err(|e| -> ServerFnError { ServerFnError::ServerError(format!("渲染失败: {}", e)) })?;
+
+    match format {
+        RenderFormat::Wav => Ok(super::voicefilter::store_pcm(&mixed)),
+        RenderFormat::Mp3 => {
+            let bytes = encode_mp3(audio::encode_wav(&mixed)).await?;
+            Ok(AudioClip {
+                id: clips::insert("audio/mpeg", bytes),
+                duration: mixed.duration(),
+                peaks: mixed.peaks(super::PEAK_BUCKETS),
+            })
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! 变声器：上传录音，按设置叠加效果后返回处理结果
+use crate::security::CsrfClient;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{audio, clips};
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+use server_fn::codec::{MultipartData, MultipartFormData};
+
+/// 上传表单中音频文件的字段名
+pub const UPLOAD_FIELD: &str = "audio";
+
+/// 单个上传文件的最大字节数
+#[cfg(not(target_arch = "wasm32"))]
+const MAX_UPLOAD_BYTES: usize = 20 * 1024 * 1024;
+
+/// 上传音频的最大时长 (秒)
+pub const MAX_UPLOAD_SECS: f32 = 120.0;
+
+/// 变声参数；数值为 0 或开关关闭时对应效果不启用
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct VoiceFilterSettings {
+    /// 变调 (半音)，共振峰随之移动
+    pub pitch: f32,
+    /// 共振峰偏移 (半音)，音高不变
+    pub formant: f32,
+    pub robot: bool,
+    pub telephone: bool,
+    /// 以下为湿声比例 (0–1)
+    pub reverb: f32,
+    pub echo: f32,
+    pub chorus: f32,
+}
+
+/// 存入片段库的音频，附带绘制波形所需的时长和峰值
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AudioClip {
+    pub id: String,
+    pub duration: f32,
+    /// 波形峰值 (0 ~ 255)
+    pub peaks: Vec<u8>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(super) fn store_pcm(pcm: &audio::Pcm) -> AudioClip {
+    AudioClip {
+        id: clips::insert("audio/wav", audio::encode_wav(pcm)),
+        duration: pcm.duration(),
+        peaks: pcm.peaks(super::PEAK_BUCKETS),
+    }
+}
+
+/// 读取 multipart 表单中的音频文件，限制大小
+#[cfg(not(target_arch = "wasm32"))]
+async fn read_upload(data: MultipartData) -> Result<Vec<u8>, ServerFnError> {
+    let mut data = data.into_inner().ok_or_else(|| -> ServerFnError {
+        ServerFnError::ServerError("无效的上传请求".to_string())
+    })?;
+    while let Ok(Some(mut field)) = data.next_field().await {
+        if field.name() != Some(UPLOAD_FIELD) {
+            continue;
+        }
+        let mut bytes = Vec::new();
+        while let Some(chunk) = field.chunk().await.map_err(|e| -> ServerFnError {
+            ServerFnError::ServerError(format!("读取上传文件失败: {}", e))
+        })? {
+            if bytes.len() + chunk.len() > MAX_UPLOAD_BYTES {
+                return Err(ServerFnError::ServerError(format!(
+                    "文件不能超过 {} MB",
+                    MAX_UPLOAD_BYTES / 1024 / 1024
+                )));
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        return Ok(bytes);
+    }
+    Err(ServerFnError::ServerError("没有收到音频文件".to_string()))
+}
+
+/// 上传一段 WAV 录音，存入片段库
+#[server(prefix = "/api", endpoint = "upload_recording", input = MultipartFormData, client = CsrfClient)]
+pub async fn upload_recording(data: MultipartData) -> Result<AudioClip, ServerFnError> {
+    let bytes = read_upload(data).await?;
+    let pcm = audio::decode_wav(&bytes).map_err(|e| -> ServerFnError {
+        ServerFnError::ServerError(format!("无法识别的音频 (目前仅支持 WAV): {}", e))
+    })?;
+    if pcm.duration() > MAX_UPLOAD_SECS {
+        return Err(ServerFnError::ServerError(format!(
+            "录音不能超过 {} 秒",
+            MAX_UPLOAD_SECS
+        )));
+    }
+    Ok(store_pcm(&pcm))
+}
+
+/// 对片段库中的录音应用变声效果，结果作为新片段返回
+#[server(client = CsrfClient)]
+pub async fn apply_voice_filter(
+    clip_id: String,
+    settings: VoiceFilterSettings,
+) -> Result<AudioClip, ServerFnError> {
+    use audio::effects;
+
+    let clip = clips::get(&clip_id).ok_or_else(|| -> ServerFnError {
+        ServerFnError::ServerError("录音不存在或已过期，请重新上传".to_string())
+    })?;
+    let mut pcm = super::decode_clip(&clip.bytes)?;
+
+    // DSP 计算量较大，放到阻塞线程池里执行
+    let processed = tokio::task::spawn_blocking(move || {
+        pcm = audio::dsp::pitch_shift(&pcm, settings.pitch.clamp(-12.0, 12.0));
+        pcm = effects::formant_shift(&pcm, settings.formant.clamp(-6.0, 6.0));
+        if settings.robot {
+            pcm = effects::robot(&pcm);
+        }
+        if settings.telephone {
+            pcm = effects::telephone(&pcm);
+        }
+        pcm = effects::chorus(&pcm, settings.chorus);
+        pcm = effects::echo(&pcm, settings.echo);
+        pcm = effects::reverb(&pcm, settings.reverb);
+        effects::prevent_clipping(&mut pcm);
+        pcm
+    })
+    .await
+    .map_err(|e| -> ServerFnError {
+        ServerFnError::ServerError(format!("处理音频失败: {}", e))
+    })?;
+
+    Ok(store_pcm(&processed))
+}
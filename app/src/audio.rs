@@ -0,0 +1,198 @@
+//! 音频处理：WAV 编解码、PCM 拼接与基础 DSP
+//!
+//! 纯 Rust 实现，服务端与浏览器端 (录音编码) 共用。
+use thiserror::Error;
+
+pub mod dsp;
+pub mod effects;
+pub mod fft;
+pub mod mixer;
+pub mod post;
+pub mod timeline;
+
+#[derive(Debug, Error)]
+pub enum AudioError {
+    #[error("不是有效的 WAV 文件")]
+    NotWav,
+    #[error("WAV 文件缺少 {0} 块")]
+    MissingChunk(&'static str),
+    #[error("不支持的 WAV 编码: format={format}, bits={bits}")]
+    Unsupported { format: u16, bits: u16 },
+    #[error("音频格式不一致，无法拼接")]
+    FormatMismatch,
+    #[error("不支持的采样率或声道数: {sample_rate} Hz, {channels} 声道 (支持 8~192 kHz 的单声道或立体声)")]
+    UnsupportedLayout { sample_rate: u32, channels: u16 },
+}
+
+/// 可解码的采样率范围 (Hz) 与最多声道数
+const MIN_SAMPLE_RATE: u32 = 8_000;
+const MAX_SAMPLE_RATE: u32 = 192_000;
+const MAX_CHANNELS: u16 = 2;
+
+/// 解码后的 PCM 音频，样本为交错排列的 f32 (-1.0 ~ 1.0)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pcm {
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub samples: Vec<f32>,
+}
+
+impl Pcm {
+    pub fn silence(sample_rate: u32, channels: u16, seconds: f32) -> Self {
+        let frames = (sample_rate as f32 * seconds.max(0.0)).round() as usize;
+        Pcm {
+            sample_rate,
+            channels,
+            samples: vec![0.0; frames * channels as usize],
+        }
+    }
+
+    /// 在末尾追加另一段相同格式的音频
+    pub fn append(&mut self, other: &Pcm) -> Result<(), AudioError> {
+        if self.sample_rate != other.sample_rate || self.channels != other.channels {
+            return Err(AudioError::FormatMismatch);
+        }
+        self.samples.extend_from_slice(&other.samples);
+        Ok(())
+    }
+
+    /// 时长 (秒)
+    pub fn duration(&self) -> f32 {
+        let frames = self.samples.len() / self.channels.max(1) as usize;
+        frames as f32 / self.sample_rate.max(1) as f32
+    }
+
+    /// 波形概览：均分为 `buckets` 段，取每段各声道的最大振幅，
+    /// 量化为 0 ~ 255 (序列化成 JSON 比浮点数紧凑得多)
+    pub fn peaks(&self, buckets: usize) -> Vec<u8> {
+        let channels = self.channels.max(1) as usize;
+        let frames = self.samples.len() / channels;
+        if frames == 0 || buckets == 0 {
+            return Vec::new();
+        }
+        (0..buckets)
+            .map(|i| {
+                let start = i * frames / buckets;
+                let end = ((i + 1) * frames / buckets).max(start + 1).min(frames);
+                let peak = self.samples[start * channels..end * channels]
+                    .iter()
+                    .fold(0.0f32, |peak, s| peak.max(s.abs()))
+                    .min(1.0);
+                (peak * 255.0).round() as u8
+            })
+            .collect()
+    }
+}
+
+fn read_u16(bytes: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes([bytes[at], bytes[at + 1]])
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]])
+}
+
+/// 解析 RIFF/WAVE 文件，支持 8/16/24/32 位整型 PCM 与 32 位浮点
+pub fn decode_wav(bytes: &[u8]) -> Result<Pcm, AudioError> {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err(AudioError::NotWav);
+    }
+
+    let mut fmt: Option<(u16, u16, u32, u16)> = None;
+    let mut data: Option<&[u8]> = None;
+    let mut pos = 12;
+    while pos + 8 <= bytes.len() {
+        let id = &bytes[pos..pos + 4];
+        let size = read_u32(bytes, pos + 4) as usize;
+        let body_start = pos + 8;
+        // 流式生成的 WAV 可能把 data 大小写成 0 或 0xFFFFFFFF，此时取到文件末尾
+        let body_end = if size == 0 || size == u32::MAX as usize {
+            bytes.len()
+        } else {
+            (body_start + size).min(bytes.len())
+        };
+        let body = &bytes[body_start..body_end];
+        match id {
+            b"fmt " if body.len() >= 16 => {
+                let mut format = read_u16(body, 0);
+                let channels = read_u16(body, 2);
+                let sample_rate = read_u32(body, 4);
+                let bits = read_u16(body, 14);
+                // WAVE_FORMAT_EXTENSIBLE：真实格式在 SubFormat GUID 的前两个字节
+                if format == 0xFFFE && body.len() >= 26 {
+                    format = read_u16(body, 24);
+                }
+                fmt = Some((format, channels, sample_rate, bits));
+            }
+            b"data" => data = Some(body),
+            _ => {}
+        }
+        // 块按偶数字节对齐
+        pos = body_end + (body_end - body_start) % 2;
+    }
+
+    let (format, channels, sample_rate, bits) = fmt.ok_or(AudioError::MissingChunk("fmt"))?;
+    let data = data.ok_or(AudioError::MissingChunk("data"))?;
+    if channels == 0 {
+        return Err(AudioError::Unsupported { format, bits });
+    }
+    // 后续处理会按采样率分配缓冲区 (静音、淡入淡出等)，过大的头部数值会耗尽内存
+    if !(MIN_SAMPLE_RATE..=MAX_SAMPLE_RATE).contains(&sample_rate) || channels > MAX_CHANNELS {
+        return Err(AudioError::UnsupportedLayout {
+            sample_rate,
+            channels,
+        });
+    }
+
+    let samples = match (format, bits) {
+        (1, 8) => data.iter().map(|&b| (b as f32 - 128.0) / 128.0).collect(),
+        (1, 16) => data
+            .chunks_exact(2)
+            .map(|c| i16::from_le_bytes([c[0], c[1]]) as f32 / 32768.0)
+            .collect(),
+        (1, 24) => data
+            .chunks_exact(3)
+            .map(|c| (i32::from_le_bytes([0, c[0], c[1], c[2]]) >> 8) as f32 / 8_388_608.0)
+            .collect(),
+        (1, 32) => data
+            .chunks_exact(4)
+            .map(|c| i32::from_le_bytes([c[0], c[1], c[2], c[3]]) as f32 / 2_147_483_648.0)
+            .collect(),
+        (3, 32) => data
+            .chunks_exact(4)
+            .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect(),
+        _ => return Err(AudioError::Unsupported { format, bits }),
+    };
+
+    Ok(Pcm {
+        sample_rate,
+        channels,
+        samples,
+    })
+}
+
+/// 编码为 16 位 PCM WAV
+pub fn encode_wav(pcm: &Pcm) -> Vec<u8> {
+    let data_len = (pcm.samples.len() * 2) as u32;
+    let block_align = pcm.channels * 2;
+    let mut out = Vec::with_capacity(44 + data_len as usize);
+    out.extend_from_slice(b"RIFF");
+    out.extend_from_slice(&(36 + data_len).to_le_bytes());
+    out.extend_from_slice(b"WAVE");
+    out.extend_from_slice(b"fmt ");
+    out.extend_from_slice(&16u32.to_le_bytes());
+    out.extend_from_slice(&1u16.to_le_bytes());
+    out.extend_from_slice(&pcm.channels.to_le_bytes());
+    out.extend_from_slice(&pcm.sample_rate.to_le_bytes());
+    out.extend_from_slice(&(pcm.sample_rate * block_align as u32).to_le_bytes());
+    out.extend_from_slice(&block_align.to_le_bytes());
+    out.extend_from_slice(&16u16.to_le_bytes());
+    out.extend_from_slice(b"data");
+    out.extend_from_slice(&data_len.to_le_bytes());
+    for &s in &pcm.samples {
+        let v = (s.clamp(-1.0, 1.0) * 32767.0).round() as i16;
+        out.extend_from_slice(&v.to_le_bytes());
+    }
+    out
+}
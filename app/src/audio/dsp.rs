@@ -0,0 +1,194 @@
+//! 纯 Rust 的基础音频算法：重采样、声道转换、变速不变调 (WSOLA)、变调不变速
+
+use super::Pcm;
+
+/// 把交错排列的样本拆成每个声道一条
+fn deinterleave(pcm: &Pcm) -> Vec<Vec<f32>> {
+    let channels = pcm.channels.max(1) as usize;
+    (0..channels)
+        .map(|ch| {
+            pcm.samples
+                .iter()
+                .skip(ch)
+                .step_by(channels)
+                .copied()
+                .collect()
+        })
+        .collect()
+}
+
+fn interleave(channels: &[Vec<f32>]) -> Vec<f32> {
+    let frames = channels.iter().map(Vec::len).min().unwrap_or(0);
+    let mut out = Vec::with_capacity(frames * channels.len());
+    for i in 0..frames {
+        for ch in channels {
+            out.push(ch[i]);
+        }
+    }
+    out
+}
+
+/// 对每个声道分别处理
+pub(super) fn map_channels(pcm: &Pcm, f: impl Fn(&[f32]) -> Vec<f32>) -> Pcm {
+    let channels: Vec<Vec<f32>> = deinterleave(pcm).iter().map(|ch| f(ch)).collect();
+    Pcm {
+        sample_rate: pcm.sample_rate,
+        channels: pcm.channels,
+        samples: interleave(&channels),
+    }
+}
+
+/// 线性插值重采样：`factor` > 1 时播放变快、音调升高
+fn resample_channel(input: &[f32], factor: f32) -> Vec<f32> {
+    if input.is_empty() || factor <= 0.0 {
+        return Vec::new();
+    }
+    let len = (input.len() as f32 / factor).floor() as usize;
+    (0..len)
+        .map(|i| {
+            let pos = i as f32 * factor;
+            let idx = pos as usize;
+            let frac = pos - idx as f32;
+            let a = input[idx.min(input.len() - 1)];
+            let b = input[(idx + 1).min(input.len() - 1)];
+            a + (b - a) * frac
+        })
+        .collect()
+}
+
+/// WSOLA 变速：`rate` > 1 时变快，音调保持不变
+fn stretch_channel(input: &[f32], rate: f32, sample_rate: u32) -> Vec<f32> {
+    // 窗长 30ms，50% 重叠，允许 ±8ms 的相位对齐搜索
+    let window = ((sample_rate as f32 * 0.03) as usize).max(64);
+    let hop_out = window / 2;
+    let hop_in = hop_out as f32 * rate;
+    let tolerance = (sample_rate as f32 * 0.008) as usize;
+
+    if input.len() < window * 2 {
+        return resample_channel(input, rate);
+    }
+
+    let hann: Vec<f32> = (0..window)
+        .map(|i| 0.5 - 0.5 * (2.0 * std::f32::consts::PI * i as f32 / window as f32).cos())
+        .collect();
+
+    let out_len = (input.len() as f32 / rate) as usize + window;
+    let mut out = vec![0.0f32; out_len];
+    let mut norm = vec![0.0f32; out_len];
+
+    // 上一帧自然延续的位置，新帧尽量与之波形对齐
+    let mut prev_end: usize = 0;
+    let mut out_pos = 0;
+    let mut frame = 0usize;
+    loop {
+        let nominal = (frame as f32 * hop_in) as usize;
+        if nominal + window >= input.len() || out_pos + window >= out_len {
+            break;
+        }
+        let start = if frame == 0 {
+            0
+        } else {
+            best_offset(input, prev_end, nominal, tolerance, hop_out)
+        };
+        for i in 0..window {
+            out[out_pos + i] += input[start + i] * hann[i];
+            norm[out_pos + i] += hann[i];
+        }
+        prev_end = start + hop_out;
+        out_pos += hop_out;
+        frame += 1;
+    }
+
+    for (s, n) in out.iter_mut().zip(&norm) {
+        if *n > 1e-3 {
+            *s /= n;
+        }
+    }
+    out.truncate(out_pos + hop_out);
+    out
+}
+
+/// 在 `nominal` 附近寻找与 `reference` 处波形最相似的起点
+fn best_offset(
+    input: &[f32],
+    reference: usize,
+    nominal: usize,
+    tolerance: usize,
+    len: usize,
+) -> usize {
+    let lo = nominal.saturating_sub(tolerance);
+    let hi = (nominal + tolerance).min(input.len().saturating_sub(len * 2));
+    if reference + len > input.len() || lo >= hi {
+        return nominal.min(input.len().saturating_sub(len * 2));
+    }
+    let target = &input[reference..reference + len];
+    let mut best = nominal.clamp(lo, hi);
+    let mut best_score = f32::MIN;
+    // 步长 2 足以满足语音，计算量减半
+    for candidate in (lo..=hi).step_by(2) {
+        let score: f32 = target
+            .iter()
+            .zip(&input[candidate..candidate + len])
+            .map(|(a, b)| a * b)
+            .sum();
+        if score > best_score {
+            best_score = score;
+            best = candidate;
+        }
+    }
+    best
+}
+
+/// 变速不变调
+pub fn time_stretch(pcm: &Pcm, rate: f32) -> Pcm {
+    if (rate - 1.0).abs() < 1e-3 {
+        return pcm.clone();
+    }
+    map_channels(pcm, |ch| stretch_channel(ch, rate, pcm.sample_rate))
+}
+
+/// 变调不变速：`semitones` 为正时升调
+pub fn pitch_shift(pcm: &Pcm, semitones: f32) -> Pcm {
+    if semitones.abs() < 1e-3 {
+        return pcm.clone();
+    }
+    let factor = 2f32.powf(semitones / 12.0);
+    // 先放慢 factor 倍 (时长变长)，再重采样加快 factor 倍，时长复原、音调改变
+    map_channels(pcm, |ch| {
+        resample_channel(&stretch_channel(ch, 1.0 / factor, pcm.sample_rate), factor)
+    })
+}
+
+/// 重采样到 `sample_rate`
+pub fn resample(pcm: &Pcm, sample_rate: u32) -> Pcm {
+    if pcm.sample_rate == sample_rate || sample_rate == 0 {
+        return pcm.clone();
+    }
+    let factor = pcm.sample_rate as f32 / sample_rate as f32;
+    Pcm {
+        sample_rate,
+        ..map_channels(pcm, |ch| resample_channel(ch, factor))
+    }
+}
+
+/// 转换声道数：单声道复制到各声道，多声道先平均成单声道再复制
+pub fn convert_channels(pcm: &Pcm, channels: u16) -> Pcm {
+    let from = pcm.channels.max(1) as usize;
+    let to = channels.max(1) as usize;
+    if from == to {
+        return pcm.clone();
+    }
+    let samples = pcm
+        .samples
+        .chunks_exact(from)
+        .flat_map(|frame| {
+            let mono = frame.iter().sum::<f32>() / from as f32;
+            std::iter::repeat_n(mono, to)
+        })
+        .collect();
+    Pcm {
+        sample_rate: pcm.sample_rate,
+        channels: to as u16,
+        samples,
+    }
+}
@@ -0,0 +1,244 @@
+//! 变声效果：共振峰偏移、机器人、电话音、混响、回声、合唱
+
+use super::dsp::map_channels;
+use super::fft::{mirror_spectrum, process_stft};
+use super::Pcm;
+use std::f32::consts::PI;
+
+/// STFT 帧长与帧移
+const FRAME: usize = 1024;
+const HOP: usize = FRAME / 4;
+
+/// 共振峰偏移：只移动频谱包络 (音色变 "大/小")，音高不变。`semitones` 为正时声音更尖细
+pub fn formant_shift(pcm: &Pcm, semitones: f32) -> Pcm {
+    if semitones.abs() < 1e-3 {
+        return pcm.clone();
+    }
+    let factor = 2f32.powf(semitones / 12.0);
+    // 约 300Hz 的平滑宽度，足以抹平谐波、保留共振峰
+    let radius = ((300.0 * FRAME as f32 / pcm.sample_rate as f32) as usize).max(2);
+    map_channels(pcm, |ch| {
+        process_stft(ch, FRAME, HOP, |re, im| {
+            let bins = FRAME / 2 + 1;
+            let mag: Vec<f32> = (0..bins).map(|k| re[k].hypot(im[k])).collect();
+            let envelope = smooth(&mag, radius);
+            for k in 0..bins {
+                // 新包络取自原频谱 k / factor 处
+                let src = k as f32 / factor;
+                let idx = src as usize;
+                let target = if idx + 1 < bins {
+                    let frac = src - idx as f32;
+                    envelope[idx] * (1.0 - frac) + envelope[idx + 1] * frac
+                } else {
+                    0.0
+                };
+                let gain = (target / envelope[k].max(1e-6)).min(10.0);
+                re[k] *= gain;
+                im[k] *= gain;
+            }
+            mirror_spectrum(re, im);
+        })
+    })
+}
+
+/// 频谱幅度的滑动平均，作为粗略的频谱包络
+fn smooth(mag: &[f32], radius: usize) -> Vec<f32> {
+    let mut prefix = Vec::with_capacity(mag.len() + 1);
+    prefix.push(0.0f32);
+    for m in mag {
+        prefix.push(prefix.last().copied().unwrap_or(0.0) + m);
+    }
+    (0..mag.len())
+        .map(|k| {
+            let lo = k.saturating_sub(radius);
+            let hi = (k + radius + 1).min(mag.len());
+            (prefix[hi] - prefix[lo]) / (hi - lo) as f32
+        })
+        .collect()
+}
+
+/// 机器人声：每帧相位清零，音高被固定为帧移对应的频率
+pub fn robot(pcm: &Pcm) -> Pcm {
+    map_channels(pcm, |ch| {
+        process_stft(ch, FRAME, HOP, |re, im| {
+            for k in 0..=FRAME / 2 {
+                // 零相位再平移半帧，让脉冲落在窗的中央
+                let sign = if k % 2 == 0 { 1.0 } else { -1.0 };
+                re[k] = re[k].hypot(im[k]) * sign;
+                im[k] = 0.0;
+            }
+            mirror_spectrum(re, im);
+        })
+    })
+}
+
+/// 二阶 IIR 滤波器 (RBJ Audio EQ Cookbook)
+pub(super) struct Biquad {
+    b: [f32; 3],
+    a: [f32; 2],
+    x: [f32; 2],
+    y: [f32; 2],
+}
+
+impl Biquad {
+    pub(super) fn new(b: [f32; 3], a0: f32, a: [f32; 2]) -> Self {
+        Biquad {
+            b: b.map(|v| v / a0),
+            a: a.map(|v| v / a0),
+            x: [0.0; 2],
+            y: [0.0; 2],
+        }
+    }
+
+    fn highpass(sample_rate: u32, freq: f32) -> Self {
+        let (cos, alpha) = Self::coefficients(sample_rate, freq);
+        Self::new(
+            [(1.0 + cos) / 2.0, -(1.0 + cos), (1.0 + cos) / 2.0],
+            1.0 + alpha,
+            [-2.0 * cos, 1.0 - alpha],
+        )
+    }
+
+    fn lowpass(sample_rate: u32, freq: f32) -> Self {
+        let (cos, alpha) = Self::coefficients(sample_rate, freq);
+        Self::new(
+            [(1.0 - cos) / 2.0, 1.0 - cos, (1.0 - cos) / 2.0],
+            1.0 + alpha,
+            [-2.0 * cos, 1.0 - alpha],
+        )
+    }
+
+    /// Q 取 1/√2 (Butterworth)
+    fn coefficients(sample_rate: u32, freq: f32) -> (f32, f32) {
+        let freq = freq.min(sample_rate as f32 * 0.45);
+        let w0 = 2.0 * PI * freq / sample_rate as f32;
+        (w0.cos(), w0.sin() / std::f32::consts::SQRT_2)
+    }
+
+    pub(super) fn process(&mut self, x: f32) -> f32 {
+        let y = self.b[0] * x + self.b[1] * self.x[0] + self.b[2] * self.x[1]
+            - self.a[0] * self.y[0]
+            - self.a[1] * self.y[1];
+        self.x = [x, self.x[0]];
+        self.y = [y, self.y[0]];
+        y
+    }
+}
+
+/// 电话音：300–3400Hz 带通，再加一点软削波
+pub fn telephone(pcm: &Pcm) -> Pcm {
+    let sr = pcm.sample_rate;
+    map_channels(pcm, |ch| {
+        let mut filters = [
+            Biquad::highpass(sr, 300.0),
+            Biquad::highpass(sr, 300.0),
+            Biquad::lowpass(sr, 3400.0),
+            Biquad::lowpass(sr, 3400.0),
+        ];
+        let drive = 1.5f32;
+        ch.iter()
+            .map(|&x| {
+                let y = filters.iter_mut().fold(x, |v, f| f.process(v));
+                (y * drive).tanh() / drive.tanh()
+            })
+            .collect()
+    })
+}
+
+/// 末尾补上 `secs` 秒静音，给混响、回声留出尾音
+fn with_tail(input: &[f32], sample_rate: u32, secs: f32) -> Vec<f32> {
+    let mut out = input.to_vec();
+    out.resize(input.len() + (sample_rate as f32 * secs) as usize, 0.0);
+    out
+}
+
+/// 混响 (Schroeder)：4 个并联梳状滤波器 + 2 个串联全通滤波器。`mix` 为湿声比例 (0–1)
+pub fn reverb(pcm: &Pcm, mix: f32) -> Pcm {
+    let mix = mix.clamp(0.0, 1.0);
+    if mix < 1e-3 {
+        return pcm.clone();
+    }
+    let sr = pcm.sample_rate;
+    let delay = |ms: f32| ((sr as f32 * ms / 1000.0) as usize).max(1);
+    map_channels(pcm, |ch| {
+        let dry = with_tail(ch, sr, 1.5);
+        let mut wet = vec![0.0f32; dry.len()];
+        for ms in [29.7, 37.1, 41.1, 43.7] {
+            let d = delay(ms);
+            let mut buf = vec![0.0f32; dry.len()];
+            for i in 0..dry.len() {
+                let fb = if i >= d { buf[i - d] } else { 0.0 };
+                buf[i] = dry[i] + 0.8 * fb;
+                wet[i] += buf[i] * 0.25;
+            }
+        }
+        for ms in [5.0, 1.7] {
+            let d = delay(ms);
+            let input = std::mem::take(&mut wet);
+            wet = vec![0.0f32; input.len()];
+            for i in 0..input.len() {
+                let x_d = if i >= d { input[i - d] } else { 0.0 };
+                let y_d = if i >= d { wet[i - d] } else { 0.0 };
+                wet[i] = -0.7 * input[i] + x_d + 0.7 * y_d;
+            }
+        }
+        dry.iter()
+            .zip(&wet)
+            .map(|(d, w)| d * (1.0 - mix) + w * mix)
+            .collect()
+    })
+}
+
+/// 回声：280ms 间隔的反馈延迟。`mix` 为回声音量 (0–1)
+pub fn echo(pcm: &Pcm, mix: f32) -> Pcm {
+    let mix = mix.clamp(0.0, 1.0);
+    if mix < 1e-3 {
+        return pcm.clone();
+    }
+    let sr = pcm.sample_rate;
+    let d = ((sr as f32 * 0.28) as usize).max(1);
+    map_channels(pcm, |ch| {
+        let dry = with_tail(ch, sr, 0.28 * 6.0);
+        let mut delayed = vec![0.0f32; dry.len()];
+        for i in d..dry.len() {
+            delayed[i] = dry[i - d] + 0.5 * delayed[i - d];
+        }
+        dry.iter().zip(&delayed).map(|(x, e)| x + e * mix).collect()
+    })
+}
+
+/// 合唱：用低频振荡调制 15–27ms 的延迟，与原声叠加。`mix` 为湿声比例 (0–1)
+pub fn chorus(pcm: &Pcm, mix: f32) -> Pcm {
+    let mix = mix.clamp(0.0, 1.0);
+    if mix < 1e-3 {
+        return pcm.clone();
+    }
+    let sr = pcm.sample_rate as f32;
+    map_channels(pcm, |ch| {
+        (0..ch.len())
+            .map(|i| {
+                let t = i as f32 / sr;
+                let delay = (0.021 + 0.006 * (2.0 * PI * 0.9 * t).sin()) * sr;
+                let pos = i as f32 - delay;
+                let wet = if pos >= 0.0 {
+                    let idx = pos as usize;
+                    let frac = pos - idx as f32;
+                    let next = ch.get(idx + 1).copied().unwrap_or(0.0);
+                    ch[idx] * (1.0 - frac) + next * frac
+                } else {
+                    0.0
+                };
+                (ch[i] + wet * mix) / (1.0 + mix)
+            })
+            .collect()
+    })
+}
+
+/// 峰值超过满刻度时整体压低，避免削波
+pub fn prevent_clipping(pcm: &mut Pcm) {
+    let peak = pcm.samples.iter().fold(0.0f32, |p, s| p.max(s.abs()));
+    if peak > 0.99 {
+        let gain = 0.99 / peak;
+        pcm.samples.iter_mut().for_each(|s| *s *= gain);
+    }
+}
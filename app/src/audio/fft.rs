@@ -0,0 +1,174 @@
+//! 基 2 快速傅里叶变换、短时傅里叶变换 (STFT) 与语谱图
+
+use super::Pcm;
+use std::f32::consts::PI;
+
+/// 原地 FFT，`re`/`im` 长度必须相同且为 2 的幂；`inverse` 时做逆变换并除以 N
+pub fn fft(re: &mut [f32], im: &mut [f32], inverse: bool) {
+    let n = re.len();
+    debug_assert!(n.is_power_of_two() && im.len() == n);
+
+    // 位反转重排
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut len = 2;
+    while len <= n {
+        let half = len / 2;
+        for k in 0..half {
+            let angle = sign * 2.0 * PI * k as f32 / len as f32;
+            let (wr, wi) = (angle.cos(), angle.sin());
+            for start in (0..n).step_by(len) {
+                let (a, b) = (start + k, start + k + half);
+                let tr = re[b] * wr - im[b] * wi;
+                let ti = re[b] * wi + im[b] * wr;
+                re[b] = re[a] - tr;
+                im[b] = im[a] - ti;
+                re[a] += tr;
+                im[a] += ti;
+            }
+        }
+        len <<= 1;
+    }
+
+    if inverse {
+        let scale = 1.0 / n as f32;
+        re.iter_mut().chain(im.iter_mut()).for_each(|v| *v *= scale);
+    }
+}
+
+/// 周期 Hann 窗
+pub fn hann(size: usize) -> Vec<f32> {
+    (0..size)
+        .map(|i| 0.5 - 0.5 * (2.0 * PI * i as f32 / size as f32).cos())
+        .collect()
+}
+
+/// 逐帧做 STFT → 修改频谱 → 逆变换，再加窗重叠相加。
+/// `process` 收到完整的 `size` 点频谱 (实部、虚部)，修改后应保持共轭对称
+pub fn process_stft(
+    input: &[f32],
+    size: usize,
+    hop: usize,
+    mut process: impl FnMut(&mut [f32], &mut [f32]),
+) -> Vec<f32> {
+    let window = hann(size);
+    let mut out = vec![0.0f32; input.len() + size];
+    let mut norm = vec![0.0f32; input.len() + size];
+    let mut re = vec![0.0f32; size];
+    let mut im = vec![0.0f32; size];
+
+    let mut pos = 0;
+    while pos < input.len() {
+        for i in 0..size {
+            re[i] = input.get(pos + i).copied().unwrap_or(0.0) * window[i];
+            im[i] = 0.0;
+        }
+        fft(&mut re, &mut im, false);
+        process(&mut re, &mut im);
+        fft(&mut re, &mut im, true);
+        for i in 0..size {
+            out[pos + i] += re[i] * window[i];
+            norm[pos + i] += window[i] * window[i];
+        }
+        pos += hop;
+    }
+
+    for (s, n) in out.iter_mut().zip(&norm) {
+        if *n > 1e-3 {
+            *s /= n;
+        }
+    }
+    out.truncate(input.len());
+    out
+}
+
+/// 由前半部分频谱 (0..=N/2) 补齐共轭对称的后半部分
+pub fn mirror_spectrum(re: &mut [f32], im: &mut [f32]) {
+    let n = re.len();
+    for k in 1..n / 2 {
+        re[n - k] = re[k];
+        im[n - k] = -im[k];
+    }
+    im[0] = 0.0;
+    im[n / 2] = 0.0;
+}
+
+/// 语谱图：在时间轴上均匀取 `columns` 帧做 FFT，频率按对数刻度分为 `bands` 个频带，
+/// 能量以整段最大值为 0 dB、显示 `range_db` 的动态范围，量化为 0 ~ 255。
+/// 结果按列存放：第 `c` 列第 `b` 个频带 (0 为最低频) 位于 `c * bands + b`
+pub fn spectrogram(pcm: &Pcm, columns: usize, bands: usize, range_db: f32) -> Vec<u8> {
+    const SIZE: usize = 1024;
+    const MIN_FREQ: f32 = 60.0;
+
+    let channels = pcm.channels.max(1) as usize;
+    let frames = pcm.samples.len() / channels;
+    if frames == 0 || columns == 0 || bands == 0 {
+        return Vec::new();
+    }
+    let mono: Vec<f32> = pcm
+        .samples
+        .chunks_exact(channels)
+        .map(|f| f.iter().sum::<f32>() / channels as f32)
+        .collect();
+
+    // 每个频带对应的 FFT 频点范围 [lo, hi)
+    let nyquist = pcm.sample_rate.max(1) as f32 / 2.0;
+    let bin_hz = nyquist / (SIZE / 2) as f32;
+    let ratio = (nyquist / MIN_FREQ).max(1.0);
+    let edges: Vec<usize> = (0..=bands)
+        .map(|b| {
+            let freq = MIN_FREQ * ratio.powf(b as f32 / bands as f32);
+            ((freq / bin_hz).round() as usize).min(SIZE / 2)
+        })
+        .collect();
+
+    let window = hann(SIZE);
+    let mut re = vec![0.0f32; SIZE];
+    let mut im = vec![0.0f32; SIZE];
+    let mut power = Vec::with_capacity(columns * bands);
+    for c in 0..columns {
+        // 帧中心均匀分布在整段音频上
+        let center = (c * 2 + 1) * frames / (columns * 2);
+        let start = center as isize - (SIZE / 2) as isize;
+        for i in 0..SIZE {
+            let at = start + i as isize;
+            let sample = if at >= 0 {
+                mono.get(at as usize).copied().unwrap_or(0.0)
+            } else {
+                0.0
+            };
+            re[i] = sample * window[i];
+            im[i] = 0.0;
+        }
+        fft(&mut re, &mut im, false);
+        for b in 0..bands {
+            let lo = edges[b].min(SIZE / 2 - 1);
+            let hi = edges[b + 1].max(lo + 1);
+            let sum: f32 = (lo..hi).map(|k| re[k] * re[k] + im[k] * im[k]).sum();
+            power.push(sum / (hi - lo) as f32);
+        }
+    }
+
+    let max = power.iter().fold(1e-12f32, |m, &p| m.max(p));
+    let range_db = range_db.max(1.0);
+    power
+        .into_iter()
+        .map(|p| {
+            let db = 10.0 * (p.max(1e-12) / max).log10();
+            ((1.0 + db / range_db).clamp(0.0, 1.0) * 255.0).round() as u8
+        })
+        .collect()
+}
@@ -0,0 +1,315 @@
+//! 背景音乐混音：内置铺底音乐、把音乐循环/裁剪到人声长度，并在说话时压低音乐 (闪避)
+//!
+//! 内置音乐由程序实时生成，不依赖音频素材文件。
+
+use super::dsp::{convert_channels, resample};
+use super::post::{fade, integrated_loudness, limit_peaks};
+use super::Pcm;
+use crate::i18n::Messages;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+/// 内置铺底音乐
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BuiltinBed {
+    /// 柔和的和弦铺底
+    Pad,
+    /// 雨声
+    Rain,
+    /// 轻快节拍
+    Pulse,
+}
+
+impl BuiltinBed {
+    pub const ALL: [BuiltinBed; 3] = [BuiltinBed::Pad, BuiltinBed::Rain, BuiltinBed::Pulse];
+
+    /// 界面上显示的名称
+    pub fn name(self, t: &'static Messages) -> &'static str {
+        match self {
+            BuiltinBed::Pad => t.bed_pad,
+            BuiltinBed::Rain => t.bed_rain,
+            BuiltinBed::Pulse => t.bed_pulse,
+        }
+    }
+
+    /// 生成一段可无缝循环的立体声音频
+    pub fn render(self, sample_rate: u32) -> Pcm {
+        match self {
+            BuiltinBed::Pad => render_pad(sample_rate),
+            BuiltinBed::Rain => render_rain(sample_rate),
+            BuiltinBed::Pulse => render_pulse(sample_rate),
+        }
+    }
+}
+
+/// 混音参数
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MixSettings {
+    /// 音乐响度相对人声响度的差值 (LU)，通常为负
+    pub music_db: f32,
+    /// 说话时是否压低音乐
+    pub ducking: bool,
+    /// 闪避时额外压低的量 (dB)，通常为负
+    pub duck_db: f32,
+    /// 人声开始前的音乐前奏 (秒)
+    pub lead_in: f32,
+    /// 人声结束后的音乐尾奏 (秒)，在尾奏内淡出
+    pub tail: f32,
+}
+
+impl Default for MixSettings {
+    fn default() -> Self {
+        MixSettings {
+            music_db: -15.0,
+            ducking: true,
+            duck_db: -10.0,
+            lead_in: 0.8,
+            tail: 1.5,
+        }
+    }
+}
+
+fn db_to_gain(db: f32) -> f32 {
+    10f32.powf(db / 20.0)
+}
+
+/// 循环衔接处的交叉淡化时长 (秒)
+const LOOP_CROSSFADE: f32 = 0.05;
+
+/// 把音乐循环或裁剪到 `frames` 帧，循环衔接处交叉淡化
+fn fit_length(bed: &Pcm, frames: usize) -> Pcm {
+    let channels = bed.channels.max(1) as usize;
+    let bed_frames = bed.samples.len() / channels;
+    let mut out = Pcm {
+        sample_rate: bed.sample_rate,
+        channels: bed.channels,
+        samples: Vec::with_capacity(frames * channels),
+    };
+    if bed_frames == 0 {
+        out.samples.resize(frames * channels, 0.0);
+        return out;
+    }
+    let crossfade = ((bed.sample_rate as f32 * LOOP_CROSSFADE) as usize).min(bed_frames / 4);
+    out.samples.extend_from_slice(&bed.samples);
+    while out.samples.len() < frames * channels {
+        // 新一轮的开头与上一轮的结尾重叠
+        let overlap_start = out.samples.len() - crossfade * channels;
+        for i in 0..crossfade * channels {
+            let t = (i / channels) as f32 / crossfade as f32;
+            out.samples[overlap_start + i] =
+                out.samples[overlap_start + i] * (1.0 - t) + bed.samples[i] * t;
+        }
+        out.samples
+            .extend_from_slice(&bed.samples[crossfade * channels..]);
+    }
+    out.samples.truncate(frames * channels);
+    out
+}
+
+/// 按人声计算每帧的音乐增益：人声出现前 `LOOKAHEAD` 秒开始压低，停止后缓慢恢复
+fn ducking_gains(voice: &Pcm, duck_db: f32) -> Vec<f32> {
+    const LOOKAHEAD: f32 = 0.1;
+    const THRESHOLD_DB: f32 = -40.0;
+    let channels = voice.channels.max(1) as usize;
+    let frames = voice.samples.len() / channels;
+    let sr = voice.sample_rate.max(1) as f32;
+    let coeff = |secs: f32| 1.0 - (-1.0 / (sr * secs)).exp();
+    let (env_attack, env_release) = (coeff(0.01), coeff(0.2));
+    let (gain_attack, gain_release) = (coeff(0.08), coeff(0.6));
+    let threshold = db_to_gain(THRESHOLD_DB);
+    let ducked = db_to_gain(duck_db.min(0.0));
+    let lookahead = (sr * LOOKAHEAD) as usize;
+
+    // 人声包络 (提前 lookahead 帧)
+    let mut envelope = 0.0f32;
+    let active: Vec<bool> = (0..frames)
+        .map(|i| {
+            let at = (i + lookahead).min(frames - 1);
+            let level = voice.samples[at * channels..(at + 1) * channels]
+                .iter()
+                .fold(0.0f32, |m, s| m.max(s.abs()));
+            let c = if level > envelope {
+                env_attack
+            } else {
+                env_release
+            };
+            envelope += (level - envelope) * c;
+            envelope > threshold
+        })
+        .collect();
+
+    let mut gain = 1.0f32;
+    active
+        .into_iter()
+        .map(|active| {
+            let (target, c) = if active {
+                (ducked, gain_attack)
+            } else {
+                (1.0, gain_release)
+            };
+            gain += (target - gain) * c;
+            gain
+        })
+        .collect()
+}
+
+/// 把人声和背景音乐混成一条：音乐按人声响度调整音量、循环到总长度、尾奏淡出，
+/// 说话时按设置压低；结果再做一次峰值限制
+pub fn mix(voice: &Pcm, bed: &Pcm, settings: &MixSettings) -> Pcm {
+    let sample_rate = voice.sample_rate;
+    let channels = voice.channels.max(bed.channels).clamp(1, 2);
+    let lead_in = settings.lead_in.clamp(0.0, 10.0);
+    let tail = settings.tail.clamp(0.0, 10.0);
+
+    // 人声前后补上前奏、尾奏的静音
+    let mut speech = Pcm::silence(sample_rate, channels, lead_in);
+    speech
+        .samples
+        .extend_from_slice(&convert_channels(voice, channels).samples);
+    speech
+        .samples
+        .extend_from_slice(&Pcm::silence(sample_rate, channels, tail).samples);
+    let frames = speech.samples.len() / channels as usize;
+
+    let bed = convert_channels(&resample(bed, sample_rate), channels);
+    let mut music = fit_length(&bed, frames);
+    // 音乐响度对齐到人声响度 + music_db；测不出响度时直接按 music_db 衰减
+    let level = match (integrated_loudness(voice), integrated_loudness(&bed)) {
+        (Some(voice_lufs), Some(bed_lufs)) => voice_lufs + settings.music_db - bed_lufs,
+        _ => settings.music_db,
+    };
+    let gain = db_to_gain(level.min(12.0));
+    music.samples.iter_mut().for_each(|s| *s *= gain);
+    fade(&mut music, lead_in.min(0.5), tail.max(0.3));
+
+    let ducking = settings
+        .ducking
+        .then(|| ducking_gains(&speech, settings.duck_db));
+    for (i, (out, m)) in speech.samples.iter_mut().zip(&music.samples).enumerate() {
+        let duck = ducking.as_ref().map_or(1.0, |g| g[i / channels as usize]);
+        *out += m * duck;
+    }
+    limit_peaks(&mut speech, -1.0);
+    speech
+}
+
+/// 可复现的伪随机数 (xorshift)，用于生成噪声
+struct Noise(u32);
+
+impl Noise {
+    fn next(&mut self) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        self.0 as f32 / u32::MAX as f32 * 2.0 - 1.0
+    }
+}
+
+fn midi_to_freq(note: f32) -> f32 {
+    440.0 * 2f32.powf((note - 69.0) / 12.0)
+}
+
+/// 柔和铺底：C - Am - F - G 四个和弦各 4 秒，和弦之间平滑过渡
+fn render_pad(sample_rate: u32) -> Pcm {
+    const CHORDS: [[f32; 4]; 4] = [
+        [48.0, 60.0, 64.0, 67.0],
+        [45.0, 57.0, 60.0, 64.0],
+        [41.0, 57.0, 60.0, 65.0],
+        [43.0, 55.0, 59.0, 62.0],
+    ];
+    const CHORD_SECS: f32 = 4.0;
+    let sr = sample_rate as f32;
+    let chord_frames = (sr * CHORD_SECS) as usize;
+    let frames = chord_frames * CHORDS.len();
+    let mut samples = Vec::with_capacity(frames * 2);
+    for i in 0..frames {
+        let t = i as f32 / sr;
+        let chord = i / chord_frames;
+        let local = (i % chord_frames) as f32 / chord_frames as f32;
+        // 每个和弦前 20% 淡入，与上一个和弦的余音交叉
+        let blend = (local / 0.2).min(1.0);
+        let voice = |notes: &[f32; 4], detune: f32| {
+            notes
+                .iter()
+                .map(|&n| (2.0 * PI * midi_to_freq(n) * detune * t).sin())
+                .sum::<f32>()
+                / notes.len() as f32
+        };
+        let current = &CHORDS[chord];
+        let previous = &CHORDS[(chord + CHORDS.len() - 1) % CHORDS.len()];
+        // 左右声道轻微失谐，听起来更宽
+        let tremolo = 0.85 + 0.15 * (2.0 * PI * 0.25 * t).sin();
+        for detune in [1.0, 1.003] {
+            let value = voice(current, detune) * blend + voice(previous, detune) * (1.0 - blend);
+            samples.push(value * 0.3 * tremolo);
+        }
+    }
+    Pcm {
+        sample_rate,
+        channels: 2,
+        samples,
+    }
+}
+
+/// 雨声：低通滤波的噪声作为底，叠加随机的雨滴
+fn render_rain(sample_rate: u32) -> Pcm {
+    let sr = sample_rate as f32;
+    let frames = (sr * 12.0) as usize;
+    let mut noise = Noise(0x9E37_79B9);
+    let smoothing = 1.0 - (-2.0 * PI * 1200.0 / sr).exp();
+    let mut lowpassed = [0.0f32; 2];
+    let mut drops = [0.0f32; 2];
+    let decay = (-1.0 / (sr * 0.004)).exp();
+    let mut samples = Vec::with_capacity(frames * 2);
+    for _ in 0..frames {
+        for ch in 0..2 {
+            lowpassed[ch] += (noise.next() - lowpassed[ch]) * smoothing;
+            // 平均每秒每声道约 30 滴
+            if noise.next() > 1.0 - 60.0 / sr {
+                drops[ch] = 0.3 + 0.4 * noise.next().abs();
+            }
+            drops[ch] *= decay;
+            samples.push(lowpassed[ch] * 0.5 + drops[ch] * noise.next());
+        }
+    }
+    Pcm {
+        sample_rate,
+        channels: 2,
+        samples,
+    }
+}
+
+/// 轻快节拍：每分钟 100 拍，正拍底鼓、反拍踩镲，加一条低音
+fn render_pulse(sample_rate: u32) -> Pcm {
+    const BPM: f32 = 100.0;
+    const BASS: [f32; 4] = [36.0, 33.0, 29.0, 31.0];
+    let sr = sample_rate as f32;
+    let beat = 60.0 / BPM;
+    let frames = (sr * beat * 16.0) as usize;
+    let mut noise = Noise(0x1234_5678);
+    let mut samples = Vec::with_capacity(frames * 2);
+    for i in 0..frames {
+        let t = i as f32 / sr;
+        let in_beat = t % beat;
+        let beat_index = (t / beat) as usize;
+        // 底鼓：120Hz 快速下滑到 50Hz 的正弦
+        let kick_freq = 50.0 + 70.0 * (-in_beat * 30.0).exp();
+        let kick = (2.0 * PI * kick_freq * in_beat).sin() * (-in_beat * 12.0).exp();
+        // 踩镲：反拍上的短促噪声
+        let off = (t + beat / 2.0) % beat;
+        let hat = noise.next() * (-off * 60.0).exp() * 0.25;
+        // 低音：每 4 拍换一个音
+        let bass_note = BASS[(beat_index / 4) % BASS.len()];
+        // 低音每拍从 0 起音，换音时不会有爆音
+        let bass_env = (1.0 - (-in_beat * 40.0).exp()) * (-in_beat * 1.5).exp();
+        let bass = (2.0 * PI * midi_to_freq(bass_note) * t).sin() * 0.25 * bass_env;
+        let value = (kick * 0.6 + bass) * 0.8;
+        samples.push(value + hat);
+        samples.push(value + hat * 0.7);
+    }
+    Pcm {
+        sample_rate,
+        channels: 2,
+        samples,
+    }
+}
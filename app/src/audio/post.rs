@@ -0,0 +1,355 @@
+//! 后期处理：响度归一化 (EBU R128 / ITU-R BS.1770)、首尾静音裁剪、淡入淡出与峰值限制
+//!
+//! 各环节都可以单独开关，默认全部关闭 (输出与未经后期处理时一致)，
+//! 按 裁剪 → 响度 → 限幅 → 淡入淡出 的顺序处理。
+
+use super::effects::Biquad;
+use super::Pcm;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+/// 每次请求可单独配置的后期处理选项，各环节需要用户主动开启
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PostProcessOptions {
+    /// 响度归一化到 `target_lufs`
+    pub loudness: bool,
+    pub target_lufs: f32,
+    /// 裁掉首尾静音
+    pub trim_silence: bool,
+    /// 开头、结尾加淡入淡出
+    pub fade: bool,
+    pub fade_in_ms: u32,
+    pub fade_out_ms: u32,
+    /// 峰值限制到 `ceiling_db` (dBFS)
+    pub limiter: bool,
+    pub ceiling_db: f32,
+}
+
+impl Default for PostProcessOptions {
+    fn default() -> Self {
+        PostProcessOptions {
+            loudness: false,
+            target_lufs: -16.0,
+            trim_silence: false,
+            fade: false,
+            fade_in_ms: 10,
+            fade_out_ms: 80,
+            limiter: false,
+            ceiling_db: -1.0,
+        }
+    }
+}
+
+impl PostProcessOptions {
+    pub fn any_enabled(&self) -> bool {
+        self.loudness || self.trim_silence || self.fade || self.limiter
+    }
+}
+
+/// 可设置的目标响度范围 (LUFS)
+pub const MIN_TARGET_LUFS: f32 = -30.0;
+pub const MAX_TARGET_LUFS: f32 = -9.0;
+
+/// 低于该电平 (dBFS) 视为静音
+const SILENCE_DB: f32 = -50.0;
+/// 裁剪静音后首尾各保留的时长 (秒)
+const SILENCE_PADDING: f32 = 0.05;
+
+/// 按选项依次处理，返回处理后的音频以及开头被裁掉的时长 (秒)，供调整时间戳使用
+pub fn process(pcm: &Pcm, options: &PostProcessOptions) -> (Pcm, f32) {
+    let (mut pcm, offset) = if options.trim_silence {
+        trim_silence(pcm)
+    } else {
+        (pcm.clone(), 0.0)
+    };
+    if options.loudness {
+        normalize_loudness(
+            &mut pcm,
+            options.target_lufs.clamp(MIN_TARGET_LUFS, MAX_TARGET_LUFS),
+        );
+    }
+    if options.limiter {
+        limit_peaks(&mut pcm, options.ceiling_db.clamp(-12.0, 0.0));
+    }
+    if options.fade {
+        fade(
+            &mut pcm,
+            options.fade_in_ms.min(5000) as f32 / 1000.0,
+            options.fade_out_ms.min(5000) as f32 / 1000.0,
+        );
+    }
+    (pcm, offset)
+}
+
+fn db_to_gain(db: f32) -> f32 {
+    10f32.powf(db / 20.0)
+}
+
+/// BS.1770 的 K 计权滤波：高架滤波 (模拟头部声学效应) + 高通 (RLB)。
+/// 系数推导与 libebur128 相同，48kHz 下与标准给出的系数一致
+fn k_weighting(sample_rate: u32) -> [Biquad; 2] {
+    let sr = sample_rate.max(1) as f32;
+
+    let (freq, gain_db, q) = (1_681.974_5_f32, 3.999_843_8f32, 0.707_175_24f32);
+    let k = (PI * freq / sr).tan();
+    let vh = 10f32.powf(gain_db / 20.0);
+    let vb = vh.powf(0.499_666_78);
+    let a0 = 1.0 + k / q + k * k;
+    let shelf = Biquad::new(
+        [
+            vh + vb * k / q + k * k,
+            2.0 * (k * k - vh),
+            vh - vb * k / q + k * k,
+        ],
+        a0,
+        [2.0 * (k * k - 1.0), 1.0 - k / q + k * k],
+    );
+
+    // 高通的分子按标准不做归一化
+    let (freq, q) = (38.135_47f32, 0.500_327_04f32);
+    let k = (PI * freq / sr).tan();
+    let a0 = 1.0 + k / q + k * k;
+    let highpass = Biquad::new(
+        [a0, -2.0 * a0, a0],
+        a0,
+        [2.0 * (k * k - 1.0), 1.0 - k / q + k * k],
+    );
+    [shelf, highpass]
+}
+
+/// 积分响度 (LUFS)：400ms 块、75% 重叠，先按 -70 LUFS 绝对门限、再按 -10 LU 相对门限筛选。
+/// 音频太短或全是静音时返回 `None`
+pub fn integrated_loudness(pcm: &Pcm) -> Option<f32> {
+    let channels = pcm.channels.max(1) as usize;
+    let frames = pcm.samples.len() / channels;
+    let block = (pcm.sample_rate as f32 * 0.4) as usize;
+    let step = block / 4;
+    if block == 0 || frames < block {
+        return None;
+    }
+
+    // 每个声道 K 计权后的平方值
+    let mut squared = vec![0.0f32; frames];
+    for ch in 0..channels {
+        let mut filters = k_weighting(pcm.sample_rate);
+        for (i, sq) in squared.iter_mut().enumerate() {
+            let y = filters
+                .iter_mut()
+                .fold(pcm.samples[i * channels + ch], |v, f| f.process(v));
+            *sq += y * y;
+        }
+    }
+
+    let loudness = |power: f64| -0.691 + 10.0 * power.max(1e-12).log10() as f32;
+    let blocks: Vec<f64> = (0..=(frames - block) / step)
+        .map(|b| {
+            let start = b * step;
+            squared[start..start + block]
+                .iter()
+                .map(|&v| v as f64)
+                .sum::<f64>()
+                / block as f64
+        })
+        .collect();
+
+    let gated_mean = |threshold: f32| {
+        let kept: Vec<f64> = blocks
+            .iter()
+            .copied()
+            .filter(|&p| loudness(p) > threshold)
+            .collect();
+        (!kept.is_empty()).then(|| kept.iter().sum::<f64>() / kept.len() as f64)
+    };
+    let absolute = gated_mean(-70.0)?;
+    let relative = gated_mean(loudness(absolute) - 10.0)?;
+    Some(loudness(relative))
+}
+
+/// 调整增益使积分响度达到 `target_lufs`；增益上限 +24dB，避免把底噪放得太大
+pub fn normalize_loudness(pcm: &mut Pcm, target_lufs: f32) {
+    if let Some(current) = integrated_loudness(pcm) {
+        let gain = db_to_gain((target_lufs - current).min(24.0));
+        pcm.samples.iter_mut().for_each(|s| *s *= gain);
+    }
+}
+
+/// 裁掉首尾低于 [`SILENCE_DB`] 的部分，首尾各留一点余量；返回开头裁掉的时长 (秒)
+pub fn trim_silence(pcm: &Pcm) -> (Pcm, f32) {
+    let channels = pcm.channels.max(1) as usize;
+    let frames = pcm.samples.len() / channels;
+    let threshold = db_to_gain(SILENCE_DB);
+    let loud = |frame: &usize| {
+        pcm.samples[frame * channels..(frame + 1) * channels]
+            .iter()
+            .any(|s| s.abs() > threshold)
+    };
+    let (Some(first), Some(last)) = ((0..frames).find(loud), (0..frames).rev().find(loud)) else {
+        return (pcm.clone(), 0.0);
+    };
+    let padding = (pcm.sample_rate as f32 * SILENCE_PADDING) as usize;
+    let start = first.saturating_sub(padding);
+    let end = (last + 1 + padding).min(frames);
+    let trimmed = Pcm {
+        sample_rate: pcm.sample_rate,
+        channels: pcm.channels,
+        samples: pcm.samples[start * channels..end * channels].to_vec(),
+    };
+    (trimmed, start as f32 / pcm.sample_rate.max(1) as f32)
+}
+
+/// 线性淡入淡出
+pub fn fade(pcm: &mut Pcm, in_secs: f32, out_secs: f32) {
+    let channels = pcm.channels.max(1) as usize;
+    let frames = pcm.samples.len() / channels;
+    let fade_in = ((pcm.sample_rate as f32 * in_secs) as usize).min(frames / 2);
+    let fade_out = ((pcm.sample_rate as f32 * out_secs) as usize).min(frames / 2);
+    for i in 0..fade_in {
+        let gain = i as f32 / fade_in as f32;
+        pcm.samples[i * channels..(i + 1) * channels]
+            .iter_mut()
+            .for_each(|s| *s *= gain);
+    }
+    for i in 0..fade_out {
+        let gain = i as f32 / fade_out as f32;
+        let frame = frames - 1 - i;
+        pcm.samples[frame * channels..(frame + 1) * channels]
+            .iter_mut()
+            .for_each(|s| *s *= gain);
+    }
+}
+
+/// 前视峰值限制器：提前 5ms 平滑压低增益，使任何样本都不超过 `ceiling_db`，之后按 50ms 释放
+pub fn limit_peaks(pcm: &mut Pcm, ceiling_db: f32) {
+    let channels = pcm.channels.max(1) as usize;
+    let frames = pcm.samples.len() / channels;
+    let ceiling = db_to_gain(ceiling_db);
+    let lookahead = ((pcm.sample_rate as f32 * 0.005) as usize).max(1);
+    let release = 1.0 / (pcm.sample_rate as f32 * 0.05).max(1.0);
+
+    // 每帧不超限所需的增益
+    let required: Vec<f32> = (0..frames)
+        .map(|i| {
+            let peak = pcm.samples[i * channels..(i + 1) * channels]
+                .iter()
+                .fold(0.0f32, |p, s| p.max(s.abs()));
+            if peak > ceiling {
+                ceiling / peak
+            } else {
+                1.0
+            }
+        })
+        .collect();
+    if required.iter().all(|&g| g >= 1.0) {
+        return;
+    }
+
+    // 向后看 `lookahead` 帧取最小值，再做同长度的滑动平均：
+    // 峰值前的增益逐渐下降，且峰值处的增益不会高于所需值
+    let ahead_min: Vec<f32> = (0..frames)
+        .map(|i| {
+            required[i..(i + lookahead + 1).min(frames)]
+                .iter()
+                .fold(1.0f32, |m, &g| m.min(g))
+        })
+        .collect();
+    let mut sum = 0.0f32;
+    let mut envelope = 1.0f32;
+    for i in 0..frames {
+        sum += ahead_min[i];
+        if i > lookahead {
+            sum -= ahead_min[i - lookahead - 1];
+        }
+        let smoothed = sum / (i.min(lookahead) + 1) as f32;
+        envelope = smoothed.min(envelope + release);
+        pcm.samples[i * channels..(i + 1) * channels]
+            .iter_mut()
+            .for_each(|s| *s *= envelope);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 48_000;
+
+    /// `secs` 秒的 1kHz 正弦，峰值为 `peak_db` dBFS，各声道相同
+    fn sine(peak_db: f32, secs: f32, channels: u16) -> Pcm {
+        let amp = db_to_gain(peak_db);
+        let frames = (RATE as f32 * secs) as usize;
+        Pcm {
+            sample_rate: RATE,
+            channels,
+            samples: (0..frames)
+                .flat_map(|i| {
+                    let s = amp * (2.0 * PI * 1000.0 * i as f32 / RATE as f32).sin();
+                    std::iter::repeat_n(s, channels as usize)
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn sine_loudness_matches_bs1770() {
+        // 1kHz 处 K 计权约 +0.69dB，与公式中的 -0.691 抵消：-20dBFS 正弦 (有效值 -23.01dB) 即 -23.01 LUFS
+        let mono = integrated_loudness(&sine(-20.0, 3.0, 1)).unwrap();
+        assert!((mono + 23.01).abs() < 0.1, "{mono}");
+        // 两个声道的能量相加，响度高 3dB
+        let stereo = integrated_loudness(&sine(-20.0, 3.0, 2)).unwrap();
+        assert!((stereo - mono - 3.01).abs() < 0.1, "{stereo}");
+
+        assert_eq!(integrated_loudness(&sine(-20.0, 0.2, 1)), None);
+        assert_eq!(integrated_loudness(&sine(-120.0, 3.0, 1)), None);
+    }
+
+    #[test]
+    fn normalizes_to_target() {
+        let mut pcm = sine(-30.0, 3.0, 1);
+        normalize_loudness(&mut pcm, -16.0);
+        let lufs = integrated_loudness(&pcm).unwrap();
+        assert!((lufs + 16.0).abs() < 0.1, "{lufs}");
+    }
+
+    #[test]
+    fn trims_leading_and_trailing_silence() {
+        let second = RATE as usize;
+        let mut samples = vec![0.0; second];
+        samples.extend(std::iter::repeat_n(0.5, second / 2));
+        samples.extend(std::iter::repeat_n(0.0, second));
+        let pcm = Pcm {
+            sample_rate: RATE,
+            channels: 1,
+            samples,
+        };
+
+        let (trimmed, offset) = trim_silence(&pcm);
+        let padding = (RATE as f32 * SILENCE_PADDING) as usize;
+        assert!((offset - (1.0 - SILENCE_PADDING)).abs() < 1e-6, "{offset}");
+        assert_eq!(trimmed.samples.len(), second / 2 + 2 * padding);
+        assert_eq!(trimmed.samples[padding], 0.5);
+
+        // 全是静音时原样返回
+        let silent = Pcm {
+            sample_rate: RATE,
+            channels: 1,
+            samples: vec![0.0; second],
+        };
+        assert_eq!(trim_silence(&silent), (silent.clone(), 0.0));
+    }
+
+    #[test]
+    fn limiter_keeps_peaks_under_ceiling() {
+        let ceiling = db_to_gain(-3.0);
+        let mut pcm = sine(0.0, 0.5, 2);
+        limit_peaks(&mut pcm, -3.0);
+        let peak = pcm.samples.iter().fold(0.0f32, |p, s| p.max(s.abs()));
+        assert!(peak <= ceiling + 1e-6, "{peak}");
+        assert!(peak > ceiling * 0.9, "{peak}");
+
+        // 本来就不超限的音频不做处理
+        let quiet = sine(-6.0, 0.5, 1);
+        let mut limited = quiet.clone();
+        limit_peaks(&mut limited, -3.0);
+        assert_eq!(limited, quiet);
+    }
+}
@@ -0,0 +1,66 @@
+//! 多轨时间轴混音：把若干片段按位置、裁剪、淡入淡出和增益叠加成一条
+
+use super::dsp::{convert_channels, resample};
+use super::post::limit_peaks;
+use super::Pcm;
+
+/// 时间轴上的一个片段 (时间单位：秒)
+pub struct Placement<'a> {
+    /// 素材，须已转换为输出的采样率和声道数 (见 [`conform`])
+    pub source: &'a Pcm,
+    /// 在时间轴上的起点
+    pub start: f32,
+    /// 从素材的哪个位置开始截取
+    pub offset: f32,
+    /// 截取的长度
+    pub length: f32,
+    pub fade_in: f32,
+    pub fade_out: f32,
+    /// 线性增益 (已乘上轨道增益)
+    pub gain: f32,
+}
+
+/// 转换为指定的采样率和声道数
+pub fn conform(pcm: &Pcm, sample_rate: u32, channels: u16) -> Pcm {
+    convert_channels(&resample(pcm, sample_rate), channels)
+}
+
+/// 叠加所有片段，总长度为最晚结束的片段的终点；结果做一次峰值限制
+pub fn render(placements: &[Placement], sample_rate: u32, channels: u16) -> Pcm {
+    let ch = channels.max(1) as usize;
+    let sr = sample_rate as f32;
+    let to_frames = |secs: f32| (secs.max(0.0) * sr).round() as usize;
+
+    let total = placements
+        .iter()
+        .map(|p| to_frames(p.start) + to_frames(p.length))
+        .max()
+        .unwrap_or(0);
+    let mut out = Pcm::silence(sample_rate, channels, 0.0);
+    out.samples.resize(total * ch, 0.0);
+
+    for p in placements {
+        let source_frames = p.source.samples.len() / ch;
+        let from = to_frames(p.offset).min(source_frames);
+        let len = to_frames(p.length).min(source_frames - from);
+        let start = to_frames(p.start);
+        let fade_in = to_frames(p.fade_in).min(len / 2);
+        let fade_out = to_frames(p.fade_out).min(len / 2);
+        for i in 0..len {
+            let mut gain = p.gain;
+            if i < fade_in {
+                gain *= i as f32 / fade_in as f32;
+            }
+            if len - i <= fade_out {
+                gain *= (len - i - 1) as f32 / fade_out as f32;
+            }
+            let src = &p.source.samples[(from + i) * ch..(from + i + 1) * ch];
+            let dst = &mut out.samples[(start + i) * ch..(start + i + 1) * ch];
+            for (d, s) in dst.iter_mut().zip(src) {
+                *d += s * gain;
+            }
+        }
+    }
+    limit_peaks(&mut out, -1.0);
+    out
+}
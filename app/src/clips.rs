@@ -0,0 +1,130 @@
+//! 服务端生成的音频片段
+//!
+//! 片段保存在内存中，通过 `/api/clips/{id}` 提供给浏览器播放和下载，
+//! 避免把大段 Base64 在服务器函数之间来回传递。需要长期引用的片段 (如时间轴工程的素材)
+//! 可以额外保存到磁盘，内存淘汰或服务重启后仍能取回。
+
+/// 浏览器访问片段的地址
+pub fn clip_url(id: &str) -> String {
+    format!("/api/clips/{}", id)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod store {
+    use std::collections::{HashMap, VecDeque};
+    use std::sync::{Arc, LazyLock, Mutex};
+
+    /// 内存中最多保留的片段数，超出后淘汰最早的片段
+    const MAX_CLIPS: usize = 256;
+
+    pub struct Clip {
+        pub content_type: &'static str,
+        pub bytes: Vec<u8>,
+    }
+
+    #[derive(Default)]
+    struct ClipStore {
+        clips: HashMap<String, Arc<Clip>>,
+        order: VecDeque<String>,
+    }
+
+    static CLIPS: LazyLock<Mutex<ClipStore>> = LazyLock::new(Default::default);
+
+    /// 保存片段并返回其 ID
+    pub fn insert(content_type: &'static str, bytes: Vec<u8>) -> String {
+        let id = uuid::Uuid::new_v4().simple().to_string();
+        let mut store = CLIPS.lock().unwrap();
+        while store.order.len() >= MAX_CLIPS {
+            if let Some(oldest) = store.order.pop_front() {
+                store.clips.remove(&oldest);
+            }
+        }
+        store.order.push_back(id.clone());
+        store.clips.insert(
+            id.clone(),
+            Arc::new(Clip {
+                content_type,
+                bytes,
+            }),
+        );
+        id
+    }
+
+    /// 先查内存，再查磁盘
+    pub fn get(id: &str) -> Option<Arc<Clip>> {
+        if let Some(clip) = CLIPS.lock().unwrap().clips.get(id).cloned() {
+            return Some(clip);
+        }
+        load_persisted(id)
+    }
+
+    pub fn contains(id: &str) -> bool {
+        CLIPS.lock().unwrap().clips.contains_key(id) || load_persisted(id).is_some()
+    }
+
+    /// 磁盘上按内容类型区分的扩展名
+    const PERSISTED_TYPES: [(&str, &str); 2] = [("audio/wav", "wav"), ("audio/mpeg", "mp3")];
+
+    /// ID 由 `insert` 生成，只含十六进制字符；其余一律拒绝，避免拼出任意路径
+    fn is_valid_id(id: &str) -> bool {
+        !id.is_empty() && id.chars().all(|c| c.is_ascii_hexdigit())
+    }
+
+    fn load_persisted(id: &str) -> Option<Arc<Clip>> {
+        if !is_valid_id(id) {
+            return None;
+        }
+        PERSISTED_TYPES.iter().find_map(|&(content_type, ext)| {
+            let bytes = crate::store::load_file(&format!("clips/{}.{}", id, ext))?;
+            Some(Arc::new(Clip {
+                content_type,
+                bytes,
+            }))
+        })
+    }
+
+    /// 把片段保存到磁盘；片段不存在或格式不支持时返回错误
+    pub fn persist(id: &str) -> Result<(), String> {
+        if !is_valid_id(id) {
+            return Err("无效的片段 ID".to_string());
+        }
+        let clip = get(id).ok_or_else(|| "片段不存在或已过期".to_string())?;
+        let ext = PERSISTED_TYPES
+            .iter()
+            .find(|(content_type, _)| *content_type == clip.content_type)
+            .map(|(_, ext)| *ext)
+            .ok_or_else(|| format!("不支持保存 {} 格式的片段", clip.content_type))?;
+        let name = format!("clips/{}.{}", id, ext);
+        if crate::store::load_file(&name).is_some() {
+            return Ok(());
+        }
+        crate::store::save_file(&name, &clip.bytes).map_err(|e| e.to_string())
+    }
+
+    /// 片段是否已保存到磁盘
+    pub fn is_persisted(id: &str) -> bool {
+        is_valid_id(id)
+            && PERSISTED_TYPES
+                .iter()
+                .any(|(_, ext)| crate::store::load_file(&format!("clips/{}.{}", id, ext)).is_some())
+    }
+
+    /// 从内存和磁盘中删除片段
+    pub fn remove(id: &str) -> std::io::Result<()> {
+        {
+            let mut store = CLIPS.lock().unwrap();
+            store.clips.remove(id);
+            store.order.retain(|other| other != id);
+        }
+        if !is_valid_id(id) {
+            return Ok(());
+        }
+        for (_, ext) in PERSISTED_TYPES {
+            crate::store::remove_file(&format!("clips/{}.{}", id, ext))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use store::{contains, get, insert, is_persisted, persist, remove, Clip};
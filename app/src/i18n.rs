@@ -0,0 +1,714 @@
+//! 界面多语言
+//!
+//! 每种语言一份 [`Messages`] 文案表，字段由编译器保证各语言齐全。
+//! SSR 时按 Cookie、`Accept-Language` 的顺序协商语言并写入 `<html lang>`；
+//! 浏览器端从 `<html lang>` 读取同一语言完成 hydrate，切换语言时写回 Cookie。
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 保存用户所选语言的 Cookie
+pub const LOCALE_COOKIE: &str = "eardo_locale";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Locale {
+    #[default]
+    ZhCn,
+    En,
+    Ja,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 3] = [Locale::ZhCn, Locale::En, Locale::Ja];
+
+    /// BCP 47 语言标签
+    pub fn tag(self) -> &'static str {
+        match self {
+            Locale::ZhCn => "zh-CN",
+            Locale::En => "en",
+            Locale::Ja => "ja",
+        }
+    }
+
+    /// 用该语言自身书写的名称，用于语言切换菜单
+    pub fn native_name(self) -> &'static str {
+        match self {
+            Locale::ZhCn => "简体中文",
+            Locale::En => "English",
+            Locale::Ja => "日本語",
+        }
+    }
+
+    /// 按主语言子标签匹配，如 `en-US` → 英文、`zh-TW` → 中文
+    pub fn from_tag(tag: &str) -> Option<Locale> {
+        let primary = tag.trim().split(['-', '_']).next()?.to_ascii_lowercase();
+        match primary.as_str() {
+            "zh" => Some(Locale::ZhCn),
+            "en" => Some(Locale::En),
+            "ja" => Some(Locale::Ja),
+            _ => None,
+        }
+    }
+
+    /// 按 `Accept-Language` 的权重选出支持的语言，都不支持时用默认语言
+    pub fn negotiate(accept_language: &str) -> Locale {
+        accept_language
+            .split(',')
+            .filter_map(|item| {
+                let mut parts = item.split(';');
+                let locale = Locale::from_tag(parts.next()?)?;
+                let quality = parts
+                    .find_map(|p| p.trim().strip_prefix("q="))
+                    .and_then(|q| q.parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                Some((locale, quality))
+            })
+            .filter(|(_, quality)| *quality > 0.0)
+            // 权重相同时保留先出现的
+            .fold(
+                None,
+                |best: Option<(Locale, f32)>, (locale, quality)| match best {
+                    Some((_, q)) if q >= quality => best,
+                    _ => Some((locale, quality)),
+                },
+            )
+            .map(|(locale, _)| locale)
+            .unwrap_or_default()
+    }
+
+    pub fn messages(self) -> &'static Messages {
+        match self {
+            Locale::ZhCn => &ZH_CN,
+            Locale::En => &EN,
+            Locale::Ja => &JA,
+        }
+    }
+}
+
+/// 当前请求或页面的语言
+///
+/// SSR：优先 Cookie 中保存的选择，其次 `Accept-Language`；浏览器端：读取 SSR 写入的 `<html lang>`
+pub fn request_locale() -> Locale {
+    #[cfg(feature = "ssr")]
+    {
+        use http::{header, request::Parts};
+
+        let Some(parts) = use_context::<Parts>() else {
+            return Locale::default();
+        };
+        if let Some(locale) = crate::session::read_cookie(&parts.headers, LOCALE_COOKIE)
+            .and_then(|tag| Locale::from_tag(&tag))
+        {
+            return locale;
+        }
+        parts
+            .headers
+            .get(header::ACCEPT_LANGUAGE)
+            .and_then(|v| v.to_str().ok())
+            .map(Locale::negotiate)
+            .unwrap_or_default()
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        web_sys::window()
+            .and_then(|w| w.document())
+            .and_then(|d| d.document_element())
+            .and_then(|el| el.get_attribute("lang"))
+            .and_then(|tag| Locale::from_tag(&tag))
+            .unwrap_or_default()
+    }
+}
+
+/// 通过 context 共享的当前语言
+#[derive(Clone, Copy)]
+pub struct I18n {
+    locale: RwSignal<Locale>,
+}
+
+impl I18n {
+    pub fn locale(&self) -> Locale {
+        self.locale.get()
+    }
+
+    /// 当前语言的文案 (会订阅语言变化)
+    pub fn t(&self) -> &'static Messages {
+        self.locale.get().messages()
+    }
+
+    /// 切换语言：更新界面、`<html lang>` 并写入 Cookie，下次访问时 SSR 直接使用
+    pub fn set_locale(&self, locale: Locale) {
+        self.locale.set(locale);
+        #[cfg(target_arch = "wasm32")]
+        {
+            use wasm_bindgen::JsCast;
+
+            let Some(document) = web_sys::window().and_then(|w| w.document()) else {
+                return;
+            };
+            if let Some(root) = document.document_element() {
+                let _ = root.set_attribute("lang", locale.tag());
+            }
+            if let Ok(html) = document.dyn_into::<web_sys::HtmlDocument>() {
+                let _ = html.set_cookie(&format!(
+                    "{}={}; Path=/; Max-Age={}; SameSite=Lax",
+                    LOCALE_COOKIE,
+                    locale.tag(),
+                    365 * 24 * 60 * 60
+                ));
+            }
+        }
+    }
+}
+
+/// 在应用根部提供语言 context
+pub fn provide_i18n() -> I18n {
+    let i18n = I18n {
+        locale: RwSignal::new(request_locale()),
+    };
+    provide_context(i18n);
+    i18n
+}
+
+pub fn use_i18n() -> I18n {
+    expect_context::<I18n>()
+}
+
+/// 界面文案的取值函数
+pub type Label = fn(&Messages) -> &'static str;
+
+/// 一种语言的全部界面文案
+pub struct Messages {
+    // --- 导航栏 ---
+    pub app_name: &'static str,
+    pub nav_voices: &'static str,
+    pub nav_playground: &'static str,
+    pub nav_voicefilter: &'static str,
+    pub nav_timeline: &'static str,
+    pub login_coming_soon: &'static str,
+    pub language: &'static str,
+    pub link_copied: &'static str,
+    pub share_link: &'static str,
+    pub theme_light: &'static str,
+    pub theme_dark: &'static str,
+    pub theme_system: &'static str,
+    pub palette: &'static str,
+    pub palette_summer: &'static str,
+    pub palette_ocean: &'static str,
+
+    // --- 首页 ---
+    pub hero_title: &'static str,
+    pub hero_subtitle: &'static str,
+    pub mode_single: &'static str,
+    pub mode_dialogue: &'static str,
+
+    pub text_input_title: &'static str,
+    pub text_placeholder: &'static str,
+    pub text_hint: &'static str,
+    pub ssml_hint: &'static str,
+
+    pub normalize_title: &'static str,
+    pub normalize_auto: &'static str,
+    pub normalize_chinese: &'static str,
+    pub normalize_english: &'static str,
+    pub normalize_example: &'static str,
+    pub rule_dates: &'static str,
+    pub rule_times: &'static str,
+    pub rule_currency: &'static str,
+    pub rule_percentages: &'static str,
+    pub rule_phone_numbers: &'static str,
+    pub rule_urls: &'static str,
+    pub rule_numbers: &'static str,
+
+    pub voice_title: &'static str,
+    pub voices_loading: &'static str,
+    pub voices_failed: &'static str,
+    pub my_voices: &'static str,
+    pub clone_voice: &'static str,
+    pub cloned_voice_desc: &'static str,
+    pub no_cloned_voices: &'static str,
+    pub cloned_voices_failed: &'static str,
+    pub synthesis_language: &'static str,
+    pub language_auto: &'static str,
+    pub dialect_needs_voice: &'static str,
+    pub segments_title: &'static str,
+    pub detect_segments: &'static str,
+    pub segments_hint: &'static str,
+    pub segments_empty: &'static str,
+
+    pub params_title: &'static str,
+    pub pitch: &'static str,
+    pub semitones: &'static str,
+    pub speed: &'static str,
+    pub emotion: &'static str,
+    pub emotion_neutral: &'static str,
+    pub emotion_happy: &'static str,
+    pub emotion_sad: &'static str,
+    pub emotion_angry: &'static str,
+    pub emotion_surprised: &'static str,
+
+    pub result_title: &'static str,
+    pub generate: &'static str,
+    pub generating: &'static str,
+    pub synthesizing: &'static str,
+    pub generate_done: &'static str,
+    pub processed_audio: &'static str,
+    pub download_audio: &'static str,
+    pub generate_failed: &'static str,
+    pub result_idle: &'static str,
+    pub chunks: &'static str,
+
+    pub post_title: &'static str,
+    pub post_loudness: &'static str,
+    pub post_trim_silence: &'static str,
+    pub post_fade: &'static str,
+    pub post_limiter: &'static str,
+    pub target_loudness: &'static str,
+
+    // --- 对白脚本 ---
+    pub dialogue_needs_voice: &'static str,
+    pub dialogue_lines_title: &'static str,
+    pub dialogue_gap: &'static str,
+    pub dialogue_empty: &'static str,
+    pub dialogue_generate_all: &'static str,
+    pub dialogue_remix: &'static str,
+    pub dialogue_done: &'static str,
+    pub dialogue_download: &'static str,
+    pub dialogue_placeholder: &'static str,
+    pub dialogue_format_hint: &'static str,
+    pub dialogue_speakers_title: &'static str,
+    pub select_voice: &'static str,
+    pub dialogue_regenerate_line: &'static str,
+
+    // --- 声线对比 ---
+    pub playground_subtitle: &'static str,
+    pub playground_text_title: &'static str,
+    pub playground_placeholder: &'static str,
+    pub playground_synthesize_all: &'static str,
+    pub playground_add_slot: &'static str,
+    pub playground_remove_slot: &'static str,
+    pub playground_preset_placeholder: &'static str,
+    /// 参数为从 1 开始的组号
+    pub playground_slot: fn(usize) -> String,
+    pub playground_saved: &'static str,
+    pub pause: &'static str,
+    pub play_from_position: &'static str,
+    pub playground_pick_best_title: &'static str,
+    pub playground_pick_best: &'static str,
+    pub playground_waiting: &'static str,
+
+    // --- 字幕 ---
+    pub subtitles_title: &'static str,
+    pub subtitle_line_width: &'static str,
+    pub subtitle_width_hint: &'static str,
+
+    // --- 背景音乐 ---
+    pub bed_pad: &'static str,
+    pub bed_rain: &'static str,
+    pub bed_pulse: &'static str,
+
+    // --- 分享 ---
+    pub expiry_never: &'static str,
+    pub expiry_day: &'static str,
+    pub expiry_week: &'static str,
+    pub expiry_month: &'static str,
+
+    // --- 时间轴 ---
+    pub untitled_project: &'static str,
+    pub track_voice: &'static str,
+    pub track_effects: &'static str,
+}
+
+static ZH_CN: Messages = Messages {
+    app_name: "耳朵",
+    nav_voices: "声音广场",
+    nav_playground: "声线对比",
+    nav_voicefilter: "变声器",
+    nav_timeline: "时间轴",
+    login_coming_soon: "登录功能开发中...",
+    language: "语言",
+    link_copied: "分享链接已复制：",
+    share_link: "分享链接：",
+    theme_light: "浅色模式",
+    theme_dark: "深色模式",
+    theme_system: "跟随系统",
+    palette: "配色",
+    palette_summer: "夏日",
+    palette_ocean: "海洋",
+
+    hero_title: "声音，也能如此多彩",
+    hero_subtitle: "输入文本，选择喜欢的声线，调整参数，体验声音的奇妙变化",
+    mode_single: "单人合成",
+    mode_dialogue: "对白脚本",
+
+    text_input_title: "文本输入",
+    text_placeholder: "请输入你想转换的文字...\n例如：你好，欢迎使用白昼聆夏",
+    text_hint: "输入文本将通过后端 TTS 转换为音频",
+    ssml_hint: "支持 SSML 标签：break、prosody、say-as、sub、phoneme (拼音)，例如 ",
+
+    normalize_title: "文本规范化",
+    normalize_auto: "自动识别语言",
+    normalize_chinese: "中文读法",
+    normalize_english: "英文读法",
+    normalize_example: "例如 2026-10-17 读作「二〇二六年十月十七日」，¥35.5 读作「三十五点五元」",
+    rule_dates: "日期",
+    rule_times: "时间",
+    rule_currency: "金额",
+    rule_percentages: "百分比",
+    rule_phone_numbers: "电话",
+    rule_urls: "网址",
+    rule_numbers: "数字",
+
+    voice_title: "声线选择",
+    voices_loading: "加载声线库...",
+    voices_failed: "加载声线库失败",
+    my_voices: "我的声音",
+    clone_voice: "复刻声音",
+    cloned_voice_desc: "我的复刻声音",
+    no_cloned_voices: "还没有复刻的声音",
+    cloned_voices_failed: "加载我的声音失败",
+    synthesis_language: "合成语言",
+    language_auto: "自动识别",
+    dialect_needs_voice: "复刻声音暂不支持方言，请选择上方的方言声线",
+    segments_title: "语言分段",
+    detect_segments: "检测",
+    segments_hint: "自动识别语言时，中英等混排文本会按语言分段合成；检测后可逐段修改语言",
+    segments_empty: "点击「检测」查看分段结果",
+
+    params_title: "参数调节",
+    pitch: "音高 (Pitch)",
+    semitones: "半音",
+    speed: "语速 (Speed)",
+    emotion: "情感 (Emotion)",
+    emotion_neutral: "平静",
+    emotion_happy: "开心",
+    emotion_sad: "悲伤",
+    emotion_angry: "生气",
+    emotion_surprised: "惊讶",
+
+    result_title: "输出结果",
+    generate: "生成音频",
+    generating: "正在生成...",
+    synthesizing: "AI 正在合成您的声音...",
+    generate_done: "生成完成！",
+    processed_audio: "处理后的音频：",
+    download_audio: "下载音频",
+    generate_failed: "生成失败",
+    result_idle: "输入文本后点击生成按钮",
+    chunks: "分段",
+
+    post_title: "后期处理",
+    post_loudness: "响度统一",
+    post_trim_silence: "裁剪首尾静音",
+    post_fade: "淡入淡出",
+    post_limiter: "峰值限制",
+    target_loudness: "目标响度",
+
+    dialogue_needs_voice: "未选择声线的说话人：",
+    dialogue_lines_title: "台词列表",
+    dialogue_gap: "句间停顿",
+    dialogue_empty: "在左侧输入脚本，每行一句，例如「Cherry: 你好」",
+    dialogue_generate_all: "生成全部",
+    dialogue_remix: "重新混音",
+    dialogue_done: "对白音轨已生成",
+    dialogue_download: "下载对白音轨",
+    dialogue_placeholder: "Cherry: 欢迎收听本期节目！\nEthan: 今天我们聊聊夏天。",
+    dialogue_format_hint: "每行格式为「说话人: 台词」，说话人为声线名称；其他角色首次出现时写作「@角色: 台词」。未标注说话人的行沿用上一行",
+    dialogue_speakers_title: "说话人声线",
+    select_voice: "选择声线",
+    dialogue_regenerate_line: "重新生成这一句",
+
+    playground_subtitle: "同一段文本，多种声线并排试听，挑出最合适的那一个",
+    playground_text_title: "对比文本",
+    playground_placeholder: "请输入要对比朗读的文本...",
+    playground_synthesize_all: "全部合成",
+    playground_add_slot: "添加一组",
+    playground_remove_slot: "移除一组",
+    playground_preset_placeholder: "最佳预设名，如 旁白-沉稳",
+    playground_slot: |n| format!("第 {} 组", n),
+    playground_saved: "已保存为预设",
+    pause: "暂停",
+    play_from_position: "从当前位置播放",
+    playground_pick_best_title: "把这一组的声线和参数保存为预设",
+    playground_pick_best: "选为最佳",
+    playground_waiting: "等待合成",
+
+    subtitles_title: "字幕",
+    subtitle_line_width: "每行宽度",
+    subtitle_width_hint: "按显示宽度计，一个汉字算 2",
+
+    bed_pad: "柔和铺底",
+    bed_rain: "雨声",
+    bed_pulse: "轻快节拍",
+
+    expiry_never: "永久有效",
+    expiry_day: "1 天",
+    expiry_week: "7 天",
+    expiry_month: "30 天",
+
+    untitled_project: "未命名工程",
+    track_voice: "人声",
+    track_effects: "音效",
+};
+
+static EN: Messages = Messages {
+    app_name: "Eardo",
+    nav_voices: "Voices",
+    nav_playground: "Compare",
+    nav_voicefilter: "Voice Changer",
+    nav_timeline: "Timeline",
+    login_coming_soon: "Sign-in coming soon...",
+    language: "Language",
+    link_copied: "Link copied:",
+    share_link: "Share link:",
+    theme_light: "Light mode",
+    theme_dark: "Dark mode",
+    theme_system: "Follow system",
+    palette: "Palette",
+    palette_summer: "Summer",
+    palette_ocean: "Ocean",
+
+    hero_title: "Give your words a voice",
+    hero_subtitle: "Enter text, pick a voice you like, tune the parameters and hear the difference",
+    mode_single: "Single voice",
+    mode_dialogue: "Dialogue script",
+
+    text_input_title: "Text",
+    text_placeholder: "Type the text you want to convert...\ne.g. Hello, welcome to Eardo",
+    text_hint: "The text is converted to audio by the TTS backend",
+    ssml_hint: "SSML tags supported: break, prosody, say-as, sub, phoneme (pinyin), e.g. ",
+
+    normalize_title: "Text normalization",
+    normalize_auto: "Detect language",
+    normalize_chinese: "Read as Chinese",
+    normalize_english: "Read as English",
+    normalize_example: "e.g. 2026-10-17 is read as \"October seventeenth, twenty twenty-six\", $35.5 as \"thirty-five dollars and fifty cents\"",
+    rule_dates: "Dates",
+    rule_times: "Times",
+    rule_currency: "Currency",
+    rule_percentages: "Percentages",
+    rule_phone_numbers: "Phone numbers",
+    rule_urls: "URLs",
+    rule_numbers: "Numbers",
+
+    voice_title: "Voice",
+    voices_loading: "Loading voices...",
+    voices_failed: "Failed to load voices",
+    my_voices: "My voices",
+    clone_voice: "Clone a voice",
+    cloned_voice_desc: "My cloned voice",
+    no_cloned_voices: "No cloned voices yet",
+    cloned_voices_failed: "Failed to load my voices",
+    synthesis_language: "Synthesis language",
+    language_auto: "Auto detect",
+    dialect_needs_voice: "Cloned voices don't support dialects yet; pick a dialect voice above",
+    segments_title: "Language segments",
+    detect_segments: "Detect",
+    segments_hint: "With auto detection, mixed-language text is synthesized segment by segment; detect to review and override each segment",
+    segments_empty: "Click \"Detect\" to see the segments",
+
+    params_title: "Parameters",
+    pitch: "Pitch",
+    semitones: "st",
+    speed: "Speed",
+    emotion: "Emotion",
+    emotion_neutral: "Neutral",
+    emotion_happy: "Happy",
+    emotion_sad: "Sad",
+    emotion_angry: "Angry",
+    emotion_surprised: "Surprised",
+
+    result_title: "Output",
+    generate: "Generate audio",
+    generating: "Generating...",
+    synthesizing: "Synthesizing your audio...",
+    generate_done: "Done!",
+    processed_audio: "Processed audio:",
+    download_audio: "Download audio",
+    generate_failed: "Generation failed",
+    result_idle: "Enter some text and click generate",
+    chunks: "Chunks",
+
+    post_title: "Post-processing",
+    post_loudness: "Normalize loudness",
+    post_trim_silence: "Trim silence",
+    post_fade: "Fade in/out",
+    post_limiter: "Peak limiter",
+    target_loudness: "Target loudness",
+
+    dialogue_needs_voice: "No voice selected for speaker: ",
+    dialogue_lines_title: "Lines",
+    dialogue_gap: "Pause between lines",
+    dialogue_empty: "Enter a script on the left, one line each, e.g. “Cherry: Hello”",
+    dialogue_generate_all: "Generate all",
+    dialogue_remix: "Remix",
+    dialogue_done: "Dialogue track ready",
+    dialogue_download: "Download dialogue track",
+    dialogue_placeholder: "Cherry: Welcome to this episode!\nEthan: Today we're talking about summer.",
+    dialogue_format_hint: "Write each line as “Speaker: line”, where the speaker is a voice name; other characters are written “@Character: line” the first time they appear. Lines without a speaker continue the previous one",
+    dialogue_speakers_title: "Speaker voices",
+    select_voice: "Select a voice",
+    dialogue_regenerate_line: "Regenerate this line",
+
+    playground_subtitle: "Listen to the same text in several voices side by side and pick the best one",
+    playground_text_title: "Text to compare",
+    playground_placeholder: "Enter the text to compare...",
+    playground_synthesize_all: "Synthesize all",
+    playground_add_slot: "Add a slot",
+    playground_remove_slot: "Remove a slot",
+    playground_preset_placeholder: "Preset name for the winner, e.g. Narrator - calm",
+    playground_slot: |n| format!("Slot {}", n),
+    playground_saved: "Saved as a preset",
+    pause: "Pause",
+    play_from_position: "Play from the current position",
+    playground_pick_best_title: "Save this slot's voice and settings as a preset",
+    playground_pick_best: "Pick as best",
+    playground_waiting: "Waiting to synthesize",
+
+    subtitles_title: "Subtitles",
+    subtitle_line_width: "Line width",
+    subtitle_width_hint: "Measured in display width; a CJK character counts as 2",
+
+    bed_pad: "Soft pad",
+    bed_rain: "Rain",
+    bed_pulse: "Light beat",
+
+    expiry_never: "Never expires",
+    expiry_day: "1 day",
+    expiry_week: "7 days",
+    expiry_month: "30 days",
+
+    untitled_project: "Untitled project",
+    track_voice: "Voice",
+    track_effects: "Effects",
+};
+
+static JA: Messages = Messages {
+    app_name: "耳朵",
+    nav_voices: "ボイス広場",
+    nav_playground: "ボイス比較",
+    nav_voicefilter: "ボイスチェンジャー",
+    nav_timeline: "タイムライン",
+    login_coming_soon: "ログイン機能は開発中です...",
+    language: "言語",
+    link_copied: "共有リンクをコピーしました：",
+    share_link: "共有リンク：",
+    theme_light: "ライトモード",
+    theme_dark: "ダークモード",
+    theme_system: "システムに合わせる",
+    palette: "配色",
+    palette_summer: "サマー",
+    palette_ocean: "オーシャン",
+
+    hero_title: "声で、言葉に彩りを",
+    hero_subtitle: "テキストを入力し、好きな声を選んでパラメータを調整しましょう",
+    mode_single: "単独合成",
+    mode_dialogue: "台本モード",
+
+    text_input_title: "テキスト入力",
+    text_placeholder: "変換したいテキストを入力してください...\n例：こんにちは、耳朵へようこそ",
+    text_hint: "入力したテキストはバックエンドの TTS で音声に変換されます",
+    ssml_hint: "SSML タグ（break、prosody、say-as、sub、ピンインの phoneme）に対応しています。例：",
+
+    normalize_title: "テキスト正規化",
+    normalize_auto: "言語を自動判定",
+    normalize_chinese: "中国語で読む",
+    normalize_english: "英語で読む",
+    normalize_example: "例：2026-10-17 や ¥35.5 などを読み上げやすい表記に変換します",
+    rule_dates: "日付",
+    rule_times: "時刻",
+    rule_currency: "金額",
+    rule_percentages: "パーセント",
+    rule_phone_numbers: "電話番号",
+    rule_urls: "URL",
+    rule_numbers: "数字",
+
+    voice_title: "ボイス選択",
+    voices_loading: "ボイスを読み込み中...",
+    voices_failed: "ボイスの読み込みに失敗しました",
+    my_voices: "マイボイス",
+    clone_voice: "ボイスを複製",
+    cloned_voice_desc: "複製したマイボイス",
+    no_cloned_voices: "複製したボイスはまだありません",
+    cloned_voices_failed: "マイボイスの読み込みに失敗しました",
+    synthesis_language: "合成言語",
+    language_auto: "自動判定",
+    dialect_needs_voice: "複製したボイスは方言に対応していません。上の方言ボイスを選んでください",
+    segments_title: "言語セグメント",
+    detect_segments: "検出",
+    segments_hint: "自動判定では、多言語が混在するテキストを言語ごとに分けて合成します。検出後にセグメントごとに言語を変更できます",
+    segments_empty: "「検出」を押すとセグメントが表示されます",
+
+    params_title: "パラメータ調整",
+    pitch: "ピッチ (Pitch)",
+    semitones: "半音",
+    speed: "話速 (Speed)",
+    emotion: "感情 (Emotion)",
+    emotion_neutral: "平静",
+    emotion_happy: "喜び",
+    emotion_sad: "悲しみ",
+    emotion_angry: "怒り",
+    emotion_surprised: "驚き",
+
+    result_title: "出力結果",
+    generate: "音声を生成",
+    generating: "生成中...",
+    synthesizing: "音声を合成しています...",
+    generate_done: "生成が完了しました！",
+    processed_audio: "処理後の音声：",
+    download_audio: "音声をダウンロード",
+    generate_failed: "生成に失敗しました",
+    result_idle: "テキストを入力して生成ボタンを押してください",
+    chunks: "セグメント",
+
+    post_title: "後処理",
+    post_loudness: "ラウドネス統一",
+    post_trim_silence: "前後の無音をカット",
+    post_fade: "フェードイン・アウト",
+    post_limiter: "ピークリミッター",
+    target_loudness: "目標ラウドネス",
+
+    dialogue_needs_voice: "ボイスが未選択の話者：",
+    dialogue_lines_title: "セリフ一覧",
+    dialogue_gap: "セリフ間の間",
+    dialogue_empty: "左側に台本を入力してください。1 行に 1 セリフ、例：「Cherry: こんにちは」",
+    dialogue_generate_all: "すべて生成",
+    dialogue_remix: "再ミックス",
+    dialogue_done: "台本の音声を生成しました",
+    dialogue_download: "台本の音声をダウンロード",
+    dialogue_placeholder: "Cherry: 今回もお聴きいただきありがとうございます！\nEthan: 今日は夏の話をしましょう。",
+    dialogue_format_hint: "各行は「話者: セリフ」の形式で、話者はボイス名です。その他の役は初登場時に「@役名: セリフ」と書きます。話者のない行は前の行の話者を引き継ぎます",
+    dialogue_speakers_title: "話者のボイス",
+    select_voice: "ボイスを選択",
+    dialogue_regenerate_line: "このセリフを再生成",
+
+    playground_subtitle: "同じテキストを複数のボイスで聴き比べて、いちばん合うものを選びましょう",
+    playground_text_title: "比較するテキスト",
+    playground_placeholder: "比較するテキストを入力してください...",
+    playground_synthesize_all: "すべて合成",
+    playground_add_slot: "グループを追加",
+    playground_remove_slot: "グループを削除",
+    playground_preset_placeholder: "ベストのプリセット名（例：ナレーション-落ち着き）",
+    playground_slot: |n| format!("グループ {}", n),
+    playground_saved: "プリセットに保存しました",
+    pause: "一時停止",
+    play_from_position: "現在の位置から再生",
+    playground_pick_best_title: "このグループのボイスと設定をプリセットに保存",
+    playground_pick_best: "ベストに選ぶ",
+    playground_waiting: "合成待ち",
+
+    subtitles_title: "字幕",
+    subtitle_line_width: "1 行の幅",
+    subtitle_width_hint: "表示幅で数え、全角文字は 2 として数えます",
+
+    bed_pad: "やわらかパッド",
+    bed_rain: "雨音",
+    bed_pulse: "軽快なビート",
+
+    expiry_never: "無期限",
+    expiry_day: "1 日",
+    expiry_week: "7 日",
+    expiry_month: "30 日",
+
+    untitled_project: "無題のプロジェクト",
+    track_voice: "ボイス",
+    track_effects: "効果音",
+};
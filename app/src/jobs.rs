@@ -0,0 +1,215 @@
+//! 生成任务的进度事件
+//!
+//! 服务端在合成过程中向任务发布 [`JobEvent`]，浏览器通过
+//! `/api/jobs/{id}/events` (SSE) 订阅，并用 [`JobProgress`] 汇总成进度条状态。
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JobEvent {
+    /// 任务开始，`total` 为分段总数
+    Started { total: usize },
+    /// 开始合成第 `index` 段
+    ChunkStarted { index: usize },
+    /// 第 `index` 段合成完成
+    ChunkDone { index: usize },
+    /// 第 `index` 段合成失败
+    ChunkFailed { index: usize, message: String },
+    /// 整个任务完成
+    Finished,
+    /// 整个任务失败
+    Failed { message: String },
+}
+
+impl JobEvent {
+    /// 是否为任务的最后一个事件
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, JobEvent::Finished | JobEvent::Failed { .. })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChunkStatus {
+    Pending,
+    Running,
+    Done,
+    Failed,
+}
+
+/// 由事件流汇总出的任务状态
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct JobProgress {
+    pub chunks: Vec<ChunkStatus>,
+    pub finished: bool,
+    pub error: Option<String>,
+}
+
+impl JobProgress {
+    pub fn apply(&mut self, event: JobEvent) {
+        match event {
+            JobEvent::Started { total } => self.chunks = vec![ChunkStatus::Pending; total],
+            JobEvent::ChunkStarted { index } => self.set_chunk(index, ChunkStatus::Running),
+            JobEvent::ChunkDone { index } => self.set_chunk(index, ChunkStatus::Done),
+            JobEvent::ChunkFailed { index, message } => {
+                self.set_chunk(index, ChunkStatus::Failed);
+                self.error = Some(message);
+            }
+            JobEvent::Finished => self.finished = true,
+            JobEvent::Failed { message } => {
+                self.finished = true;
+                self.error = Some(message);
+            }
+        }
+    }
+
+    fn set_chunk(&mut self, index: usize, status: ChunkStatus) {
+        if let Some(chunk) = self.chunks.get_mut(index) {
+            *chunk = status;
+        }
+    }
+
+    /// 已完成的分段数
+    pub fn done(&self) -> usize {
+        self.chunks
+            .iter()
+            .filter(|c| **c == ChunkStatus::Done)
+            .count()
+    }
+
+    /// 完成百分比 (0 ~ 100)
+    pub fn percent(&self) -> u32 {
+        if self.chunks.is_empty() {
+            return if self.finished { 100 } else { 0 };
+        }
+        (self.done() * 100 / self.chunks.len()) as u32
+    }
+}
+
+/// 订阅任务进度：`job_id` 变化时重新连接 SSE，并返回汇总后的进度信号
+pub fn use_job_progress(job_id: RwSignal<Option<String>>) -> RwSignal<JobProgress> {
+    let progress = RwSignal::new(JobProgress::default());
+
+    // EventSource 只存在于浏览器端，SSR 时不做任何事
+    #[cfg(target_arch = "wasm32")]
+    {
+        use wasm_bindgen::{closure::Closure, JsCast};
+        use web_sys::{EventSource, MessageEvent};
+
+        let source = StoredValue::new_local(None::<EventSource>);
+        Effect::new(move |_| {
+            if let Some(old) = source.get_value() {
+                old.close();
+            }
+            progress.set(JobProgress::default());
+
+            let Some(id) = job_id.get() else {
+                return;
+            };
+            let Ok(es) = EventSource::new(&format!("/api/jobs/{}/events", id)) else {
+                return;
+            };
+
+            let es_handle = es.clone();
+            let on_message = Closure::<dyn FnMut(MessageEvent)>::new(move |ev: MessageEvent| {
+                let Some(data) = ev.data().as_string() else {
+                    return;
+                };
+                if let Ok(event) = serde_json::from_str::<JobEvent>(&data) {
+                    // 任务结束后主动断开，否则浏览器会自动重连
+                    if event.is_terminal() {
+                        es_handle.close();
+                    }
+                    progress.update(|p| p.apply(event));
+                }
+            });
+            es.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+            on_message.forget();
+
+            source.set_value(Some(es));
+        });
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    let _ = job_id;
+
+    progress
+}
+
+/// 服务端的任务注册表
+#[cfg(not(target_arch = "wasm32"))]
+mod registry {
+    use super::JobEvent;
+    use std::collections::HashMap;
+    use std::sync::{LazyLock, Mutex};
+    use std::time::{Duration, Instant};
+    use tokio::sync::broadcast;
+
+    /// 结束后的任务保留多久，方便迟到的订阅者拿到最终状态
+    const FINISHED_TTL: Duration = Duration::from_secs(60);
+    /// 多久没有新事件的任务视为失效 (创建后从未开始，或中途中断没有发出结束事件)；
+    /// 仍在运行的任务每合成一段都会发布事件，不会被误删
+    const IDLE_TTL: Duration = Duration::from_secs(10 * 60);
+
+    struct Job {
+        /// 创建或最近一次发布事件的时间
+        last_active: Instant,
+        /// 已发布的事件，新订阅者先回放这些事件
+        history: Vec<JobEvent>,
+        sender: broadcast::Sender<JobEvent>,
+    }
+
+    static JOBS: LazyLock<Mutex<HashMap<String, Job>>> =
+        LazyLock::new(|| Mutex::new(HashMap::new()));
+
+    /// 注册一个新任务并返回其 ID
+    pub fn create() -> String {
+        let id = uuid::Uuid::new_v4().to_string();
+        let mut jobs = JOBS.lock().unwrap();
+        jobs.retain(|_, job| job.last_active.elapsed() < IDLE_TTL);
+        let (sender, _) = broadcast::channel(64);
+        jobs.insert(
+            id.clone(),
+            Job {
+                last_active: Instant::now(),
+                history: Vec::new(),
+                sender,
+            },
+        );
+        id
+    }
+
+    /// 向任务发布事件；任务不存在时静默忽略
+    pub fn publish(id: &str, event: JobEvent) {
+        let terminal = event.is_terminal();
+        {
+            let mut jobs = JOBS.lock().unwrap();
+            let Some(job) = jobs.get_mut(id) else {
+                return;
+            };
+            job.last_active = Instant::now();
+            job.history.push(event.clone());
+            // 没有订阅者时 send 会返回错误，这是正常情况
+            let _ = job.sender.send(event);
+        }
+
+        if terminal {
+            let id = id.to_string();
+            tokio::spawn(async move {
+                tokio::time::sleep(FINISHED_TTL).await;
+                JOBS.lock().unwrap().remove(&id);
+            });
+        }
+    }
+
+    /// 订阅任务：返回已发生的事件，以及 (任务尚未结束时) 后续事件的接收端
+    pub fn subscribe(id: &str) -> Option<(Vec<JobEvent>, Option<broadcast::Receiver<JobEvent>>)> {
+        let jobs = JOBS.lock().unwrap();
+        let job = jobs.get(id)?;
+        let finished = job.history.last().is_some_and(JobEvent::is_terminal);
+        let receiver = (!finished).then(|| job.sender.subscribe());
+        Some((job.history.clone(), receiver))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use registry::{create, publish, subscribe};
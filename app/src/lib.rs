@@ -2,16 +2,35 @@ use leptos::prelude::*;
 use leptos_meta::{provide_meta_context, MetaTags, Stylesheet, Title};
 use leptos_router::{
     components::{Route, Router, Routes},
-    StaticSegment,
+    hooks::use_location,
+    ParamSegment, SsrMode, StaticSegment,
 };
 
 mod api;
+pub mod audio;
+pub mod clips;
+pub mod i18n;
+pub mod jobs;
 mod pages;
+pub mod security;
+// 依赖 leptos_axum 读写 Cookie，只在 SSR 构建中可用
+#[cfg(feature = "ssr")]
+mod session;
+#[cfg(not(target_arch = "wasm32"))]
+mod store;
+pub mod text;
+pub mod theme;
 
 pub fn shell(options: LeptosOptions) -> impl IntoView {
+    // 按 Cookie / Accept-Language 协商界面语言，浏览器端 hydrate 时再从 lang 属性读回
+    let locale = i18n::request_locale();
+    // 主题同样在服务端写进 <html>，避免首屏先以默认主题渲染再闪烁
+    let theme = theme::request_theme();
+    #[cfg(feature = "ssr")]
+    security::apply_headers();
     view! {
         <!DOCTYPE html>
-        <html lang="zh-CN">
+        <html lang=locale.tag() data-mode=theme.mode.code() data-theme=theme.palette.code()>
             <head>
                 <meta charset="utf-8"/>
                 <meta name="viewport" content="width=device-width, initial-scale=1"/>
@@ -19,12 +38,11 @@ pub fn shell(options: LeptosOptions) -> impl IntoView {
                 <HydrationScripts options/>
 
                 <Stylesheet id="leptos" href="/pkg/eardo.css"/>
-                <link href="https://cdn.jsdelivr.net/npm/font-awesome@4.7.0/css/font-awesome.min.css" rel="stylesheet" />
 
                 <MetaTags/>
             </head>
 
-            <body class="bg-gradient-summer min-h-screen font-sans text-dark">
+            <body class="bg-gradient-theme min-h-screen font-sans text-dark">
                 <App/>
             </body>
         </html>
@@ -35,22 +53,47 @@ pub fn shell(options: LeptosOptions) -> impl IntoView {
 pub fn App() -> impl IntoView {
     // Provides context that manages stylesheets, titles, meta tags, etc.
     provide_meta_context();
+    let i18n = i18n::provide_i18n();
+    theme::provide_theme();
 
     view! {
         // sets the document title
-        <Title text="耳朵 - 白昼聆夏"/>
+        <Title text=move || format!("{} - 白昼聆夏", i18n.t().app_name)/>
 
         // content for this welcome page
         <Router>
             <main>
-                <pages::Header/>
+                <Navigation/>
                 <Routes fallback=|| "Page not found.".into_view()>
                     <Route path=StaticSegment("") view=pages::homepage::HomePage/>
-                    //<Route path=StaticSegment("playground") view=Playground/>
-                    //<Route path=StaticSegment("voicefilter") view=Voicefilter/>
+                    <Route path=StaticSegment("playground") view=pages::playground::Playground/>
+                    <Route path=StaticSegment("voicefilter") view=pages::voicefilter::Voicefilter/>
+                    <Route path=StaticSegment("my-voices") view=pages::voices::MyVoices/>
+                    <Route path=StaticSegment("timeline") view=pages::timeline::Timeline/>
+                    // 分享页需要在 <head> 中输出 OpenGraph 标签，等数据加载完再渲染
+                    <Route
+                        path=(StaticSegment("s"), ParamSegment("id"))
+                        view=pages::share::SharedClipPage
+                        ssr=SsrMode::Async
+                    />
+                    <Route
+                        path=(StaticSegment("embed"), ParamSegment("id"))
+                        view=pages::embed::EmbedPlayer
+                    />
 
                 </Routes>
             </main>
         </Router>
     }
 }
+
+/// 导航栏；嵌入页 (iframe) 中不显示
+#[component]
+fn Navigation() -> impl IntoView {
+    let location = use_location();
+    view! {
+        <Show when=move || !location.pathname.with(|p| p.starts_with("/embed/"))>
+            <pages::Header/>
+        </Show>
+    }
+}
@@ -1,10 +1,134 @@
+use crate::i18n::{use_i18n, Locale, Messages};
+use crate::theme::{use_theme, Palette, Theme, ThemeMode};
 use leptos::prelude::*;
 use leptos_router::components::A;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::HtmlInputElement;
 
+pub mod dialogue;
+pub mod editor_state;
+pub mod embed;
 pub mod homepage;
+pub mod lexicon;
+pub mod mixer;
+pub mod playground;
+pub mod presets;
+pub mod recorder;
+pub mod segments;
+pub mod share;
+pub mod subtitles;
+pub mod timeline;
+pub mod transcript;
+pub mod voicefilter;
+pub mod voices;
+pub mod waveform;
+
+/// 复制文本到剪贴板，返回是否成功 (没有剪贴板权限时失败)
+pub async fn copy_text(text: &str) -> bool {
+    let Some(window) = web_sys::window() else {
+        return false;
+    };
+    JsFuture::from(window.navigator().clipboard().write_text(text))
+        .await
+        .is_ok()
+}
+
+/// 把站内路径补全为带当前域名的完整地址
+pub fn absolute_url(path: &str) -> String {
+    let origin = web_sys::window()
+        .and_then(|w| w.location().origin().ok())
+        .unwrap_or_default();
+    format!("{}{}", origin, path)
+}
+
+/// 补全地址并尽量复制到剪贴板，返回 (完整地址, 是否已复制)
+///
+/// 没有剪贴板权限时仍返回地址，由页面展示出来让用户手动复制
+pub async fn copy_link(path: &str) -> (String, bool) {
+    let link = absolute_url(path);
+    let copied = copy_text(&link).await;
+    (link, copied)
+}
+
+/// 展示生成的分享链接，聚焦时全选方便手动复制
+#[component]
+pub fn SharedLink(link: String, copied: bool) -> impl IntoView {
+    let i18n = use_i18n();
+    view! {
+        <p class="text-xs text-gray-500 mb-1">
+            {move || if copied { i18n.t().link_copied } else { i18n.t().share_link }}
+        </p>
+        <input
+            readonly
+            class="w-full p-1.5 border border-gray-200 rounded-lg text-xs text-gray-600 bg-gray-50"
+            prop:value=link
+            on:focus=move |ev| {
+                if let Some(input) = ev
+                    .target()
+                    .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+                {
+                    input.select();
+                }
+            }
+        />
+    }
+}
+
+/// 主题切换：点击图标循环切换明暗模式，下拉框选择配色
+#[component]
+fn ThemeSwitcher() -> impl IntoView {
+    let i18n = use_i18n();
+    let theme = use_theme();
+    fn mode_label(mode: ThemeMode, t: &Messages) -> &'static str {
+        match mode {
+            ThemeMode::Light => t.theme_light,
+            ThemeMode::Dark => t.theme_dark,
+            ThemeMode::System => t.theme_system,
+        }
+    }
+    fn palette_label(palette: Palette, t: &Messages) -> &'static str {
+        match palette {
+            Palette::Summer => t.palette_summer,
+            Palette::Ocean => t.palette_ocean,
+        }
+    }
+
+    view! {
+        <button
+            class="w-8 h-8 rounded-full text-gray-500 hover:text-primary hover:bg-primary/10 transition-colors"
+            title=move || mode_label(theme.get().mode, i18n.t())
+            on:click=move |_| {
+                let current = theme.get_untracked();
+                theme.set(Theme { mode: current.mode.next(), ..current });
+            }
+        >
+            <i class=move || theme.get().mode.icon()></i>
+        </button>
+        <select
+            class="py-1 px-2 bg-transparent border border-gray-200 rounded-full text-sm text-gray-600 focus:outline-none focus:ring-2 focus:ring-primary/50"
+            title=move || i18n.t().palette
+            on:change=move |ev| {
+                if let Some(palette) = Palette::from_code(&event_target_value(&ev)) {
+                    theme.set(Theme { palette, ..theme.get_untracked() });
+                }
+            }
+        >
+            {Palette::ALL
+                .into_iter()
+                .map(|palette| view! {
+                    <option value=palette.code() selected=move || theme.get().palette == palette>
+                        {move || palette_label(palette, i18n.t())}
+                    </option>
+                })
+                .collect_view()}
+        </select>
+    }
+}
 
 #[component]
 pub fn Header() -> impl IntoView {
+    let i18n = use_i18n();
     view! {
         // 顶部导航栏容器
         // sticky top-0: 吸顶
@@ -19,36 +143,78 @@ pub fn Header() -> impl IntoView {
                     // 旋转动画效果
                     <i class="fa fa-headphones text-primary text-2xl group-hover:rotate-12 transition-transform duration-300"></i>
                     <h1 class="text-xl md:text-2xl font-bold bg-gradient-to-r from-primary to-secondary bg-clip-text text-transparent">
-                        "耳朵"
+                        {move || i18n.t().app_name}
                     </h1>
                 </A>
 
                 // --- 中间：页面跳转 (声音广场) ---
-                <nav class="absolute left-1/2 transform -translate-x-1/2">
+                <nav class="absolute left-1/2 transform -translate-x-1/2 flex items-center space-x-2">
                     <A
                         href="/voice"
                         attr:class="flex items-center space-x-2 px-4 py-2 rounded-full hover:bg-primary/10 transition-colors duration-300 group"
                     >
                         <i class="fa fa-music text-gray-400 group-hover:text-primary transition-colors"></i>
-                        <span class="text-gray-600 font-medium group-hover:text-primary transition-colors">"声音广场"</span>
+                        <span class="text-gray-600 font-medium group-hover:text-primary transition-colors">{move || i18n.t().nav_voices}</span>
+                    </A>
+                    <A
+                        href="/playground"
+                        attr:class="flex items-center space-x-2 px-4 py-2 rounded-full hover:bg-primary/10 transition-colors duration-300 group"
+                    >
+                        <i class="fa fa-columns text-gray-400 group-hover:text-primary transition-colors"></i>
+                        <span class="text-gray-600 font-medium group-hover:text-primary transition-colors">{move || i18n.t().nav_playground}</span>
+                    </A>
+                    <A
+                        href="/voicefilter"
+                        attr:class="flex items-center space-x-2 px-4 py-2 rounded-full hover:bg-primary/10 transition-colors duration-300 group"
+                    >
+                        <i class="fa fa-microphone text-gray-400 group-hover:text-primary transition-colors"></i>
+                        <span class="text-gray-600 font-medium group-hover:text-primary transition-colors">{move || i18n.t().nav_voicefilter}</span>
+                    </A>
+                    <A
+                        href="/timeline"
+                        attr:class="flex items-center space-x-2 px-4 py-2 rounded-full hover:bg-primary/10 transition-colors duration-300 group"
+                    >
+                        <i class="fa fa-film text-gray-400 group-hover:text-primary transition-colors"></i>
+                        <span class="text-gray-600 font-medium group-hover:text-primary transition-colors">{move || i18n.t().nav_timeline}</span>
                     </A>
                 </nav>
 
-                // --- 右侧：头像框 (Todo) ---
-                <div class="relative group">
-                    // 头像容器：带渐变边框
-                    <button class="w-10 h-10 rounded-full p-[2px] bg-gradient-to-tr from-primary to-accent shadow-sm hover:shadow-md transition-all duration-300">
-                        <div class="w-full h-full rounded-full bg-white flex items-center justify-center overflow-hidden">
-                            // 默认用户图标
-                            <i class="fa fa-user text-gray-400 text-lg"></i>
-                        </div>
-                    </button>
+                // --- 右侧：主题、语言切换 + 头像框 (Todo) ---
+                <div class="flex items-center space-x-3">
+                    <ThemeSwitcher/>
+                    <select
+                        class="py-1 px-2 bg-transparent border border-gray-200 rounded-full text-sm text-gray-600 focus:outline-none focus:ring-2 focus:ring-primary/50"
+                        title=move || i18n.t().language
+                        on:change=move |ev| {
+                            if let Some(locale) = Locale::from_tag(&event_target_value(&ev)) {
+                                i18n.set_locale(locale);
+                            }
+                        }
+                    >
+                        {Locale::ALL
+                            .into_iter()
+                            .map(|locale| view! {
+                                <option value=locale.tag() selected=move || i18n.locale() == locale>
+                                    {locale.native_name()}
+                                </option>
+                            })
+                            .collect_view()}
+                    </select>
+                    <div class="relative group">
+                        // 头像容器：带渐变边框
+                        <button class="w-10 h-10 rounded-full p-[2px] bg-gradient-to-tr from-primary to-accent shadow-sm hover:shadow-md transition-all duration-300">
+                            <div class="w-full h-full rounded-full bg-white flex items-center justify-center overflow-hidden">
+                                // 默认用户图标
+                                <i class="fa fa-user text-gray-400 text-lg"></i>
+                            </div>
+                        </button>
 
-                    // Todo Tooltip (Hover 时显示)
-                    <div class="absolute right-0 top-full mt-2 w-32 bg-dark text-xs rounded-lg py-2 px-3 opacity-0 invisible group-hover:opacity-100 group-hover:visible transition-all duration-200 transform translate-y-2 group-hover:translate-y-0 text-center shadow-lg z-50">
-                        "登录功能开发中..."
-                        // 小三角
-                        <div class="absolute -top-1 right-3 w-2 h-2 bg-dark transform rotate-45"></div>
+                        // Todo Tooltip (Hover 时显示)
+                        <div class="absolute right-0 top-full mt-2 w-32 bg-dark text-xs rounded-lg py-2 px-3 opacity-0 invisible group-hover:opacity-100 group-hover:visible transition-all duration-200 transform translate-y-2 group-hover:translate-y-0 text-center shadow-lg z-50">
+                            {move || i18n.t().login_coming_soon}
+                            // 小三角
+                            <div class="absolute -top-1 right-3 w-2 h-2 bg-dark transform rotate-45"></div>
+                        </div>
                     </div>
                 </div>
             </div>
@@ -0,0 +1,457 @@
+use crate::api;
+use crate::clips::clip_url;
+use crate::i18n::use_i18n;
+use crate::text::{parse_script, script_speakers, ScriptLine};
+use leptos::prelude::*;
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, PartialEq)]
+enum LineStatus {
+    Idle,
+    Generating,
+    Done,
+    Failed(String),
+}
+
+/// 脚本中一行台词的合成状态
+#[derive(Clone, Debug, PartialEq)]
+struct LineState {
+    line: ScriptLine,
+    /// 已合成的片段及合成时使用的声线 ID
+    clip: Option<(String, String)>,
+    status: LineStatus,
+}
+
+/// 对白脚本编辑器：每行台词绑定一个说话人，逐句合成后拼接成一条音轨
+#[component]
+pub fn DialogueStudio() -> impl IntoView {
+    let i18n = use_i18n();
+
+    // 状态
+    let script = RwSignal::new(String::new());
+    let voice_map = RwSignal::new(HashMap::<String, String>::new());
+    let gap_ms = RwSignal::new(400_u32);
+    let lines = RwSignal::new(Vec::<LineState>::new());
+    let mixed_clip = RwSignal::new(None::<String>);
+
+    let voices_resource = Resource::new(|| (), |_| api::get_voices());
+    // 声线 ID 和名称都可以直接作为说话人
+    let known_speakers = move || {
+        voices_resource
+            .get()
+            .and_then(Result::ok)
+            .unwrap_or_default()
+            .into_iter()
+            .flat_map(|voice| [voice.id, voice.name])
+            .collect::<Vec<_>>()
+    };
+    let parsed = Memo::new(move |_| parse_script(&script.get(), &known_speakers()));
+    let speakers = Memo::new(move |_| script_speakers(&parsed.get()));
+
+    // 脚本或声线分配变化时重建行状态；同一位置台词和声线都没变的保留已合成的片段
+    Effect::new(move |_| {
+        let parsed = parsed.get();
+        let voices = voice_map.get();
+        let mut changed = false;
+        lines.update(|lines| {
+            let old = std::mem::take(lines);
+            *lines = parsed
+                .into_iter()
+                .enumerate()
+                .map(|(i, line)| {
+                    let voice = voices.get(&line.speaker);
+                    match old.get(i) {
+                        Some(prev)
+                            if prev.line == line && prev.clip.as_ref().map(|(_, v)| v) == voice =>
+                        {
+                            prev.clone()
+                        }
+                        // 台词没变但还没有片段的，保留状态 (如失败原因)
+                        Some(prev) if prev.line == line && prev.clip.is_none() => prev.clone(),
+                        prev => {
+                            changed |= prev.is_some_and(|prev| prev.clip.is_some());
+                            LineState {
+                                line,
+                                clip: None,
+                                status: LineStatus::Idle,
+                            }
+                        }
+                    }
+                })
+                .collect();
+            changed |= lines.len() != old.len();
+        });
+        // 混音结果里有已失效的片段
+        if changed {
+            mixed_clip.set(None);
+        }
+    });
+
+    let set_status = move |index: usize, status: LineStatus, clip: Option<(String, String)>| {
+        lines.update(|lines| {
+            if let Some(state) = lines.get_mut(index) {
+                state.status = status;
+                if clip.is_some() {
+                    state.clip = clip;
+                }
+            }
+        });
+    };
+
+    // 合成单行台词
+    let synthesize_line = move |index: usize, line: ScriptLine| {
+        let voice = voice_map.with_untracked(|m| m.get(&line.speaker).cloned());
+        let needs_voice = i18n.t().dialogue_needs_voice;
+        async move {
+            let Some(voice) = voice else {
+                let message = format!("{}{}", needs_voice, line.speaker);
+                set_status(index, LineStatus::Failed(message.clone()), None);
+                return Err(ServerFnError::ServerError(message));
+            };
+            set_status(index, LineStatus::Generating, None);
+            match api::generate_dialogue_line(voice.clone(), line.text).await {
+                Ok(clip_id) => {
+                    set_status(index, LineStatus::Done, Some((clip_id, voice)));
+                    Ok(())
+                }
+                Err(e) => {
+                    set_status(index, LineStatus::Failed(e.to_string()), None);
+                    Err(e)
+                }
+            }
+        }
+    };
+
+    // 所有行都有片段时拼接成完整音轨
+    let remix = move || {
+        let clip_ids = lines.with_untracked(|lines| {
+            lines
+                .iter()
+                .map(|state| state.clip.as_ref().map(|(id, _)| id.clone()))
+                .collect::<Option<Vec<_>>>()
+        });
+        let gap = gap_ms.get_untracked();
+        async move {
+            let Some(clip_ids) = clip_ids.filter(|ids| !ids.is_empty()) else {
+                return Ok(());
+            };
+            let mixed = api::mix_dialogue(clip_ids, gap).await?;
+            mixed_clip.set(Some(mixed));
+            Ok::<_, ServerFnError>(())
+        }
+    };
+
+    let generate_all = Action::new(move |_: &()| {
+        let snapshot = lines.get_untracked();
+        async move {
+            mixed_clip.set(None);
+            for (index, state) in snapshot.into_iter().enumerate() {
+                synthesize_line(index, state.line).await?;
+            }
+            remix().await
+        }
+    });
+
+    let regenerate_line = Action::new(move |index: &usize| {
+        let index = *index;
+        let line = lines.with_untracked(|lines| lines.get(index).map(|s| s.line.clone()));
+        async move {
+            if let Some(line) = line {
+                synthesize_line(index, line).await?;
+                remix().await?;
+            }
+            Ok::<_, ServerFnError>(())
+        }
+    });
+
+    let remix_action = Action::new(move |_: &()| remix());
+
+    let busy = move || {
+        generate_all.pending().get()
+            || regenerate_line.pending().get()
+            || remix_action.pending().get()
+    };
+    let last_error = move || {
+        [
+            generate_all.value(),
+            regenerate_line.value(),
+            remix_action.value(),
+        ]
+        .into_iter()
+        .find_map(|value| value.get().and_then(Result::err))
+    };
+
+    view! {
+        <div class="grid grid-cols-1 lg:grid-cols-3 gap-8">
+            // --- 左侧栏 (脚本 + 说话人) ---
+            <div class="lg:col-span-1 space-y-8">
+                <ScriptInputCard script=script />
+                <SpeakerVoiceCard speakers=speakers voice_map=voice_map voices_resource=voices_resource />
+            </div>
+
+            // --- 右侧栏 (逐行状态 + 混音结果) ---
+            <div class="lg:col-span-2 space-y-8">
+                <section class="bg-white rounded-xl p-6 shadow-soft transition-all duration-300 hover:shadow-hover">
+                    <h3 class="text-lg font-semibold mb-4 flex items-center">
+                        <i class="fa fa-list-ol text-primary mr-2"></i>
+                        {move || i18n.t().dialogue_lines_title}
+                    </h3>
+
+                    // 句间停顿
+                    <div class="mb-4">
+                        <div class="flex justify-between mb-2">
+                            <label class="font-medium">{move || i18n.t().dialogue_gap}</label>
+                            <span class="text-sm text-primary">{move || format!("{} ms", gap_ms.get())}</span>
+                        </div>
+                        <input
+                            type="range"
+                            min="0"
+                            max="3000"
+                            step="100"
+                            class="w-full h-2 bg-gray-200 rounded-lg accent-primary"
+                            prop:value=move || gap_ms.get().to_string()
+                            on:input=move |ev| {
+                                if let Ok(v) = event_target_value(&ev).parse() {
+                                    gap_ms.set(v);
+                                }
+                            }
+                        />
+                    </div>
+
+                    <div class="space-y-2 mb-6">
+                        <Show
+                            when=move || !lines.with(Vec::is_empty)
+                            fallback=move || view! {
+                                <div class="text-center py-8 text-gray-400 bg-gray-50 rounded-xl border border-dashed border-gray-200 text-sm">
+                                    {move || i18n.t().dialogue_empty}
+                                </div>
+                            }
+                        >
+                            {move || {
+                                lines
+                                    .get()
+                                    .into_iter()
+                                    .enumerate()
+                                    .map(|(index, state)| view! {
+                                        <DialogueLineRow
+                                            state=state
+                                            disabled=Signal::derive(busy)
+                                            on_regenerate=move || { regenerate_line.dispatch(index); }
+                                        />
+                                    })
+                                    .collect_view()
+                            }}
+                        </Show>
+                    </div>
+
+                    <div class="flex flex-wrap gap-3">
+                        <button
+                            class="bg-primary hover:bg-primary-focus text-white py-3 px-6 rounded-lg font-medium transition-all duration-300 flex items-center justify-center flex-1 shadow-md hover:shadow-lg active:scale-[0.98] disabled:opacity-50 disabled:cursor-not-allowed"
+                            on:click=move |_| { generate_all.dispatch(()); }
+                            disabled=move || busy() || lines.with(Vec::is_empty)
+                        >
+                            <i class="fa fa-magic mr-2"></i>
+                            {move || i18n.t().dialogue_generate_all}
+                        </button>
+                        <button
+                            class="bg-white border border-gray-200 hover:border-primary text-gray-600 py-3 px-6 rounded-lg font-medium transition-all duration-300 flex items-center justify-center disabled:opacity-50 disabled:cursor-not-allowed"
+                            on:click=move |_| { remix_action.dispatch(()); }
+                            disabled=busy
+                        >
+                            <i class="fa fa-random mr-2"></i>
+                            {move || i18n.t().dialogue_remix}
+                        </button>
+                    </div>
+
+                    {move || last_error().map(|e| view! {
+                        <p class="text-sm text-red-500 mt-4">{e.to_string()}</p>
+                    })}
+                </section>
+
+                // --- 混音结果 ---
+                {move || mixed_clip.get().map(|id| {
+                    let url = clip_url(&id);
+                    view! {
+                        <section class="border border-green-200 bg-green-50 rounded-xl p-6 animate-slide-up">
+                            <h4 class="font-semibold text-green-800 mb-4 flex items-center">
+                                <i class="fa fa-check text-green-600 mr-2"></i>
+                                {move || i18n.t().dialogue_done}
+                            </h4>
+                            <div class="flex flex-col gap-3">
+                                <audio controls class="w-full" src=url.clone()></audio>
+                                <a
+                                    href=url
+                                    download="dialogue.wav"
+                                    class="bg-white border border-green-200 text-green-700 hover:bg-green-100 px-4 py-2 rounded-lg text-sm flex items-center justify-center transition-colors"
+                                >
+                                    <i class="fa fa-download mr-2"></i>
+                                    {move || i18n.t().dialogue_download}
+                                </a>
+                            </div>
+                        </section>
+                    }
+                })}
+            </div>
+        </div>
+    }
+    .into_any()
+}
+
+#[component]
+fn ScriptInputCard(script: RwSignal<String>) -> impl IntoView {
+    let i18n = use_i18n();
+
+    view! {
+        <section class="bg-white rounded-xl p-6 shadow-soft transition-all duration-300 hover:shadow-hover">
+            <h3 class="text-lg font-semibold mb-4 flex items-center">
+                <i class="fa fa-file-text-o text-primary mr-2"></i>
+                {move || i18n.t().mode_dialogue}
+            </h3>
+            <textarea
+                class="w-full p-4 border border-gray-200 rounded-lg \
+                       focus:outline-none focus:ring-2 focus:ring-primary/50 focus:border-primary \
+                       transition-all duration-300 resize-y h-64 font-sans text-gray-700 placeholder-gray-400"
+                placeholder=move || i18n.t().dialogue_placeholder
+                prop:value=move || script.get()
+                on:input=move |ev| script.set(event_target_value(&ev))
+            ></textarea>
+            <p class="text-xs text-gray-500 mt-2">
+                {move || i18n.t().dialogue_format_hint}
+            </p>
+        </section>
+    }
+    .into_any()
+}
+
+#[component]
+fn SpeakerVoiceCard(
+    speakers: Memo<Vec<String>>,
+    voice_map: RwSignal<HashMap<String, String>>,
+    voices_resource: Resource<Result<Vec<api::VoiceOption>, ServerFnError>>,
+) -> impl IntoView {
+    let i18n = use_i18n();
+
+    // 说话人名称恰好是某个声线 ID 或名称时自动选中
+    Effect::new(move |_| {
+        let Some(Ok(voices)) = voices_resource.get() else {
+            return;
+        };
+        for speaker in speakers.get() {
+            if voice_map.with_untracked(|m| m.contains_key(&speaker)) {
+                continue;
+            }
+            if let Some(voice) = voices.iter().find(|v| v.id == speaker || v.name == speaker) {
+                voice_map.update(|m| {
+                    m.insert(speaker.clone(), voice.id.clone());
+                });
+            }
+        }
+    });
+
+    view! {
+        <section class="bg-white rounded-xl p-6 shadow-soft transition-all duration-300 hover:shadow-hover">
+            <h3 class="text-lg font-semibold mb-4 flex items-center">
+                <i class="fa fa-users text-primary mr-2"></i>
+                {move || i18n.t().dialogue_speakers_title}
+            </h3>
+            <Suspense fallback=move || view! {
+                <div class="flex justify-center items-center py-8 text-gray-400 animate-pulse">
+                    <i class="fa fa-spinner fa-spin mr-2"></i>
+                    {move || i18n.t().voices_loading}
+                </div>
+            }>
+                {move || match voices_resource.get() {
+                    Some(Ok(voices)) => {
+                        view! {
+                            <div class="space-y-3">
+                                {speakers
+                                    .get()
+                                    .into_iter()
+                                    .map(|speaker| {
+                                        let voices = voices.clone();
+                                        let key = speaker.clone();
+                                        let selected = move || {
+                                            voice_map.with(|m| m.get(&key).cloned().unwrap_or_default())
+                                        };
+                                        view! {
+                                            <div class="flex items-center justify-between gap-3">
+                                                <span class="font-medium truncate">{speaker.clone()}</span>
+                                                <select
+                                                    class="p-2 border border-gray-200 rounded-lg text-sm focus:outline-none focus:ring-2 focus:ring-primary/50"
+                                                    prop:value=selected
+                                                    on:change=move |ev| {
+                                                        let voice_id = event_target_value(&ev);
+                                                        voice_map.update(|m| {
+                                                            m.insert(speaker.clone(), voice_id);
+                                                        });
+                                                    }
+                                                >
+                                                    <option value="">{move || i18n.t().select_voice}</option>
+                                                    {voices
+                                                        .into_iter()
+                                                        .map(|v| view! { <option value=v.id>{v.name}</option> })
+                                                        .collect_view()}
+                                                </select>
+                                            </div>
+                                        }
+                                    })
+                                    .collect_view()}
+                            </div>
+                        }
+                        .into_any()
+                    }
+                    Some(Err(_)) => view! {
+                        <div class="text-center py-8 text-gray-400">{move || i18n.t().voices_failed}</div>
+                    }
+                    .into_any(),
+                    None => ().into_any(),
+                }}
+            </Suspense>
+        </section>
+    }
+    .into_any()
+}
+
+#[component]
+fn DialogueLineRow(
+    state: LineState,
+    disabled: Signal<bool>,
+    on_regenerate: impl Fn() + 'static,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let (icon, color) = match &state.status {
+        LineStatus::Idle => ("fa-circle-o", "text-gray-300"),
+        LineStatus::Generating => ("fa-circle-o-notch fa-spin", "text-primary"),
+        LineStatus::Done => ("fa-check-circle", "text-green-500"),
+        LineStatus::Failed(_) => ("fa-exclamation-circle", "text-red-500"),
+    };
+    let error = match &state.status {
+        LineStatus::Failed(message) => Some(message.clone()),
+        _ => None,
+    };
+
+    view! {
+        <div class="p-3 border border-gray-100 rounded-lg flex items-start gap-3">
+            <i class=format!("fa {} {} mt-1", icon, color)></i>
+            <div class="flex-1 min-w-0">
+                <div class="text-sm">
+                    <span class="font-medium text-primary mr-2">{state.line.speaker}</span>
+                    <span class="text-gray-700">{state.line.text}</span>
+                </div>
+                {error.map(|e| view! { <p class="text-xs text-red-500 mt-1">{e}</p> })}
+                {state.clip.map(|(id, _)| view! {
+                    <audio controls class="w-full h-8 mt-2" src=clip_url(&id)></audio>
+                })}
+            </div>
+            <button
+                class="text-gray-400 hover:text-primary transition-colors disabled:opacity-50 disabled:cursor-not-allowed"
+                title=move || i18n.t().dialogue_regenerate_line
+                on:click=move |_| on_regenerate()
+                disabled=move || disabled.get()
+            >
+                <i class="fa fa-refresh"></i>
+            </button>
+        </div>
+    }
+    .into_any()
+}
@@ -0,0 +1,178 @@
+//! 首页编辑器状态的持久化
+//!
+//! 声线、参数和较短的文本同步到地址栏查询参数，方便收藏和分享；
+//! 完整状态 (包括长文本) 存入 localStorage，刷新后恢复。地址栏中的参数优先于本地保存的状态。
+use crate::api::language::SynthesisLanguage;
+use crate::pages::homepage::VoiceParams;
+use leptos::prelude::*;
+use leptos_router::{hooks::use_query_map, params::ParamsMap};
+use serde::{Deserialize, Serialize};
+
+pub const VOICE_QUERY_KEY: &str = "voice";
+pub const SPEED_QUERY_KEY: &str = "speed";
+pub const PITCH_QUERY_KEY: &str = "pitch";
+pub const EMOTION_QUERY_KEY: &str = "emotion";
+pub const LANGUAGE_QUERY_KEY: &str = "lang";
+pub const TEXT_QUERY_KEY: &str = "text";
+
+/// 超过该字符数的文本只保存在 localStorage，不放进地址栏
+#[cfg(target_arch = "wasm32")]
+const MAX_QUERY_TEXT_CHARS: usize = 200;
+
+/// 情感参数的最大字符数，防止地址栏塞入过长的内容
+const MAX_EMOTION_CHARS: usize = 16;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+struct EditorState {
+    text: String,
+    voice_id: String,
+    params: VoiceParams,
+}
+
+impl EditorState {
+    /// 从查询参数读取；一个相关参数都没有时返回 `None`
+    fn from_query(query: &ParamsMap) -> Option<Self> {
+        let keys = [
+            VOICE_QUERY_KEY,
+            SPEED_QUERY_KEY,
+            PITCH_QUERY_KEY,
+            EMOTION_QUERY_KEY,
+            LANGUAGE_QUERY_KEY,
+            TEXT_QUERY_KEY,
+        ];
+        if keys.iter().all(|key| query.get(key).is_none()) {
+            return None;
+        }
+        let mut params = VoiceParams::default();
+        if let Some(speed) = query
+            .get(SPEED_QUERY_KEY)
+            .and_then(|v| v.parse::<f32>().ok())
+        {
+            if speed.is_finite() {
+                params.speed = speed.clamp(0.5, 2.0);
+            }
+        }
+        if let Some(pitch) = query
+            .get(PITCH_QUERY_KEY)
+            .and_then(|v| v.parse::<f32>().ok())
+        {
+            if pitch.is_finite() {
+                params.pitch = pitch.clamp(-12.0, 12.0).round();
+            }
+        }
+        if let Some(emotion) = query.get(EMOTION_QUERY_KEY) {
+            if !emotion.is_empty() && emotion.chars().count() <= MAX_EMOTION_CHARS {
+                params.emotion = emotion;
+            }
+        }
+        if let Some(language) = query
+            .get(LANGUAGE_QUERY_KEY)
+            .and_then(|v| SynthesisLanguage::from_code(&v))
+        {
+            params.language = language;
+        }
+        Some(EditorState {
+            text: query.get(TEXT_QUERY_KEY).unwrap_or_default(),
+            voice_id: query.get(VOICE_QUERY_KEY).unwrap_or_default(),
+            params,
+        })
+    }
+
+    /// 生成查询参数 (不含 `?`)，与默认值相同的参数省略
+    #[cfg(target_arch = "wasm32")]
+    fn to_query(&self) -> String {
+        let defaults = VoiceParams::default();
+        let mut pairs: Vec<(&str, String)> = Vec::new();
+        if !self.voice_id.is_empty() {
+            pairs.push((VOICE_QUERY_KEY, self.voice_id.clone()));
+        }
+        if self.params.speed != defaults.speed {
+            pairs.push((SPEED_QUERY_KEY, self.params.speed.to_string()));
+        }
+        if self.params.pitch != defaults.pitch {
+            pairs.push((PITCH_QUERY_KEY, self.params.pitch.to_string()));
+        }
+        if self.params.emotion != defaults.emotion {
+            pairs.push((EMOTION_QUERY_KEY, self.params.emotion.clone()));
+        }
+        if self.params.language != defaults.language {
+            pairs.push((LANGUAGE_QUERY_KEY, self.params.language.code().to_string()));
+        }
+        if !self.text.is_empty() && self.text.chars().count() <= MAX_QUERY_TEXT_CHARS {
+            pairs.push((TEXT_QUERY_KEY, self.text.clone()));
+        }
+        pairs
+            .into_iter()
+            .map(|(key, value)| format!("{}={}", key, js_sys::encode_uri_component(&value)))
+            .collect::<Vec<_>>()
+            .join("&")
+    }
+
+    fn apply(self, text: RwSignal<String>, voice: RwSignal<String>, params: RwSignal<VoiceParams>) {
+        text.set(self.text);
+        voice.set(self.voice_id);
+        params.set(self.params);
+    }
+}
+
+/// 恢复并持续保存编辑器状态
+///
+/// 地址栏参数在创建时立即应用 (SSR 也能渲染出同样的内容)；localStorage 只在浏览器端读取，
+/// 仅当地址栏没有相关参数时才恢复。之后每次修改都同步写回两处。
+pub fn use_persisted_editor(
+    text: RwSignal<String>,
+    voice: RwSignal<String>,
+    params: RwSignal<VoiceParams>,
+) {
+    let from_query = use_query_map().with_untracked(EditorState::from_query);
+    if let Some(state) = from_query.clone() {
+        state.apply(text, voice, params);
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        const STORAGE_KEY: &str = "eardo.editor";
+        let restored = from_query.is_some();
+
+        let storage = || web_sys::window().and_then(|w| w.local_storage().ok().flatten());
+
+        Effect::new(move |previous: Option<()>| {
+            let state = EditorState {
+                text: text.get(),
+                voice_id: voice.get(),
+                params: params.get(),
+            };
+            if previous.is_none() && !restored {
+                let saved = storage()
+                    .and_then(|s| s.get_item(STORAGE_KEY).ok().flatten())
+                    .and_then(|json| serde_json::from_str::<EditorState>(&json).ok());
+                if let Some(saved) = saved.filter(|saved| *saved != state) {
+                    // 修改信号会再次触发本 Effect，届时再写回
+                    saved.apply(text, voice, params);
+                    return;
+                }
+            }
+
+            if let (Some(storage), Ok(json)) = (storage(), serde_json::to_string(&state)) {
+                let _ = storage.set_item(STORAGE_KEY, &json);
+            }
+            // 只替换当前历史记录，不产生新的后退项，也不触发路由
+            if let Some(window) = web_sys::window() {
+                let path = window.location().pathname().unwrap_or_default();
+                let query = state.to_query();
+                let url = if query.is_empty() {
+                    path
+                } else {
+                    format!("{}?{}", path, query)
+                };
+                if let Ok(history) = window.history() {
+                    let _ = history.replace_state_with_url(
+                        &wasm_bindgen::JsValue::NULL,
+                        "",
+                        Some(&url),
+                    );
+                }
+            }
+        });
+    }
+}
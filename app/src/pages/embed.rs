@@ -0,0 +1,143 @@
+use crate::api::share::{get_shared_clip, share_url, SharedClipLookup, SharedClipView};
+use crate::clips::clip_url;
+use crate::pages::waveform::Waveform;
+use leptos::prelude::*;
+use leptos_meta::Title;
+use leptos_router::hooks::{use_params_map, use_query_map};
+
+/// 嵌入播放器的配色，由查询参数 `theme=light|dark` 指定
+#[derive(Clone, Copy, PartialEq)]
+enum EmbedTheme {
+    Light,
+    Dark,
+}
+
+impl EmbedTheme {
+    fn from_query(value: Option<String>) -> Self {
+        match value.as_deref() {
+            Some("dark") => EmbedTheme::Dark,
+            _ => EmbedTheme::Light,
+        }
+    }
+
+    /// (容器, 次要文字) 的样式
+    fn classes(self) -> (&'static str, &'static str) {
+        match self {
+            EmbedTheme::Light => ("bg-white text-dark border-gray-200", "text-gray-400"),
+            EmbedTheme::Dark => ("bg-gray-900 text-gray-100 border-gray-700", "text-gray-500"),
+        }
+    }
+}
+
+/// 嵌入播放器 `/embed/{id}`：用于 `<iframe>`，没有导航栏
+#[component]
+pub fn EmbedPlayer() -> impl IntoView {
+    let params = use_params_map();
+    let id = move || params.with(|p| p.get("id").unwrap_or_default());
+    let theme = EmbedTheme::from_query(use_query_map().with_untracked(|q| q.get("theme")));
+    let (container, muted) = theme.classes();
+    let shared = Resource::new(id, |id| get_shared_clip(id, None));
+
+    view! {
+        <div class=format!("h-screen w-full border rounded-xl overflow-hidden flex items-center px-4 {}", container)>
+            <Suspense fallback=move || view! {
+                <span class=format!("text-sm animate-pulse {}", muted)>"加载中..."</span>
+            }>
+                {move || shared.get().map(|result| match result {
+                    Ok(SharedClipLookup::Ready(clip)) => view! {
+                        <Title text=format!("{} 的语音 - 耳朵", clip.voice_name)/>
+                        <EmbedControls clip=clip id=id() muted=muted />
+                    }.into_any(),
+                    Ok(SharedClipLookup::Locked { .. }) => view! {
+                        <a
+                            href=share_url(&id())
+                            target="_blank"
+                            rel="noopener"
+                            class="text-sm text-primary hover:underline"
+                        >
+                            <i class="fa fa-lock mr-2"></i>
+                            "这段音频设置了访问密码，点击前往收听"
+                        </a>
+                    }.into_any(),
+                    Err(e) => view! {
+                        <span class=format!("text-sm {}", muted)>{e.to_string()}</span>
+                    }.into_any(),
+                })}
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+fn EmbedControls(clip: SharedClipView, id: String, muted: &'static str) -> impl IntoView {
+    let audio_ref = NodeRef::<leptos::html::Audio>::new();
+    let position = RwSignal::new(0.0_f32);
+    let playing = RwSignal::new(false);
+    let duration = clip.duration;
+    let seek = Callback::new(move |secs: f32| {
+        position.set(secs);
+        if let Some(audio) = audio_ref.get_untracked() {
+            audio.set_current_time(secs as f64);
+        }
+    });
+    let toggle = move |_| {
+        let Some(audio) = audio_ref.get_untracked() else {
+            return;
+        };
+        if playing.get_untracked() {
+            let _ = audio.pause();
+        } else {
+            let _ = audio.play();
+        }
+    };
+
+    view! {
+        <audio
+            node_ref=audio_ref
+            src=clip_url(&clip.clip_id)
+            preload="metadata"
+            class="hidden"
+            on:play=move |_| playing.set(true)
+            on:pause=move |_| playing.set(false)
+            on:ended=move |_| playing.set(false)
+            on:timeupdate=move |_| {
+                if let Some(audio) = audio_ref.get_untracked() {
+                    position.set(audio.current_time() as f32);
+                }
+            }
+        ></audio>
+        <button
+            class="shrink-0 w-12 h-12 rounded-full bg-primary text-white hover:bg-primary-focus transition-colors flex items-center justify-center mr-4"
+            title=move || if playing.get() { "暂停" } else { "播放" }
+            on:click=toggle
+        >
+            <i class=move || if playing.get() { "fa fa-pause" } else { "fa fa-play ml-0.5" }></i>
+        </button>
+        <div class="flex-1 min-w-0">
+            <div class="flex items-center justify-between text-xs mb-1">
+                <span class="font-medium truncate">{clip.voice_name.clone()}</span>
+                <span class=muted>
+                    {move || format!("{:.1}s / {:.1}s", position.get().min(duration), duration)}
+                </span>
+            </div>
+            {(!clip.peaks.is_empty()).then(|| view! {
+                <Waveform
+                    peaks=clip.peaks
+                    duration=duration
+                    scale=Signal::stored(duration)
+                    position=position
+                    on_seek=seek
+                />
+            })}
+            <a
+                href=share_url(&id)
+                target="_blank"
+                rel="noopener"
+                class=format!("text-[10px] hover:text-primary transition-colors {}", muted)
+            >
+                <i class="fa fa-headphones mr-1"></i>
+                "在耳朵中打开"
+            </a>
+        </div>
+    }
+}
@@ -1,13 +1,33 @@
-use crate::api;
-use leptos::logging::{debug_log, debug_warn};
+use crate::api::cloning::list_custom_voices;
+use crate::api::language::SynthesisLanguage;
+use crate::api::{self, GeneratedAudio, VoiceOption};
+use crate::audio::post::{PostProcessOptions, MAX_TARGET_LUFS, MIN_TARGET_LUFS};
+use crate::i18n::{use_i18n, Label, Messages};
+use crate::jobs::{self, ChunkStatus, JobProgress};
+use crate::pages::dialogue::DialogueStudio;
+use crate::pages::editor_state;
+use crate::pages::lexicon::LexiconCard;
+use crate::pages::mixer::BackgroundMusicPanel;
+use crate::pages::presets::VoicePresetPanel;
+use crate::pages::segments::LanguageSegmentsCard;
+use crate::pages::share::SharePanel;
+use crate::pages::subtitles::SubtitleDownloads;
+use crate::pages::waveform::WaveformPlayer;
+use crate::text::detect::LanguageSpan;
+use crate::text::normalize::{NormalizeLanguage, NormalizeOptions};
+use leptos::logging::debug_log;
 use leptos::prelude::*;
+use leptos_router::components::A;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone)]
-struct VoiceParams {
-    pitch: f32,
-    speed: f32,
-    emotion: String,
+/// 声线参数：音高 (半音)、语速 (倍率)、情感与合成语言
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct VoiceParams {
+    pub pitch: f32,
+    pub speed: f32,
+    pub emotion: String,
+    #[serde(default)]
+    pub language: SynthesisLanguage,
 }
 
 impl Default for VoiceParams {
@@ -16,6 +36,7 @@ impl Default for VoiceParams {
             pitch: 0.0,
             speed: 1.0,
             emotion: "happy".to_string(),
+            language: SynthesisLanguage::default(),
         }
     }
 }
@@ -27,27 +48,81 @@ pub struct GenerateParams {
     pub pitch: f32,
     pub speed: f32,
     pub emotion: String,
+    /// 输入文本的语言或方言
+    #[serde(default)]
+    pub language: SynthesisLanguage,
+    /// 自动识别语言时用户修正过的分段，按分段文本匹配
+    #[serde(default)]
+    pub language_overrides: Vec<LanguageSpan>,
+    /// 进度推送所用的任务 ID，由 `api::create_job` 创建
+    pub job_id: Option<String>,
+    /// 合成前的文本规范化选项
+    pub normalize: NormalizeOptions,
+    /// 合成后的后期处理选项
+    pub post: PostProcessOptions,
+}
+
+impl GenerateParams {
+    pub fn new(text: String, voice_id: String, params: VoiceParams) -> Self {
+        GenerateParams {
+            text,
+            voice_id,
+            pitch: params.pitch,
+            speed: params.speed,
+            emotion: params.emotion,
+            language: params.language,
+            language_overrides: Vec::new(),
+            job_id: None,
+            normalize: NormalizeOptions::default(),
+            post: PostProcessOptions::default(),
+        }
+    }
 }
 
 #[component]
 pub fn HomePage() -> impl IntoView {
+    let i18n = use_i18n();
     // 状态
     let text_signal = RwSignal::new(String::new());
     let voice_signal = RwSignal::new(String::new());
     let param_signal = RwSignal::new(VoiceParams::default());
+    let normalize_signal = RwSignal::new(NormalizeOptions::default());
+    let post_signal = RwSignal::new(PostProcessOptions::default());
+    let job_signal = RwSignal::new(None::<String>);
+    // 自动识别语言时检测出的分段 (含用户修正)
+    let spans_signal = RwSignal::new(Vec::<LanguageSpan>::new());
+    // 最近一次提交合成的 (文本, 声线 ID)，分享时使用
+    let submitted = RwSignal::new((String::new(), String::new()));
+    // 对白模式：多说话人脚本
+    let dialogue_mode = RwSignal::new(false);
+    let progress = jobs::use_job_progress(job_signal);
+    // 刷新或通过链接打开时恢复编辑器状态
+    editor_state::use_persisted_editor(text_signal, voice_signal, param_signal);
 
     // 创建 Action 处理生成请求
     // Action 自动管理 pending (加载中) 和 value (返回值) 状态
     let generate_action = Action::new(move |_| {
-        let voice_params = GenerateParams {
+        let mut voice_params = GenerateParams {
             text: text_signal.get(),
             voice_id: voice_signal.get(),
             pitch: param_signal.get().pitch,
             speed: param_signal.get().speed,
             emotion: param_signal.get().emotion.clone(),
+            language: param_signal.get().language,
+            language_overrides: spans_signal.get(),
+            job_id: None,
+            normalize: normalize_signal.get(),
+            post: post_signal.get(),
         };
         debug_log!("使用参数生成音频: {:?}", voice_params);
-        return api::generate_audio(voice_params);
+        submitted.set((voice_params.text.clone(), voice_params.voice_id.clone()));
+        async move {
+            // 先创建任务并订阅进度，再开始合成
+            let job_id = api::create_job().await?;
+            job_signal.set(Some(job_id.clone()));
+            voice_params.job_id = Some(job_id);
+            api::generate_audio(voice_params).await
+        }
     });
 
     view! {
@@ -56,29 +131,61 @@ pub fn HomePage() -> impl IntoView {
 
                 <section class="text-center mb-12">
                     <h2 class="text-[clamp(1.8rem,4vw,2.5rem)] font-bold mb-4 text-shadow text-dark">
-                        "声音，也能如此多彩"
+                        {move || i18n.t().hero_title}
                     </h2>
                     <p class="text-gray-600 max-w-2xl mx-auto">
-                        "输入文本，选择喜欢的声线，调整参数，体验声音的奇妙变化"
+                        {move || i18n.t().hero_subtitle}
                     </p>
                 </section>
 
-                <div class="grid grid-cols-1 lg:grid-cols-3 gap-8">
-
-                    // --- 左侧栏 (输入 + 声线) ---
-                    <div class="lg:col-span-1 space-y-8">
-                        <TextInputCard text=text_signal />
-                        <VoiceSelectorCard selected_voice=voice_signal />
+                // --- 模式切换 ---
+                <div class="flex justify-center mb-8">
+                    <div class="inline-flex bg-white rounded-full p-1 shadow-soft">
+                        <button
+                            class="px-5 py-2 rounded-full text-sm font-medium transition-colors duration-300"
+                            class=("bg-primary", move || !dialogue_mode.get())
+                            class=("text-white", move || !dialogue_mode.get())
+                            class=("text-gray-500", move || dialogue_mode.get())
+                            on:click=move |_| dialogue_mode.set(false)
+                        >
+                            <i class="fa fa-user mr-2"></i>
+                            {move || i18n.t().mode_single}
+                        </button>
+                        <button
+                            class="px-5 py-2 rounded-full text-sm font-medium transition-colors duration-300"
+                            class=("bg-primary", move || dialogue_mode.get())
+                            class=("text-white", move || dialogue_mode.get())
+                            class=("text-gray-500", move || !dialogue_mode.get())
+                            on:click=move |_| dialogue_mode.set(true)
+                        >
+                            <i class="fa fa-comments mr-2"></i>
+                            {move || i18n.t().mode_dialogue}
+                        </button>
                     </div>
+                </div>
+
+                <Show when=move || !dialogue_mode.get() fallback=DialogueStudio>
+                    <div class="grid grid-cols-1 lg:grid-cols-3 gap-8">
+
+                        // --- 左侧栏 (输入 + 声线) ---
+                        <div class="lg:col-span-1 space-y-8">
+                            <TextInputCard text=text_signal normalize=normalize_signal />
+                            <VoiceSelectorCard selected_voice=voice_signal selected_param=param_signal />
+                            <Show when=move || param_signal.with(|p| p.language == SynthesisLanguage::Auto)>
+                                <LanguageSegmentsCard text=text_signal normalize=normalize_signal spans=spans_signal />
+                            </Show>
+                            <LexiconCard text=text_signal normalize=normalize_signal />
+                        </div>
 
-                    // --- 右侧栏 (参数 + 结果) ---
-                    <div class="lg:col-span-2 space-y-8">
-                        // 1. 参数调节 (占位符)
-                        <ParameterControlCard selected_param=param_signal />
-                        // 2. 输出结果 (核心功能)
-                        <AudioResultCard generate_action=generate_action />
+                        // --- 右侧栏 (参数 + 结果) ---
+                        <div class="lg:col-span-2 space-y-8">
+                            // 1. 参数调节与预设
+                            <ParameterControlCard selected_voice=voice_signal selected_param=param_signal />
+                            // 2. 输出结果 (核心功能)
+                            <AudioResultCard generate_action=generate_action progress=progress post=post_signal submitted=submitted />
+                        </div>
                     </div>
-                </div>
+                </Show>
             </div>
         </div>
     }
@@ -88,14 +195,17 @@ pub fn HomePage() -> impl IntoView {
 pub fn TextInputCard(
     /// 用于存储输入文本的信号，由父组件传入
     text: RwSignal<String>,
+    /// 文本规范化选项
+    normalize: RwSignal<NormalizeOptions>,
 ) -> impl IntoView {
+    let i18n = use_i18n();
     view! {
         // 卡片容器：白色背景、圆角、阴影
         <section class="bg-white rounded-xl p-6 shadow-soft transition-all duration-300 hover:shadow-hover">
             // 标题区域
             <h3 class="text-lg font-semibold mb-4 flex items-center">
                 <i class="fa fa-comment text-primary mr-2"></i>
-                "文本输入"
+                {move || i18n.t().text_input_title}
             </h3>
             // 输入区域
             <textarea
@@ -104,7 +214,7 @@ pub fn TextInputCard(
                 class="w-full p-4 border border-gray-200 rounded-lg \
                        focus:outline-none focus:ring-2 focus:ring-primary/50 focus:border-primary \
                        transition-all duration-300 resize-none h-32 font-sans text-gray-700 placeholder-gray-400"
-                placeholder="请输入你想转换的文字...\n例如：你好，欢迎使用白昼聆夏"
+                placeholder=move || i18n.t().text_placeholder
                 // --- 核心逻辑：绑定信号 ---
                 // 1. 当信号改变时，更新 textarea 的值
                 prop:value=move || text.get()
@@ -113,32 +223,160 @@ pub fn TextInputCard(
             ></textarea>
             // 底部提示
             <p class="text-xs text-gray-500 mt-2">
-                "输入文本将通过后端 TTS 转换为音频"
+                {move || i18n.t().text_hint}
             </p>
+            <p class="text-xs text-gray-400 mt-1">
+                {move || i18n.t().ssml_hint}
+                <code class="text-primary">"<break time=\"500ms\"/>"</code>
+            </p>
+            <NormalizeOptionsPanel options=normalize />
         </section>
     }
+    .into_any()
+}
+
+/// 文本规范化开关：总开关、语言以及各类规则
+#[component]
+fn NormalizeOptionsPanel(options: RwSignal<NormalizeOptions>) -> impl IntoView {
+    let i18n = use_i18n();
+    // (名称, 读取字段, 写入字段)
+    type Field = (
+        fn(&Messages) -> &'static str,
+        fn(&NormalizeOptions) -> bool,
+        fn(&mut NormalizeOptions, bool),
+    );
+    let fields: [Field; 7] = [
+        (|m| m.rule_dates, |o| o.dates, |o, v| o.dates = v),
+        (|m| m.rule_times, |o| o.times, |o, v| o.times = v),
+        (|m| m.rule_currency, |o| o.currency, |o, v| o.currency = v),
+        (
+            |m| m.rule_percentages,
+            |o| o.percentages,
+            |o, v| o.percentages = v,
+        ),
+        (
+            |m| m.rule_phone_numbers,
+            |o| o.phone_numbers,
+            |o, v| o.phone_numbers = v,
+        ),
+        (|m| m.rule_urls, |o| o.urls, |o, v| o.urls = v),
+        (|m| m.rule_numbers, |o| o.numbers, |o, v| o.numbers = v),
+    ];
+
+    view! {
+        <div class="mt-4 pt-4 border-t border-gray-100 text-sm">
+            <div class="flex items-center justify-between">
+                <label class="flex items-center cursor-pointer">
+                    <input
+                        type="checkbox"
+                        class="mr-2 accent-primary"
+                        prop:checked=move || options.with(|o| o.enabled)
+                        on:change=move |ev| options.update(|o| o.enabled = event_target_checked(&ev))
+                    />
+                    {move || i18n.t().normalize_title}
+                </label>
+                <select
+                    class="p-1 border border-gray-200 rounded-lg text-xs focus:outline-none focus:ring-2 focus:ring-primary/50 disabled:opacity-50"
+                    disabled=move || options.with(|o| !o.enabled)
+                    on:change=move |ev| {
+                        let language = match event_target_value(&ev).as_str() {
+                            "zh" => NormalizeLanguage::Chinese,
+                            "en" => NormalizeLanguage::English,
+                            _ => NormalizeLanguage::Auto,
+                        };
+                        options.update(|o| o.language = language);
+                    }
+                >
+                    <option value="auto">{move || i18n.t().normalize_auto}</option>
+                    <option value="zh">{move || i18n.t().normalize_chinese}</option>
+                    <option value="en">{move || i18n.t().normalize_english}</option>
+                </select>
+            </div>
+            <Show when=move || options.with(|o| o.enabled)>
+                <div class="flex flex-wrap gap-2 mt-3">
+                    {fields
+                        .into_iter()
+                        .map(|(label, get, set)| view! {
+                            <button
+                                class="px-2 py-1 rounded-full text-xs border transition-colors duration-200"
+                                class=("border-primary", move || options.with(get))
+                                class=("text-primary", move || options.with(get))
+                                class=("border-gray-200", move || !options.with(get))
+                                class=("text-gray-400", move || !options.with(get))
+                                on:click=move |_| options.update(|o| set(o, !get(o)))
+                            >
+                                {move || label(i18n.t())}
+                            </button>
+                        })
+                        .collect_view()}
+                </div>
+                <p class="text-xs text-gray-400 mt-2">
+                    {move || i18n.t().normalize_example}
+                </p>
+            </Show>
+        </div>
+    }
+    .into_any()
 }
 
 #[component]
 pub fn VoiceSelectorCard(
     /// 当前选中的声线 ID (双向绑定)
     selected_voice: RwSignal<String>,
+    /// 声线参数，其中的合成语言用于筛选声线
+    selected_param: RwSignal<VoiceParams>,
 ) -> impl IntoView {
+    let i18n = use_i18n();
     // Resource 用于异步获取数据
     let voices_resource = Resource::new(|| (), |_| api::get_voices());
+    let custom_voices = Resource::new(|| (), |_| list_custom_voices());
+    let language = move || selected_param.with(|p| p.language);
+    // 切换语言后，当前声线不支持时取消选择；复刻声线只支持普通语言
+    let set_language = move |language: SynthesisLanguage| {
+        selected_param.update(|p| p.language = language);
+        let voice_id = selected_voice.get_untracked();
+        let supported = voices_resource
+            .get_untracked()
+            .and_then(Result::ok)
+            .and_then(|voices| voices.into_iter().find(|v| v.id == voice_id))
+            .map_or(!language.is_dialect(), |v| v.supports(language));
+        if !supported {
+            selected_voice.set(String::new());
+        }
+    };
 
     view! {
         <section class="bg-white rounded-xl p-6 shadow-soft transition-all duration-300 hover:shadow-hover">
-            <h3 class="text-lg font-semibold mb-4 flex items-center">
-                <i class="fa fa-user-circle text-primary mr-2"></i>
-                "声线选择"
-            </h3>
+            <div class="flex justify-between items-center mb-4">
+                <h3 class="text-lg font-semibold flex items-center">
+                    <i class="fa fa-user-circle text-primary mr-2"></i>
+                    {move || i18n.t().voice_title}
+                </h3>
+                <select
+                    class="p-1 border border-gray-200 rounded-lg text-xs focus:outline-none focus:ring-2 focus:ring-primary/50"
+                    title=move || i18n.t().synthesis_language
+                    on:change=move |ev| {
+                        if let Some(language) = SynthesisLanguage::from_code(&event_target_value(&ev)) {
+                            set_language(language);
+                        }
+                    }
+                >
+                    {SynthesisLanguage::ALL
+                        .into_iter()
+                        .map(|option| view! {
+                            <option value=option.code() selected=move || language() == option>
+                                {move || option.native_name().unwrap_or(i18n.t().language_auto)}
+                            </option>
+                        })
+                        .collect_view()}
+                </select>
+            </div>
 
             <div id="voice-selector" class="grid grid-cols-1 gap-3">
                 <Suspense fallback=move || view! {
                     <div class="flex justify-center items-center py-8 text-gray-400 animate-pulse">
                         <i class="fa fa-spinner fa-spin mr-2"></i>
-                        "加载声线库..."
+                        {move || i18n.t().voices_loading}
                     </div>
                 }>
                     {move || {
@@ -146,7 +384,7 @@ pub fn VoiceSelectorCard(
                             None => view! {
                                 <div class="flex justify-center items-center py-8 text-gray-400 animate-pulse">
                                     <i class="fa fa-spinner fa-spin mr-2"></i>
-                                    "加载声线库..."
+                                    {move || i18n.t().voices_loading}
                                 </div>
                             }.into_any(),
                             Some(Err(e)) =>
@@ -154,53 +392,19 @@ pub fn VoiceSelectorCard(
                                 <div class="flex justify-center items-center py-8 text-gray-400 animate-pulse">
                                     <i class="fa fa-spinner fa-spin mr-2"></i>
                                     {move || debug_log!("加载声线库失败: {}", e)}
-                                    "加载声线库失败"
+                                    {move || i18n.t().voices_failed}
                                 </div>
                             }.into_any(),
                             Some(Ok(voices)) => view! {
                             <div class="grid grid-cols-1 gap-3">
                                 <For
-                                    each=move || voices.clone()
+                                    each=move || {
+                                        let language = language();
+                                        voices.iter().filter(|v| v.supports(language)).cloned().collect::<Vec<_>>()
+                                    }
                                     key=|voice| voice.id.clone()
-                                    children=move |voice| {
-                                        let voice_id = voice.id.clone();
-                                        // is_active 是一个闭包：Fn() -> bool
-                                        let is_active = move || selected_voice.get() == voice_id;
-
-                                        view! {
-                                            <div
-                                                class="voice-option p-4 border rounded-lg cursor-pointer transition-all duration-200 flex justify-between items-center group"
-                                                // 1. 选中状态: 边框变黄
-                                                //class:border-primary=is_active
-                                                // 2. 选中状态: 背景变淡黄 (使用 opacity 语法，因为 primary 是单色)
-                                                //class:bg-primary\/10=is_active
-
-                                                // --- 修复点在这里 ---
-                                                // 错误写法: !is_active
-                                                // 正确写法: move || !is_active()
-                                                //class:border-gray-200=move || !is_active()
-
-                                                class:hover:border-primary=true
-                                                on:click=move |_| selected_voice.set(voice.id.clone())
-                                            >
-                                                <div>
-                                                    <div class="font-medium group-hover:text-primary transition-colors">
-                                                        {voice.name}
-                                                    </div>
-                                                    <div class="text-sm text-gray-500">
-                                                        {voice.desc}
-                                                    </div>
-                                                </div>
-
-                                                // 选中时的图标
-                                                <div class="text-primary transition-opacity duration-200"
-                                                     // 这里也是同样的逻辑：需要调用闭包并取反
-                                                     class:hidden=move || !is_active()
-                                                >
-                                                    <i class="fa fa-check-circle text-xl"></i>
-                                                </div>
-                                            </div>
-                                        }
+                                    children=move |voice| view! {
+                                        <VoiceOptionRow voice=voice selected_voice=selected_voice />
                                     }
                                 />
                             </div>
@@ -211,55 +415,191 @@ pub fn VoiceSelectorCard(
                     }
                 </Suspense>
             </div>
+
+            // --- 我的声音 (声音复刻) ---
+            <div class="mt-6 pt-4 border-t border-gray-100">
+                <div class="flex justify-between items-center mb-3">
+                    <h4 class="text-sm font-medium text-gray-600">{move || i18n.t().my_voices}</h4>
+                    <A href="/my-voices" attr:class="text-xs text-primary hover:underline">
+                        <i class="fa fa-plus mr-1"></i>
+                        {move || i18n.t().clone_voice}
+                    </A>
+                </div>
+                <Suspense fallback=|| ()>
+                    {move || custom_voices.get().map(|result| match result {
+                        Ok(voices) if !voices.is_empty() && language().is_dialect() => view! {
+                            <p class="text-xs text-gray-400">{move || i18n.t().dialect_needs_voice}</p>
+                        }.into_any(),
+                        Ok(voices) if !voices.is_empty() => view! {
+                            <div class="grid grid-cols-1 gap-3">
+                                {voices.into_iter().map(|voice| view! {
+                                    <VoiceOptionRow
+                                        voice=VoiceOption {
+                                            id: voice.id,
+                                            name: voice.name,
+                                            desc: i18n.t().cloned_voice_desc.to_string(),
+                                            languages: Vec::new(),
+                                        }
+                                        selected_voice=selected_voice
+                                    />
+                                }).collect_view()}
+                            </div>
+                        }.into_any(),
+                        Ok(_) => view! {
+                            <p class="text-xs text-gray-400">{move || i18n.t().no_cloned_voices}</p>
+                        }.into_any(),
+                        Err(e) => view! {
+                            <p class="text-xs text-red-500">{format!("{}: {}", i18n.t().cloned_voices_failed, e)}</p>
+                        }.into_any(),
+                    })}
+                </Suspense>
+            </div>
         </section>
     }
+    .into_any()
 }
 
 #[component]
-fn ParameterControlCard(selected_param: RwSignal<VoiceParams>) -> impl IntoView {
-    let _ = selected_param;
+fn VoiceOptionRow(voice: VoiceOption, selected_voice: RwSignal<String>) -> impl IntoView {
+    let voice_id = voice.id.clone();
+    // is_active 是一个闭包：Fn() -> bool
+    let is_active = move || selected_voice.get() == voice_id;
+
     view! {
-        <section class="bg-white rounded-xl p-6 shadow-soft transition-all duration-300 hover:shadow-hover relative overflow-hidden group">
+        <div
+            class="voice-option p-4 border rounded-lg cursor-pointer transition-all duration-200 flex justify-between items-center group"
+            // 1. 选中状态: 边框变黄
+            //class:border-primary=is_active
+            // 2. 选中状态: 背景变淡黄 (使用 opacity 语法，因为 primary 是单色)
+            //class:bg-primary\/10=is_active
+
+            // --- 修复点在这里 ---
+            // 错误写法: !is_active
+            // 正确写法: move || !is_active()
+            //class:border-gray-200=move || !is_active()
+
+            class:hover:border-primary=true
+            on:click=move |_| selected_voice.set(voice.id.clone())
+        >
+            <div>
+                <div class="font-medium group-hover:text-primary transition-colors">
+                    {voice.name}
+                </div>
+                <div class="text-sm text-gray-500">
+                    {voice.desc}
+                </div>
+            </div>
+
+            // 选中时的图标
+            <div class="text-primary transition-opacity duration-200"
+                 // 这里也是同样的逻辑：需要调用闭包并取反
+                 class:hidden=move || !is_active()
+            >
+                <i class="fa fa-check-circle text-xl"></i>
+            </div>
+        </div>
+    }
+}
+
+/// 可选的情感 (值, 显示名)
+const EMOTIONS: [(&str, Label); 5] = [
+    ("neutral", |m| m.emotion_neutral),
+    ("happy", |m| m.emotion_happy),
+    ("sad", |m| m.emotion_sad),
+    ("angry", |m| m.emotion_angry),
+    ("surprised", |m| m.emotion_surprised),
+];
+
+#[component]
+fn ParameterControlCard(
+    /// 当前选中的声线 ID，套用预设时一起修改
+    selected_voice: RwSignal<String>,
+    selected_param: RwSignal<VoiceParams>,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    view! {
+        <section class="bg-white rounded-xl p-6 shadow-soft transition-all duration-300 hover:shadow-hover">
             // 标题
-            <h3 class="text-lg font-semibold mb-6 flex items-center text-gray-400">
-                <i class="fa fa-sliders mr-2"></i>
-                "参数调节"
+            <h3 class="text-lg font-semibold mb-6 flex items-center">
+                <i class="fa fa-sliders text-primary mr-2"></i>
+                {move || i18n.t().params_title}
             </h3>
 
-            // 模拟的内容（模糊处理）
-            <div class="space-y-6 opacity-30 pointer-events-none select-none filter blur-[1px]">
+            <VoicePresetPanel voice=selected_voice params=selected_param />
+
+            <div class="grid grid-cols-1 md:grid-cols-3 gap-6">
                 <div>
                     <div class="flex justify-between mb-2">
-                        <label class="font-medium">"音高 (Pitch)"</label>
-                        <span class="text-sm text-primary">"0"</span>
+                        <label class="font-medium">{move || i18n.t().pitch}</label>
+                        <span class="text-sm text-primary">{move || format!("{:+.0} {}", selected_param.with(|p| p.pitch), i18n.t().semitones)}</span>
                     </div>
-                    <input type="range" class="w-full h-2 bg-gray-200 rounded-lg" />
+                    <input
+                        type="range"
+                        min="-12"
+                        max="12"
+                        step="1"
+                        class="w-full h-2 bg-gray-200 rounded-lg accent-primary"
+                        prop:value=move || selected_param.with(|p| p.pitch).to_string()
+                        on:input=move |ev| {
+                            if let Ok(v) = event_target_value(&ev).parse() {
+                                selected_param.update(|p| p.pitch = v);
+                            }
+                        }
+                    />
                 </div>
                 <div>
                     <div class="flex justify-between mb-2">
-                        <label class="font-medium">"语速 (Speed)"</label>
-                        <span class="text-sm text-primary">"1.0x"</span>
+                        <label class="font-medium">{move || i18n.t().speed}</label>
+                        <span class="text-sm text-primary">{move || format!("{:.2}x", selected_param.with(|p| p.speed))}</span>
                     </div>
-                    <input type="range" class="w-full h-2 bg-gray-200 rounded-lg" />
+                    <input
+                        type="range"
+                        min="0.5"
+                        max="2"
+                        step="0.05"
+                        class="w-full h-2 bg-gray-200 rounded-lg accent-primary"
+                        prop:value=move || selected_param.with(|p| p.speed).to_string()
+                        on:input=move |ev| {
+                            if let Ok(v) = event_target_value(&ev).parse() {
+                                selected_param.update(|p| p.speed = v);
+                            }
+                        }
+                    />
                 </div>
-            </div>
-
-            // 待开发提示遮罩
-            <div class="absolute inset-0 flex items-center justify-center bg-white/10 backdrop-blur-[1px]">
-                <div class="bg-white/80 px-4 py-2 rounded-full border border-dashed border-gray-300 text-gray-500 text-sm shadow-sm flex items-center">
-                    <i class="fa fa-wrench mr-2"></i>
-                    "参数调节功能开发中..."
+                <div>
+                    <label class="block font-medium mb-2">{move || i18n.t().emotion}</label>
+                    <select
+                        class="w-full p-2 border border-gray-200 rounded-lg text-sm focus:outline-none focus:ring-2 focus:ring-primary/50"
+                        on:change=move |ev| selected_param.update(|p| p.emotion = event_target_value(&ev))
+                    >
+                        {EMOTIONS
+                            .into_iter()
+                            .map(|(value, label)| view! {
+                                <option value=value selected=move || selected_param.with(|p| p.emotion == value)>
+                                    {move || label(i18n.t())}
+                                </option>
+                            })
+                            .collect_view()}
+                    </select>
                 </div>
             </div>
         </section>
     }
+    .into_any()
 }
 
 #[component]
 pub fn AudioResultCard(
     /// 生成动作 (Action)
-    generate_action: Action<(), Result<String, ServerFnError>>,
+    generate_action: Action<(), Result<GeneratedAudio, ServerFnError>>,
+    /// 当前任务的合成进度
+    progress: RwSignal<JobProgress>,
+    /// 后期处理选项
+    post: RwSignal<PostProcessOptions>,
+    /// 合成时的 (文本, 声线 ID)
+    submitted: RwSignal<(String, String)>,
 ) -> impl IntoView {
+    let i18n = use_i18n();
     // 获取 Action 的状态信号
     let is_pending = generate_action.pending();
     let value = generate_action.value();
@@ -268,9 +608,11 @@ pub fn AudioResultCard(
         <section class="bg-white rounded-xl p-6 shadow-soft transition-all duration-300 hover:shadow-hover">
             <h3 class="text-lg font-semibold mb-4 flex items-center">
                 <i class="fa fa-volume-up text-primary mr-2"></i>
-                "输出结果"
+                {move || i18n.t().result_title}
             </h3>
 
+            <PostProcessPanel options=post />
+
             // --- 生成按钮 ---
             <div class="flex flex-wrap gap-3 mb-6">
                 <button
@@ -282,9 +624,9 @@ pub fn AudioResultCard(
                     disabled=move || is_pending.get()
                 >
                     {move || if is_pending.get() {
-                        view! { <> <i class="fa fa-circle-o-notch fa-spin mr-2"></i> "正在生成..." </> }.into_view()
+                        view! { <> <i class="fa fa-circle-o-notch fa-spin mr-2"></i> {i18n.t().generating} </> }.into_view()
                     } else {
-                        view! { <> <i class="fa fa-magic mr-2"></i> "生成音频" </> }.into_view()
+                        view! { <> <i class="fa fa-magic mr-2"></i> {i18n.t().generate} </> }.into_view()
                     }}
                 </button>
             </div>
@@ -296,23 +638,31 @@ pub fn AudioResultCard(
                     (true, _) => view! {
                         <div class="flex flex-col items-center justify-center py-8 animate-fade-in">
                             <div class="w-12 h-12 border-4 border-primary/30 border-t-primary rounded-full animate-spin mb-4"></div>
-                            <p class="text-gray-500">"AI 正在合成您的声音..."</p>
+                            <p class="text-gray-500">{move || i18n.t().synthesizing}</p>
+                            <JobProgressBar progress=progress />
                         </div>
                     }.into_any(),
 
                     // 2. 加载完成，成功获取 URL
-                    (false, Some(Ok(url))) => view! {
+                    (false, Some(Ok(GeneratedAudio { clip_id, src: url, duration, peaks, alignment }))) => view! {
                         <div class="border border-green-200 bg-green-50 rounded-xl p-6 animate-slide-up">
                             <div class="flex items-center mb-4">
                                 <div class="bg-green-100 p-2 rounded-full mr-3">
                                     <i class="fa fa-check text-green-600"></i>
                                 </div>
-                                <h4 class="font-semibold text-green-800">"生成完成！"</h4>
+                                <h4 class="font-semibold text-green-800">{move || i18n.t().generate_done}</h4>
                             </div>
                             <div class="mb-4">
-                                <p class="text-sm text-gray-600 mb-2">"处理后的音频："</p>
+                                <p class="text-sm text-gray-600 mb-2">{move || i18n.t().processed_audio}</p>
                                 <div class="flex flex-col gap-3">
-                                    <audio controls autoplay class="w-full" src=url.clone()></audio>
+                                    <WaveformPlayer
+                                        src=url.clone()
+                                        duration=duration
+                                        peaks=peaks
+                                        clip_id=clip_id.clone()
+                                        alignment=alignment.clone()
+                                        autoplay=true
+                                    />
                                     <a
                                         href=url
                                         download="tts_audio.mp3"
@@ -320,8 +670,16 @@ pub fn AudioResultCard(
                                         class="bg-white border border-green-200 text-green-700 hover:bg-green-100 px-4 py-2 rounded-lg text-sm flex items-center justify-center transition-colors"
                                     >
                                         <i class="fa fa-download mr-2"></i>
-                                        "下载音频"
+                                        {move || i18n.t().download_audio}
                                     </a>
+                                    {(!alignment.sentences.is_empty()).then(|| view! {
+                                        <SubtitleDownloads sentences=alignment.sentences />
+                                    })}
+                                    <BackgroundMusicPanel clip_id=clip_id.clone() />
+                                    {
+                                        let (text, voice_id) = submitted.get_untracked();
+                                        view! { <SharePanel clip_id=clip_id text=text voice_id=voice_id /> }
+                                    }
                                 </div>
                             </div>
                         </div>
@@ -331,8 +689,7 @@ pub fn AudioResultCard(
                     (false, Some(Err(e))) => view! {
                         <div class="text-center py-8 text-red-500 bg-red-50 rounded-xl border border-red-200">
                             <i class="fa fa-exclamation-triangle text-4xl mb-3 opacity-50"></i>
-                            <p>"生成失败: "</p>
-                            {move || debug_warn!("生成音频失败: {:?}", e)}
+                            <p>{move || format!("{}: {}", i18n.t().generate_failed, api::error_message(&e))}</p>
                         </div>
                     }.into_any(),
 
@@ -340,11 +697,123 @@ pub fn AudioResultCard(
                     _ => view! {
                         <div class="text-center py-12 text-gray-400 bg-gray-50 rounded-xl border border-dashed border-gray-200">
                             <i class="fa fa-headphones text-4xl mb-3 opacity-30"></i>
-                            <p class="text-sm">"输入文本后点击生成按钮"</p>
+                            <p class="text-sm">{move || i18n.t().result_idle}</p>
                         </div>
                     }.into_any(),
                 }}
             </div>
         </section>
     }
+    .into_any()
+}
+
+/// 后期处理开关：响度归一化 (可调目标响度)、静音裁剪、淡入淡出、峰值限制
+#[component]
+fn PostProcessPanel(options: RwSignal<PostProcessOptions>) -> impl IntoView {
+    let i18n = use_i18n();
+    // (名称, 读取字段, 写入字段)
+    type Field = (
+        fn(&Messages) -> &'static str,
+        fn(&PostProcessOptions) -> bool,
+        fn(&mut PostProcessOptions, bool),
+    );
+    let fields: [Field; 4] = [
+        (|m| m.post_loudness, |o| o.loudness, |o, v| o.loudness = v),
+        (
+            |m| m.post_trim_silence,
+            |o| o.trim_silence,
+            |o, v| o.trim_silence = v,
+        ),
+        (|m| m.post_fade, |o| o.fade, |o, v| o.fade = v),
+        (|m| m.post_limiter, |o| o.limiter, |o, v| o.limiter = v),
+    ];
+
+    view! {
+        <div class="mb-4 text-sm">
+            <div class="flex flex-wrap items-center gap-2">
+                <span class="text-gray-600 mr-1">{move || i18n.t().post_title}</span>
+                {fields
+                    .into_iter()
+                    .map(|(label, get, set)| view! {
+                        <button
+                            class="px-2 py-1 rounded-full text-xs border transition-colors duration-200"
+                            class=("border-primary", move || options.with(get))
+                            class=("text-primary", move || options.with(get))
+                            class=("border-gray-200", move || !options.with(get))
+                            class=("text-gray-400", move || !options.with(get))
+                            on:click=move |_| options.update(|o| set(o, !get(o)))
+                        >
+                            {move || label(i18n.t())}
+                        </button>
+                    })
+                    .collect_view()}
+            </div>
+            <Show when=move || options.with(|o| o.loudness)>
+                <div class="flex items-center gap-3 mt-3">
+                    <label class="text-xs text-gray-500 whitespace-nowrap">{move || i18n.t().target_loudness}</label>
+                    <input
+                        type="range"
+                        min=MIN_TARGET_LUFS.to_string()
+                        max=MAX_TARGET_LUFS.to_string()
+                        step="1"
+                        class="flex-1 h-2 bg-gray-200 rounded-lg accent-primary"
+                        prop:value=move || options.with(|o| o.target_lufs).to_string()
+                        on:input=move |ev| {
+                            if let Ok(v) = event_target_value(&ev).parse() {
+                                options.update(|o| o.target_lufs = v);
+                            }
+                        }
+                    />
+                    <span class="text-xs text-primary w-16 text-right">
+                        {move || format!("{} LUFS", options.with(|o| o.target_lufs))}
+                    </span>
+                </div>
+            </Show>
+        </div>
+    }
+    .into_any()
+}
+
+#[component]
+fn JobProgressBar(progress: RwSignal<JobProgress>) -> impl IntoView {
+    let i18n = use_i18n();
+    view! {
+        // 还没收到 Started 事件时不显示
+        <Show when=move || !progress.with(|p| p.chunks.is_empty())>
+            <div class="w-full max-w-md mt-4">
+                <div class="flex justify-between text-xs text-gray-500 mb-1">
+                    <span>{move || progress.with(|p| format!("{} {}/{}", i18n.t().chunks, p.done(), p.chunks.len()))}</span>
+                    <span>{move || format!("{}%", progress.with(JobProgress::percent))}</span>
+                </div>
+                // 总进度条
+                <div class="w-full h-2 bg-gray-100 rounded-full overflow-hidden">
+                    <div
+                        class="h-full bg-primary transition-all duration-300"
+                        style:width=move || format!("{}%", progress.with(JobProgress::percent))
+                    ></div>
+                </div>
+                // 每个分段的状态
+                <div class="flex flex-wrap gap-1 mt-2">
+                    {move || {
+                        progress
+                            .with(|p| p.chunks.clone())
+                            .into_iter()
+                            .map(|status| {
+                                let color = match status {
+                                    ChunkStatus::Pending => "bg-gray-200",
+                                    ChunkStatus::Running => "bg-primary/50 animate-pulse",
+                                    ChunkStatus::Done => "bg-primary",
+                                    ChunkStatus::Failed => "bg-red-400",
+                                };
+                                view! { <span class=format!("w-3 h-3 rounded-sm {}", color)></span> }
+                            })
+                            .collect_view()
+                    }}
+                </div>
+                {move || progress.with(|p| p.error.clone()).map(|e| view! {
+                    <p class="text-xs text-red-500 mt-2">{e}</p>
+                })}
+            </div>
+        </Show>
+    }
 }
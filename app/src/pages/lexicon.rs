@@ -0,0 +1,241 @@
+use crate::api::lexicon::{
+    delete_lexicon_entry, get_lexicon, preview_text, save_lexicon_entry, unlock_global_lexicon,
+    LexiconScope,
+};
+use crate::text::lexicon::{LexiconEntry, ReplacementKind};
+use crate::text::normalize::NormalizeOptions;
+use crate::text::ssml::Segment;
+use leptos::prelude::*;
+
+/// 发音词典：管理 "我的词典" 与 "全局词典"，并预览实际送去合成的文本
+#[component]
+pub fn LexiconCard(
+    /// 当前输入的文本，用于预览
+    text: RwSignal<String>,
+    /// 当前的文本规范化选项，预览时一并应用
+    normalize: RwSignal<NormalizeOptions>,
+) -> impl IntoView {
+    let scope = RwSignal::new(LexiconScope::User);
+    let word = RwSignal::new(String::new());
+    let replacement = RwSignal::new(String::new());
+    let kind = RwSignal::new(ReplacementKind::Text);
+
+    let save_action = Action::new(move |_: &()| {
+        let entry = LexiconEntry {
+            word: word.get_untracked(),
+            replacement: replacement.get_untracked(),
+            kind: kind.get_untracked(),
+        };
+        let scope = scope.get_untracked();
+        async move {
+            save_lexicon_entry(scope, entry).await?;
+            word.set(String::new());
+            replacement.set(String::new());
+            Ok::<_, ServerFnError>(())
+        }
+    });
+    let delete_action = Action::new(move |(scope, word): &(LexiconScope, String)| {
+        delete_lexicon_entry(*scope, word.clone())
+    });
+    let preview_action =
+        Action::new(move |_: &()| preview_text(text.get_untracked(), normalize.get_untracked()));
+    let admin_token = RwSignal::new(String::new());
+    let unlock_action = Action::new(move |_: &()| unlock_global_lexicon(admin_token.get_untracked()));
+
+    // 保存、删除或解锁后重新拉取词典
+    let lexicon = Resource::new(
+        move || {
+            (
+                save_action.version().get(),
+                delete_action.version().get(),
+                unlock_action.version().get(),
+            )
+        },
+        |_| get_lexicon(),
+    );
+
+    let error = move || {
+        save_action
+            .value()
+            .get()
+            .and_then(Result::err)
+            .or_else(|| delete_action.value().get().and_then(Result::err))
+            .or_else(|| unlock_action.value().get().and_then(Result::err))
+    };
+
+    view! {
+        <section class="bg-white rounded-xl p-6 shadow-soft transition-all duration-300 hover:shadow-hover">
+            <h3 class="text-lg font-semibold mb-4 flex items-center">
+                <i class="fa fa-book text-primary mr-2"></i>
+                "发音词典"
+            </h3>
+
+            // 词典切换
+            <div class="flex gap-2 mb-4 text-sm">
+                <ScopeTab scope=scope value=LexiconScope::User label="我的词典" />
+                <ScopeTab scope=scope value=LexiconScope::Global label="全局词典" />
+            </div>
+
+            <Suspense fallback=move || view! {
+                <div class="text-center py-4 text-gray-400 text-sm animate-pulse">"加载词典..."</div>
+            }>
+                {move || lexicon.get().map(|result| match result {
+                    Err(e) => view! {
+                        <p class="text-sm text-red-500">{format!("加载词典失败: {}", e)}</p>
+                    }.into_any(),
+                    Ok(view) => {
+                        let current_scope = scope.get();
+                        let (entries, editable) = match current_scope {
+                            LexiconScope::User => (view.user, true),
+                            LexiconScope::Global => (view.global, view.global_editable),
+                        };
+                        view! {
+                            <ul class="space-y-2 mb-4 max-h-48 overflow-y-auto">
+                                {entries.is_empty().then(|| view! {
+                                    <li class="text-center py-4 text-gray-400 text-sm">"暂无词条"</li>
+                                })}
+                                {entries.into_iter().map(|entry| {
+                                    let target = (current_scope, entry.word.clone());
+                                    view! {
+                                        <li class="flex items-center justify-between text-sm p-2 border border-gray-100 rounded-lg">
+                                            <span class="truncate">
+                                                <span class="font-medium">{entry.word}</span>
+                                                <i class="fa fa-long-arrow-right text-gray-300 mx-2"></i>
+                                                <span class="text-gray-600">{entry.replacement}</span>
+                                                {(entry.kind == ReplacementKind::Pinyin).then(|| view! {
+                                                    <span class="ml-2 text-xs text-secondary">"拼音"</span>
+                                                })}
+                                            </span>
+                                            {editable.then(|| view! {
+                                                <button
+                                                    class="text-gray-300 hover:text-red-500 transition-colors"
+                                                    title="删除"
+                                                    on:click=move |_| { delete_action.dispatch(target.clone()); }
+                                                >
+                                                    <i class="fa fa-trash-o"></i>
+                                                </button>
+                                            })}
+                                        </li>
+                                    }
+                                }).collect_view()}
+                            </ul>
+                            {(!editable).then(|| view! {
+                                <p class="text-xs text-gray-400 mb-2">"全局词典为只读，由管理员维护"</p>
+                                <div class="flex gap-2 mb-4">
+                                    <input
+                                        type="password"
+                                        class="flex-1 p-2 border border-gray-200 rounded-lg text-sm focus:outline-none focus:ring-2 focus:ring-primary/50"
+                                        placeholder="管理员令牌"
+                                        prop:value=move || admin_token.get()
+                                        on:input=move |ev| admin_token.set(event_target_value(&ev))
+                                    />
+                                    <button
+                                        class="px-3 text-sm text-primary border border-primary/30 rounded-lg hover:bg-primary/10 disabled:opacity-50"
+                                        on:click=move |_| { unlock_action.dispatch(()); }
+                                        disabled=move || unlock_action.pending().get() || admin_token.with(String::is_empty)
+                                    >
+                                        "解锁编辑"
+                                    </button>
+                                </div>
+                            })}
+                        }.into_any()
+                    }
+                })}
+            </Suspense>
+
+            // 新增词条
+            <div class="grid grid-cols-2 gap-2">
+                <input
+                    class="p-2 border border-gray-200 rounded-lg text-sm focus:outline-none focus:ring-2 focus:ring-primary/50"
+                    placeholder="词语，如 重庆"
+                    prop:value=move || word.get()
+                    on:input=move |ev| word.set(event_target_value(&ev))
+                />
+                <input
+                    class="p-2 border border-gray-200 rounded-lg text-sm focus:outline-none focus:ring-2 focus:ring-primary/50"
+                    placeholder=move || match kind.get() {
+                        ReplacementKind::Text => "替换为，如 崇庆",
+                        ReplacementKind::Pinyin => "拼音，如 chong2 qing4",
+                    }
+                    prop:value=move || replacement.get()
+                    on:input=move |ev| replacement.set(event_target_value(&ev))
+                />
+                <select
+                    class="p-2 border border-gray-200 rounded-lg text-sm focus:outline-none focus:ring-2 focus:ring-primary/50"
+                    on:change=move |ev| kind.set(match event_target_value(&ev).as_str() {
+                        "pinyin" => ReplacementKind::Pinyin,
+                        _ => ReplacementKind::Text,
+                    })
+                >
+                    <option value="text">"替换文本"</option>
+                    <option value="pinyin">"标注拼音"</option>
+                </select>
+                <button
+                    class="bg-primary hover:bg-primary-focus text-white rounded-lg text-sm font-medium transition-all duration-300 disabled:opacity-50 disabled:cursor-not-allowed"
+                    on:click=move |_| { save_action.dispatch(()); }
+                    disabled=move || save_action.pending().get() || word.with(String::is_empty)
+                >
+                    <i class="fa fa-plus mr-1"></i>
+                    "添加"
+                </button>
+            </div>
+            {move || error().map(|e| view! { <p class="text-xs text-red-500 mt-2">{e.to_string()}</p> })}
+
+            // 合成文本预览
+            <div class="mt-6 pt-4 border-t border-gray-100">
+                <button
+                    class="text-sm text-primary hover:underline flex items-center disabled:opacity-50"
+                    on:click=move |_| { preview_action.dispatch(()); }
+                    disabled=move || preview_action.pending().get()
+                >
+                    <i class="fa fa-eye mr-2"></i>
+                    "预览实际合成文本"
+                </button>
+                {move || preview_action.value().get().map(|result| match result {
+                    Err(e) => view! { <p class="text-xs text-red-500 mt-2">{e.to_string()}</p> }.into_any(),
+                    Ok(preview) => view! {
+                        <div class="mt-3 p-3 bg-gray-50 rounded-lg text-sm text-gray-700 space-y-1">
+                            {preview.segments.into_iter().map(|segment| match segment {
+                                Segment::Speech { text, .. } => view! { <p>{text}</p> }.into_any(),
+                                Segment::Break(secs) => view! {
+                                    <p class="text-xs text-gray-400">{format!("[停顿 {:.1}s]", secs)}</p>
+                                }.into_any(),
+                            }).collect_view()}
+                        </div>
+                        <ul class="mt-2 space-y-1">
+                            {preview.applied.into_iter().map(|hit| view! {
+                                <li class="text-xs" class:text-gray-500=!hit.skipped class:text-yellow-600=hit.skipped>
+                                    {if hit.skipped {
+                                        format!("「{}」({}) 命中 {} 次：拼音无法识别，已按原文朗读", hit.word, hit.replacement, hit.count)
+                                    } else {
+                                        format!("「{}」→「{}」替换 {} 次", hit.word, hit.replacement, hit.count)
+                                    }}
+                                </li>
+                            }).collect_view()}
+                        </ul>
+                    }.into_any(),
+                })}
+            </div>
+        </section>
+    }
+    .into_any()
+}
+
+#[component]
+fn ScopeTab(
+    scope: RwSignal<LexiconScope>,
+    value: LexiconScope,
+    label: &'static str,
+) -> impl IntoView {
+    view! {
+        <button
+            class="px-3 py-1 rounded-full transition-colors duration-300"
+            class=("bg-primary/10", move || scope.get() == value)
+            class=("text-primary", move || scope.get() == value)
+            class=("text-gray-500", move || scope.get() != value)
+            on:click=move |_| scope.set(value)
+        >
+            {label}
+        </button>
+    }
+}
@@ -0,0 +1,192 @@
+use crate::api::mixer::{mix_background_music, MusicSource};
+use crate::api::voicefilter::{upload_recording, AudioClip, MAX_UPLOAD_SECS, UPLOAD_FIELD};
+use crate::audio::mixer::{BuiltinBed, MixSettings};
+use crate::clips::clip_url;
+use crate::i18n::use_i18n;
+use crate::pages::waveform::WaveformPlayer;
+use leptos::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{FormData, HtmlFormElement};
+
+/// 下拉框中 "上传音乐" 一项的值
+const UPLOAD_OPTION: &str = "upload";
+
+/// 背景音乐：选择内置或上传的音乐，调节音量与闪避后混入合成结果
+#[component]
+pub fn BackgroundMusicPanel(
+    /// 片段库中合成结果的 ID
+    clip_id: String,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    // 选中的内置音乐序号，`None` 表示使用上传的音乐
+    let builtin = RwSignal::new(Some(0usize));
+    let uploaded = RwSignal::new(None::<AudioClip>);
+    let settings = RwSignal::new(MixSettings::default());
+
+    // FormData 不能跨线程，使用本地 Action
+    let upload_action = Action::new_local(move |data: &FormData| {
+        let upload = upload_recording(data.clone().into());
+        async move {
+            uploaded.set(Some(upload.await?));
+            Ok::<_, ServerFnError>(())
+        }
+    });
+    let on_file_change = move |ev: leptos::ev::Event| {
+        let Some(form) = ev
+            .current_target()
+            .and_then(|t| t.dyn_into::<HtmlFormElement>().ok())
+        else {
+            return;
+        };
+        if let Ok(data) = FormData::new_with_form(&form) {
+            upload_action.dispatch_local(data);
+        }
+    };
+
+    let mix_action = Action::new(move |_: &()| {
+        let clip_id = clip_id.clone();
+        let music = match builtin.get_untracked() {
+            Some(i) => BuiltinBed::ALL.get(i).copied().map(MusicSource::Builtin),
+            None => {
+                uploaded.with_untracked(|c| c.as_ref().map(|c| MusicSource::Clip(c.id.clone())))
+            }
+        };
+        let settings = settings.get_untracked();
+        async move {
+            let Some(music) = music else {
+                return Err(ServerFnError::ServerError("请先上传背景音乐".to_string()));
+            };
+            mix_background_music(clip_id, music, settings).await
+        }
+    });
+
+    view! {
+        <details class="bg-white border border-green-200 rounded-lg text-sm">
+            <summary class="px-4 py-2 cursor-pointer text-green-700 select-none">
+                <i class="fa fa-music mr-2"></i>
+                "背景音乐"
+            </summary>
+            <div class="p-4 pt-2 space-y-4">
+                <select
+                    class="w-full p-2 border border-gray-200 rounded-lg focus:outline-none focus:ring-2 focus:ring-primary/50"
+                    on:change=move |ev| builtin.set(event_target_value(&ev).parse().ok())
+                >
+                    {BuiltinBed::ALL
+                        .iter()
+                        .enumerate()
+                        .map(|(i, bed)| view! {
+                            <option value=i.to_string() selected=move || builtin.get() == Some(i)>
+                                {move || bed.name(i18n.t())}
+                            </option>
+                        })
+                        .collect_view()}
+                    <option value=UPLOAD_OPTION>"上传音乐 (仅 WAV)"</option>
+                </select>
+
+                <Show when=move || builtin.get().is_none()>
+                    <form on:change=on_file_change>
+                        <input
+                            type="file"
+                            name=UPLOAD_FIELD
+                            accept=".wav,audio/wav"
+                            class="w-full text-sm text-gray-600 file:mr-3 file:py-2 file:px-4 file:rounded-lg file:border-0 file:bg-primary/10 file:text-primary hover:file:bg-primary/20"
+                            disabled=move || upload_action.pending().get()
+                        />
+                    </form>
+                    <p class="text-xs text-gray-400 mt-1">
+                        {format!(
+                            "仅支持 WAV 格式，最长 {} 秒；MP3、OGG 等压缩格式请先转换为 WAV",
+                            MAX_UPLOAD_SECS,
+                        )}
+                    </p>
+                    {move || uploaded.get().map(|clip| view! {
+                        <p class="text-xs text-gray-500 mt-1">
+                            {format!("已上传 {:.1} 秒，不足时循环播放", clip.duration)}
+                        </p>
+                    })}
+                    {move || upload_action.value().get().and_then(Result::err).map(|e| view! {
+                        <p class="text-xs text-red-500 mt-1">{e.to_string()}</p>
+                    })}
+                </Show>
+
+                <div>
+                    <div class="flex justify-between mb-1">
+                        <label>"音乐音量 (相对人声)"</label>
+                        <span class="text-primary">{move || format!("{} dB", settings.with(|s| s.music_db))}</span>
+                    </div>
+                    <input
+                        type="range"
+                        min="-30"
+                        max="-3"
+                        step="1"
+                        class="w-full h-2 bg-gray-200 rounded-lg accent-primary"
+                        prop:value=move || settings.with(|s| s.music_db).to_string()
+                        on:input=move |ev| {
+                            if let Ok(v) = event_target_value(&ev).parse() {
+                                settings.update(|s| s.music_db = v);
+                            }
+                        }
+                    />
+                </div>
+                <div>
+                    <div class="flex justify-between mb-1">
+                        <label class="flex items-center cursor-pointer">
+                            <input
+                                type="checkbox"
+                                class="mr-2 accent-primary"
+                                prop:checked=move || settings.with(|s| s.ducking)
+                                on:change=move |ev| settings.update(|s| s.ducking = event_target_checked(&ev))
+                            />
+                            "说话时压低音乐"
+                        </label>
+                        <span class="text-primary">{move || format!("{} dB", settings.with(|s| s.duck_db))}</span>
+                    </div>
+                    <input
+                        type="range"
+                        min="-24"
+                        max="0"
+                        step="1"
+                        class="w-full h-2 bg-gray-200 rounded-lg accent-primary disabled:opacity-50"
+                        disabled=move || settings.with(|s| !s.ducking)
+                        prop:value=move || settings.with(|s| s.duck_db).to_string()
+                        on:input=move |ev| {
+                            if let Ok(v) = event_target_value(&ev).parse() {
+                                settings.update(|s| s.duck_db = v);
+                            }
+                        }
+                    />
+                </div>
+
+                <button
+                    class="w-full bg-primary hover:bg-primary-focus text-white py-2 px-4 rounded-lg font-medium transition-all duration-300 flex items-center justify-center disabled:opacity-50 disabled:cursor-not-allowed"
+                    on:click=move |_| { mix_action.dispatch(()); }
+                    disabled=move || mix_action.pending().get()
+                >
+                    {move || if mix_action.pending().get() {
+                        view! { <> <i class="fa fa-circle-o-notch fa-spin mr-2"></i> "正在混音..." </> }.into_view()
+                    } else {
+                        view! { <> <i class="fa fa-sliders mr-2"></i> "混入背景音乐" </> }.into_view()
+                    }}
+                </button>
+
+                {move || mix_action.value().get().map(|result| match result {
+                    Ok(clip) => view! {
+                        <WaveformPlayer src=clip_url(&clip.id) duration=clip.duration peaks=clip.peaks clip_id=clip.id.clone() />
+                        <a
+                            href=clip_url(&clip.id)
+                            download="tts_with_music.wav"
+                            class="mt-3 bg-white border border-green-200 text-green-700 hover:bg-green-100 px-4 py-2 rounded-lg text-sm flex items-center justify-center transition-colors"
+                        >
+                            <i class="fa fa-download mr-2"></i>
+                            "下载混音"
+                        </a>
+                    }.into_any(),
+                    Err(e) => view! {
+                        <p class="text-sm text-red-500 bg-red-50 rounded-lg p-3">{format!("混音失败: {}", e)}</p>
+                    }.into_any(),
+                })}
+            </div>
+        </details>
+    }
+    .into_any()
+}
@@ -0,0 +1,390 @@
+use crate::api::presets::{save_voice_preset, VoicePreset};
+use crate::api::{self, GeneratedAudio, VoiceOption};
+use crate::i18n::use_i18n;
+use crate::pages::homepage::{GenerateParams, VoiceParams};
+use crate::pages::waveform::Waveform;
+use leptos::html::Audio;
+use leptos::prelude::*;
+
+/// 最少、最多同时对比的声线数
+const MIN_SLOTS: usize = 2;
+const MAX_SLOTS: usize = 4;
+
+/// 对比中的一路：声线 + 参数，以及各自的合成动作和播放器
+#[derive(Clone, Copy)]
+struct Slot {
+    voice: RwSignal<String>,
+    params: RwSignal<VoiceParams>,
+    action: Action<(), Result<GeneratedAudio, ServerFnError>>,
+    audio_ref: NodeRef<Audio>,
+}
+
+/// 声线对比：同一段文本用多组声线/参数并行合成，A/B 切换试听并选出最佳
+#[component]
+pub fn Playground() -> impl IntoView {
+    let i18n = use_i18n();
+
+    // 状态
+    let text = RwSignal::new(String::new());
+    let slot_count = RwSignal::new(MIN_SLOTS);
+    // 共享的播放位置 (秒)，切换试听时从同一位置继续
+    let position = RwSignal::new(0.0_f32);
+    let playing = RwSignal::new(None::<usize>);
+    let preset_name = RwSignal::new(String::new());
+    let winner = RwSignal::new(None::<usize>);
+
+    let slots: Vec<Slot> = (0..MAX_SLOTS)
+        .map(|_| {
+            let voice = RwSignal::new(String::new());
+            let params = RwSignal::new(VoiceParams::default());
+            let action = Action::new(move |_: &()| {
+                api::generate_audio(GenerateParams::new(
+                    text.get_untracked(),
+                    voice.get_untracked(),
+                    params.get_untracked(),
+                ))
+            });
+            Slot {
+                voice,
+                params,
+                action,
+                audio_ref: NodeRef::new(),
+            }
+        })
+        .collect();
+    let slots = StoredValue::new(slots);
+
+    let visible = move || slots.with_value(|s| s[..slot_count.get()].to_vec());
+    let any_pending = move || visible().iter().any(|s| s.action.pending().get());
+
+    // 所有波形按最长的一段对齐
+    let scale = Signal::derive(move || {
+        visible()
+            .iter()
+            .filter_map(|s| s.action.value().get().and_then(Result::ok))
+            .map(|audio| audio.duration)
+            .fold(0.0_f32, f32::max)
+    });
+
+    let generate_all = move || {
+        position.set(0.0);
+        winner.set(None);
+        stop_all(slots, playing);
+        for slot in visible() {
+            slot.action.dispatch(());
+        }
+    };
+
+    let play = move |index: usize| {
+        stop_all(slots, playing);
+        let Some(audio) = slots.with_value(|s| s[index].audio_ref.get()) else {
+            return;
+        };
+        audio.set_current_time(position.get_untracked() as f64);
+        if audio.play().is_ok() {
+            playing.set(Some(index));
+        }
+    };
+
+    let seek = Callback::new(move |secs: f32| {
+        position.set(secs);
+        if let Some(index) = playing.get_untracked() {
+            if let Some(audio) = slots.with_value(|s| s[index].audio_ref.get()) {
+                audio.set_current_time(secs as f64);
+            }
+        }
+    });
+
+    let save_winner = Action::new(move |index: &usize| {
+        let index = *index;
+        let slot = slots.with_value(|s| s[index]);
+        let preset = VoicePreset {
+            name: preset_name.get_untracked(),
+            voice_id: slot.voice.get_untracked(),
+            params: slot.params.get_untracked(),
+            is_default: false,
+        };
+        async move {
+            save_voice_preset(preset).await?;
+            winner.set(Some(index));
+            Ok::<_, ServerFnError>(())
+        }
+    });
+
+    let voices_resource = Resource::new(|| (), |_| api::get_voices());
+
+    view! {
+        <div class="min-h-screen bg-base-100 pb-12">
+            <div class="container mx-auto px-4 py-8 md:py-12 max-w-6xl space-y-8">
+
+                <section class="text-center">
+                    <h2 class="text-[clamp(1.8rem,4vw,2.5rem)] font-bold mb-4 text-shadow text-dark">
+                        {move || i18n.t().nav_playground}
+                    </h2>
+                    <p class="text-gray-600 max-w-2xl mx-auto">
+                        {move || i18n.t().playground_subtitle}
+                    </p>
+                </section>
+
+                // --- 共享文本 ---
+                <section class="bg-white rounded-xl p-6 shadow-soft transition-all duration-300 hover:shadow-hover">
+                    <h3 class="text-lg font-semibold mb-4 flex items-center">
+                        <i class="fa fa-pencil text-primary mr-2"></i>
+                        {move || i18n.t().playground_text_title}
+                    </h3>
+                    <textarea
+                        class="w-full h-28 p-4 border border-gray-200 rounded-lg focus:outline-none focus:ring-2 focus:ring-primary/50 resize-none transition-all duration-300"
+                        placeholder=move || i18n.t().playground_placeholder
+                        prop:value=move || text.get()
+                        on:input=move |ev| text.set(event_target_value(&ev))
+                    ></textarea>
+                    <div class="flex flex-wrap items-center gap-3 mt-4">
+                        <button
+                            class="bg-primary hover:bg-primary-focus text-white py-2 px-6 rounded-lg font-medium transition-all duration-300 flex items-center disabled:opacity-50 disabled:cursor-not-allowed"
+                            on:click=move |_| generate_all()
+                            disabled=move || any_pending() || text.with(|t| t.trim().is_empty())
+                        >
+                            <i class="fa fa-magic mr-2"></i>
+                            {move || i18n.t().playground_synthesize_all}
+                        </button>
+                        <button
+                            class="border border-gray-200 text-gray-600 hover:border-primary hover:text-primary py-2 px-4 rounded-lg text-sm transition-colors disabled:opacity-50"
+                            on:click=move |_| slot_count.update(|n| *n += 1)
+                            disabled=move || slot_count.get() >= MAX_SLOTS
+                        >
+                            <i class="fa fa-plus mr-1"></i>
+                            {move || i18n.t().playground_add_slot}
+                        </button>
+                        <button
+                            class="border border-gray-200 text-gray-600 hover:border-primary hover:text-primary py-2 px-4 rounded-lg text-sm transition-colors disabled:opacity-50"
+                            on:click=move |_| {
+                                stop_all(slots, playing);
+                                slot_count.update(|n| *n -= 1);
+                            }
+                            disabled=move || slot_count.get() <= MIN_SLOTS
+                        >
+                            <i class="fa fa-minus mr-1"></i>
+                            {move || i18n.t().playground_remove_slot}
+                        </button>
+                        <input
+                            class="ml-auto p-2 border border-gray-200 rounded-lg text-sm focus:outline-none focus:ring-2 focus:ring-primary/50"
+                            placeholder=move || i18n.t().playground_preset_placeholder
+                            prop:value=move || preset_name.get()
+                            on:input=move |ev| preset_name.set(event_target_value(&ev))
+                        />
+                    </div>
+                    {move || save_winner.value().get().and_then(Result::err).map(|e| view! {
+                        <p class="text-xs text-red-500 mt-2">{e.to_string()}</p>
+                    })}
+                </section>
+
+                // --- 各组对比 ---
+                <div class="grid grid-cols-1 md:grid-cols-2 gap-8">
+                    {move || visible().into_iter().enumerate().map(|(index, slot)| view! {
+                        <SlotCard
+                            index=index
+                            state=slot
+                            voices=voices_resource
+                            scale=scale
+                            position=position
+                            playing=playing
+                            winner=winner
+                            on_seek=seek
+                            on_play=move || play(index)
+                            on_pause=move || stop_all(slots, playing)
+                            on_pick=move || { save_winner.dispatch(index); }
+                            pick_disabled=Signal::derive(move || {
+                                save_winner.pending().get() || preset_name.with(|n| n.trim().is_empty())
+                            })
+                        />
+                    }).collect_view()}
+                </div>
+            </div>
+        </div>
+    }
+}
+
+/// 暂停所有播放器
+fn stop_all(slots: StoredValue<Vec<Slot>>, playing: RwSignal<Option<usize>>) {
+    slots.with_value(|slots| {
+        for slot in slots {
+            if let Some(audio) = slot.audio_ref.get_untracked() {
+                let _ = audio.pause();
+            }
+        }
+    });
+    playing.set(None);
+}
+
+/// 组标签：A、B、C、D
+fn slot_label(index: usize) -> char {
+    (b'A' + index as u8) as char
+}
+
+#[component]
+fn SlotCard(
+    index: usize,
+    state: Slot,
+    voices: Resource<Result<Vec<VoiceOption>, ServerFnError>>,
+    #[prop(into)] scale: Signal<f32>,
+    position: RwSignal<f32>,
+    playing: RwSignal<Option<usize>>,
+    winner: RwSignal<Option<usize>>,
+    on_seek: Callback<f32>,
+    on_play: impl Fn() + Copy + Send + Sync + 'static,
+    on_pause: impl Fn() + Copy + Send + Sync + 'static,
+    on_pick: impl Fn() + Copy + Send + Sync + 'static,
+    pick_disabled: Signal<bool>,
+) -> impl IntoView {
+    let Slot {
+        voice,
+        params,
+        action,
+        audio_ref,
+    } = state;
+    let is_playing = move || playing.get() == Some(index);
+    let i18n = use_i18n();
+
+    view! {
+        <section
+            class="bg-white rounded-xl p-6 shadow-soft transition-all duration-300 hover:shadow-hover border-2"
+            class=("border-primary", move || winner.get() == Some(index))
+            class=("border-transparent", move || winner.get() != Some(index))
+        >
+            <div class="flex items-center justify-between mb-4">
+                <h3 class="text-lg font-semibold flex items-center">
+                    <span class="w-8 h-8 rounded-full bg-primary/10 text-primary flex items-center justify-center mr-2">
+                        {slot_label(index).to_string()}
+                    </span>
+                    {move || (i18n.t().playground_slot)(index + 1)}
+                </h3>
+                {move || (winner.get() == Some(index)).then(|| view! {
+                    <span class="text-sm text-primary flex items-center">
+                        <i class="fa fa-trophy mr-1"></i>
+                        {move || i18n.t().playground_saved}
+                    </span>
+                })}
+            </div>
+
+            // 声线与参数
+            <div class="space-y-3 mb-4">
+                <Suspense fallback=move || view! {
+                    <div class="text-sm text-gray-400 animate-pulse">{move || i18n.t().voices_loading}</div>
+                }>
+                    {move || voices.get().map(|result| match result {
+                        Err(e) => view! {
+                            <p class="text-sm text-red-500">{move || format!("{}: {}", i18n.t().voices_failed, e)}</p>
+                        }.into_any(),
+                        Ok(list) => view! {
+                            <select
+                                class="w-full p-2 border border-gray-200 rounded-lg text-sm focus:outline-none focus:ring-2 focus:ring-primary/50"
+                                prop:value=move || voice.get()
+                                on:change=move |ev| voice.set(event_target_value(&ev))
+                            >
+                                <option value="">{move || i18n.t().select_voice}</option>
+                                {list.into_iter().map(|v| view! {
+                                    <option value=v.id.clone()>{format!("{} ({})", v.name, v.id)}</option>
+                                }).collect_view()}
+                            </select>
+                        }.into_any(),
+                    })}
+                </Suspense>
+                <div>
+                    <div class="flex justify-between text-sm mb-1">
+                        <label>{move || i18n.t().speed}</label>
+                        <span class="text-primary">{move || format!("{:.2}x", params.with(|p| p.speed))}</span>
+                    </div>
+                    <input
+                        type="range"
+                        min="0.5"
+                        max="2"
+                        step="0.05"
+                        class="w-full h-2 bg-gray-200 rounded-lg accent-primary"
+                        prop:value=move || params.with(|p| p.speed).to_string()
+                        on:input=move |ev| {
+                            if let Ok(v) = event_target_value(&ev).parse() {
+                                params.update(|p| p.speed = v);
+                            }
+                        }
+                    />
+                </div>
+                <div>
+                    <div class="flex justify-between text-sm mb-1">
+                        <label>{move || i18n.t().pitch}</label>
+                        <span class="text-primary">{move || format!("{:+.0} {}", params.with(|p| p.pitch), i18n.t().semitones)}</span>
+                    </div>
+                    <input
+                        type="range"
+                        min="-12"
+                        max="12"
+                        step="1"
+                        class="w-full h-2 bg-gray-200 rounded-lg accent-primary"
+                        prop:value=move || params.with(|p| p.pitch).to_string()
+                        on:input=move |ev| {
+                            if let Ok(v) = event_target_value(&ev).parse() {
+                                params.update(|p| p.pitch = v);
+                            }
+                        }
+                    />
+                </div>
+            </div>
+
+            // 合成结果
+            {move || match (action.pending().get(), action.value().get()) {
+                (true, _) => view! {
+                    <div class="flex items-center justify-center h-16 text-sm text-gray-500">
+                        <i class="fa fa-circle-o-notch fa-spin mr-2"></i>
+                        {move || i18n.t().generating}
+                    </div>
+                }.into_any(),
+                (false, Some(Ok(GeneratedAudio { src, duration, peaks, .. }))) => view! {
+                    <audio
+                        node_ref=audio_ref
+                        src=src
+                        preload="auto"
+                        class="hidden"
+                        on:timeupdate=move |_| {
+                            if is_playing() {
+                                if let Some(audio) = audio_ref.get_untracked() {
+                                    position.set(audio.current_time() as f32);
+                                }
+                            }
+                        }
+                        on:ended=move |_| playing.set(None)
+                    ></audio>
+                    <Waveform peaks=peaks duration=duration scale=scale position=position on_seek=on_seek />
+                    <div class="flex items-center gap-2 mt-3">
+                        <button
+                            class="bg-primary/10 text-primary hover:bg-primary hover:text-white w-10 h-10 rounded-full transition-colors"
+                            title=move || if is_playing() { i18n.t().pause } else { i18n.t().play_from_position }
+                            on:click=move |_| if is_playing() { on_pause() } else { on_play() }
+                        >
+                            <i class=move || if is_playing() { "fa fa-pause" } else { "fa fa-play" }></i>
+                        </button>
+                        <span class="text-xs text-gray-500">
+                            {move || format!("{:.1}s / {:.1}s", position.get().min(duration), duration)}
+                        </span>
+                        <button
+                            class="ml-auto border border-primary text-primary hover:bg-primary hover:text-white py-1.5 px-3 rounded-lg text-sm transition-colors disabled:opacity-50 disabled:cursor-not-allowed"
+                            title=move || i18n.t().playground_pick_best_title
+                            on:click=move |_| on_pick()
+                            disabled=move || pick_disabled.get()
+                        >
+                            <i class="fa fa-trophy mr-1"></i>
+                            {move || i18n.t().playground_pick_best}
+                        </button>
+                    </div>
+                }.into_any(),
+                (false, Some(Err(e))) => view! {
+                    <p class="text-sm text-red-500 bg-red-50 rounded-lg p-3">{move || format!("{}: {}", i18n.t().generate_failed, api::error_message(&e))}</p>
+                }.into_any(),
+                (false, None) => view! {
+                    <div class="flex items-center justify-center h-16 text-sm text-gray-400 bg-gray-50 rounded-lg border border-dashed border-gray-200">
+                        {move || i18n.t().playground_waiting}
+                    </div>
+                }.into_any(),
+            }}
+        </section>
+    }
+    .into_any()
+}
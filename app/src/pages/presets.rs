@@ -0,0 +1,303 @@
+use crate::api::presets::{
+    delete_voice_preset, get_shared_preset, list_voice_presets, rename_voice_preset,
+    save_voice_preset, set_default_voice_preset, share_voice_preset, VoicePreset,
+    MAX_PRESET_NAME_CHARS, SHARE_QUERY_KEY,
+};
+use crate::pages::homepage::VoiceParams;
+use crate::pages::{copy_link, SharedLink};
+use leptos::prelude::*;
+use leptos_router::hooks::use_query_map;
+
+/// 生成分享链接并尽量复制到剪贴板
+async fn share_link(name: String) -> Result<(String, bool), ServerFnError> {
+    let token = share_voice_preset(name).await?;
+    Ok(copy_link(&format!("/?{}={}", SHARE_QUERY_KEY, token)).await)
+}
+
+/// 我的预设：一键套用、保存当前组合、重命名、设为默认和分享
+#[component]
+pub fn VoicePresetPanel(
+    /// 当前选中的声线 ID
+    voice: RwSignal<String>,
+    /// 当前的声线参数
+    params: RwSignal<VoiceParams>,
+) -> impl IntoView {
+    let new_name = RwSignal::new(String::new());
+    // 正在重命名的预设 (原名, 输入框内容)
+    let renaming = RwSignal::new(None::<(String, String)>);
+
+    let apply = move |preset: &VoicePreset| {
+        voice.set(preset.voice_id.clone());
+        params.set(preset.params.clone());
+    };
+
+    let current_preset = move || VoicePreset {
+        name: new_name.get_untracked(),
+        voice_id: voice.get_untracked(),
+        params: params.get_untracked(),
+        is_default: false,
+    };
+    let save_action = Action::new(move |preset: &VoicePreset| {
+        let preset = preset.clone();
+        async move {
+            save_voice_preset(preset).await?;
+            new_name.set(String::new());
+            Ok::<_, ServerFnError>(())
+        }
+    });
+    let rename_action = Action::new(move |(name, new_name): &(String, String)| {
+        let (name, new_name) = (name.clone(), new_name.clone());
+        async move {
+            rename_voice_preset(name, new_name).await?;
+            renaming.set(None);
+            Ok::<_, ServerFnError>(())
+        }
+    });
+    let default_action =
+        Action::new(move |name: &Option<String>| set_default_voice_preset(name.clone()));
+    let delete_action = Action::new(move |name: &String| delete_voice_preset(name.clone()));
+    // 剪贴板 API 返回的 Future 不能跨线程，使用本地 Action
+    let share_action = Action::new_local(move |name: &String| share_link(name.clone()));
+
+    let presets = Resource::new(
+        move || {
+            (
+                save_action.version().get(),
+                rename_action.version().get(),
+                default_action.version().get(),
+                delete_action.version().get(),
+            )
+        },
+        |_| list_voice_presets(),
+    );
+
+    // 通过分享链接打开时套用分享的预设，否则套用默认预设；都只在首次加载时生效
+    let shared_token = use_query_map().with_untracked(|q| q.get(SHARE_QUERY_KEY));
+    let has_shared = shared_token.is_some();
+    let shared = Resource::new(
+        move || shared_token.clone(),
+        |token| async move {
+            match token {
+                Some(token) => get_shared_preset(token).await.map(Some),
+                None => Ok(None),
+            }
+        },
+    );
+    let initialized = StoredValue::new(false);
+    Effect::new(move |_| {
+        if initialized.get_value() {
+            return;
+        }
+        let preset = if has_shared {
+            match shared.get() {
+                Some(Ok(preset)) => preset,
+                Some(Err(_)) => None,
+                None => return,
+            }
+        } else {
+            match presets.get() {
+                // 已从地址栏或本地恢复了声线时不再覆盖
+                Some(Ok(list)) => list
+                    .into_iter()
+                    .find(|p| p.is_default)
+                    .filter(|_| voice.with_untracked(String::is_empty)),
+                Some(Err(_)) => None,
+                None => return,
+            }
+        };
+        initialized.set_value(true);
+        if let Some(preset) = preset {
+            apply(&preset);
+        }
+    });
+
+    let is_active = move |preset: &VoicePreset| {
+        voice.with(|v| *v == preset.voice_id) && params.with(|p| *p == preset.params)
+    };
+
+    let action_error = move || {
+        [
+            save_action.value().get(),
+            rename_action.value().get(),
+            default_action.value().get(),
+            delete_action.value().get(),
+        ]
+        .into_iter()
+        .flatten()
+        .find_map(Result::err)
+        .or_else(|| share_action.value().get().and_then(Result::err))
+    };
+
+    view! {
+        <div class="mb-6 pb-6 border-b border-gray-100 text-sm">
+            <h4 class="font-medium text-gray-600 mb-3 flex items-center">
+                <i class="fa fa-bookmark text-primary mr-2"></i>
+                "我的预设"
+            </h4>
+
+            // 分享链接带来的预设
+            {move || shared.get().map(|result| match result {
+                Ok(Some(preset)) => {
+                    let to_save = preset.clone();
+                    Some(view! {
+                        <div class="mb-3 p-3 rounded-lg bg-primary/5 border border-primary/20 flex items-center gap-2">
+                            <i class="fa fa-share-alt text-primary"></i>
+                            <span class="flex-1 truncate">
+                                {format!("已套用分享的预设「{}」", preset.name)}
+                            </span>
+                            <button
+                                class="text-xs text-primary hover:underline disabled:opacity-50"
+                                on:click=move |_| { save_action.dispatch(to_save.clone()); }
+                                disabled=move || save_action.pending().get()
+                            >
+                                "保存到我的预设"
+                            </button>
+                        </div>
+                    }.into_any())
+                }
+                Ok(None) => None,
+                Err(e) => Some(view! {
+                    <p class="mb-3 text-xs text-red-500">{e.to_string()}</p>
+                }.into_any()),
+            })}
+
+            <Suspense fallback=move || view! {
+                <div class="text-gray-400 animate-pulse">"加载预设..."</div>
+            }>
+                {move || presets.get().map(|result| match result {
+                    Err(e) => view! {
+                        <p class="text-red-500">{format!("加载预设失败: {}", e)}</p>
+                    }.into_any(),
+                    Ok(list) if list.is_empty() => view! {
+                        <p class="text-gray-400 text-xs">"还没有预设，调好声线和参数后保存一个吧"</p>
+                    }.into_any(),
+                    Ok(list) => view! {
+                        <ul class="space-y-2">
+                            {list.into_iter().map(|preset| {
+                                let name = preset.name.clone();
+                                let is_default = preset.is_default;
+                                let active = {
+                                    let preset = preset.clone();
+                                    move || is_active(&preset)
+                                };
+                                let editing = {
+                                    let name = name.clone();
+                                    move || renaming.with(|r| r.as_ref().is_some_and(|(n, _)| *n == name))
+                                };
+                                let (rename_from, default_name, share_name, delete_name) =
+                                    (name.clone(), name.clone(), name.clone(), name.clone());
+                                view! {
+                                    <li
+                                        class="flex items-center gap-2 p-2 border rounded-lg transition-colors"
+                                        class=("border-primary", active.clone())
+                                        class=("border-gray-100", move || !active())
+                                    >
+                                        <Show
+                                            when=editing
+                                            fallback=move || {
+                                                let preset = preset.clone();
+                                                view! {
+                                                    <button
+                                                        class="flex-1 text-left truncate hover:text-primary transition-colors"
+                                                        title="套用预设"
+                                                        on:click=move |_| apply(&preset)
+                                                    >
+                                                        {preset.name.clone()}
+                                                    </button>
+                                                }
+                                            }
+                                        >
+                                            <input
+                                                class="flex-1 min-w-0 p-1 border border-gray-200 rounded focus:outline-none focus:ring-2 focus:ring-primary/50"
+                                                maxlength=MAX_PRESET_NAME_CHARS.to_string()
+                                                prop:value=move || renaming.with(|r| r.as_ref().map(|(_, t)| t.clone()).unwrap_or_default())
+                                                on:input=move |ev| renaming.update(|r| {
+                                                    if let Some((_, text)) = r {
+                                                        *text = event_target_value(&ev);
+                                                    }
+                                                })
+                                                on:keydown=move |ev| match ev.key().as_str() {
+                                                    "Enter" => {
+                                                        if let Some(pair) = renaming.get_untracked() {
+                                                            rename_action.dispatch(pair);
+                                                        }
+                                                    }
+                                                    "Escape" => renaming.set(None),
+                                                    _ => {}
+                                                }
+                                            />
+                                        </Show>
+                                        <button
+                                            class="text-gray-300 hover:text-primary transition-colors"
+                                            title="重命名"
+                                            on:click=move |_| renaming.set(Some((rename_from.clone(), rename_from.clone())))
+                                        >
+                                            <i class="fa fa-pencil"></i>
+                                        </button>
+                                        <button
+                                            class=if is_default { "text-yellow-500" } else { "text-gray-300 hover:text-yellow-500 transition-colors" }
+                                            title=if is_default { "取消默认" } else { "设为默认，打开页面时自动套用" }
+                                            on:click=move |_| {
+                                                default_action.dispatch((!is_default).then(|| default_name.clone()));
+                                            }
+                                        >
+                                            <i class=if is_default { "fa fa-star" } else { "fa fa-star-o" }></i>
+                                        </button>
+                                        <button
+                                            class="text-gray-300 hover:text-primary transition-colors"
+                                            title="复制分享链接"
+                                            on:click=move |_| { share_action.dispatch_local(share_name.clone()); }
+                                        >
+                                            <i class="fa fa-share-alt"></i>
+                                        </button>
+                                        <button
+                                            class="text-gray-300 hover:text-red-500 transition-colors"
+                                            title="删除"
+                                            on:click=move |_| { delete_action.dispatch(delete_name.clone()); }
+                                        >
+                                            <i class="fa fa-trash-o"></i>
+                                        </button>
+                                    </li>
+                                }
+                            }).collect_view()}
+                        </ul>
+                    }.into_any(),
+                })}
+            </Suspense>
+
+            {move || share_action.value().get().and_then(Result::ok).map(|(link, copied)| view! {
+                <div class="mt-3">
+                    <SharedLink link=link copied=copied />
+                </div>
+            })}
+
+            // 保存当前组合
+            <div class="flex gap-2 mt-3">
+                <input
+                    class="flex-1 min-w-0 p-2 border border-gray-200 rounded-lg focus:outline-none focus:ring-2 focus:ring-primary/50"
+                    placeholder="预设名，如 旁白-沉稳"
+                    maxlength=MAX_PRESET_NAME_CHARS.to_string()
+                    prop:value=move || new_name.get()
+                    on:input=move |ev| new_name.set(event_target_value(&ev))
+                />
+                <button
+                    class="px-3 py-2 rounded-lg border border-primary text-primary hover:bg-primary hover:text-white transition-colors disabled:opacity-50 disabled:cursor-not-allowed"
+                    title="把当前的声线和参数保存为预设，同名时覆盖"
+                    on:click=move |_| { save_action.dispatch(current_preset()); }
+                    disabled=move || {
+                        save_action.pending().get()
+                            || new_name.with(|n| n.trim().is_empty())
+                            || voice.with(String::is_empty)
+                    }
+                >
+                    <i class="fa fa-floppy-o mr-1"></i>
+                    "保存"
+                </button>
+            </div>
+            {move || action_error().map(|e| view! {
+                <p class="text-xs text-red-500 mt-2">{e.to_string()}</p>
+            })}
+        </div>
+    }
+    .into_any()
+}
@@ -0,0 +1,463 @@
+use crate::api::voicefilter::{upload_recording, AudioClip, MAX_UPLOAD_SECS, UPLOAD_FIELD};
+use crate::audio::{self, Pcm};
+use js_sys::{Array, Uint8Array};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use std::time::Duration;
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    AnalyserNode, AudioBuffer, AudioContext, Blob, BlobEvent, BlobPropertyBag, FormData,
+    MediaRecorder, MediaStream, MediaStreamConstraints, MediaStreamTrack, Url,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum RecorderState {
+    Idle,
+    /// 正在请求麦克风权限
+    Starting,
+    Recording,
+    /// 录音结束，正在解码
+    Processing,
+    Recorded,
+}
+
+/// 一次录音过程中需要保持存活的浏览器对象
+struct Session {
+    recorder: MediaRecorder,
+    stream: MediaStream,
+    context: AudioContext,
+    analyser: AnalyserNode,
+    timer: Option<IntervalHandle>,
+    started_at: f64,
+    _on_data: Closure<dyn FnMut(BlobEvent)>,
+    _on_stop: Closure<dyn FnMut()>,
+}
+
+impl Session {
+    /// 停止计时、释放麦克风和音频上下文
+    fn close(self) {
+        if let Some(timer) = self.timer {
+            timer.clear();
+        }
+        self.recorder.set_ondataavailable(None);
+        self.recorder.set_onstop(None);
+        if self.recorder.state() != web_sys::RecordingState::Inactive {
+            let _ = self.recorder.stop();
+        }
+        for track in self.stream.get_tracks().iter() {
+            if let Ok(track) = track.dyn_into::<MediaStreamTrack>() {
+                track.stop();
+            }
+        }
+        let _ = self.context.close();
+    }
+}
+
+/// 录音组件的全部状态，方便在异步回调之间传递
+#[derive(Clone, Copy)]
+struct Recorder {
+    state: RwSignal<RecorderState>,
+    /// 已录制时长 (秒)
+    elapsed: RwSignal<f32>,
+    /// 实时音量 (0.0 ~ 1.0)
+    level: RwSignal<f32>,
+    recording: RwSignal<Option<Pcm>>,
+    /// 保留区间 (起点, 终点)，单位秒
+    trim: RwSignal<(f32, f32)>,
+    error: RwSignal<Option<String>>,
+    session: StoredValue<Option<Session>, LocalStorage>,
+}
+
+impl Recorder {
+    fn new() -> Self {
+        Recorder {
+            state: RwSignal::new(RecorderState::Idle),
+            elapsed: RwSignal::new(0.0),
+            level: RwSignal::new(0.0),
+            recording: RwSignal::new(None),
+            trim: RwSignal::new((0.0, 0.0)),
+            error: RwSignal::new(None),
+            session: StoredValue::new_local(None),
+        }
+    }
+
+    async fn start(self) -> Result<(), JsValue> {
+        let window = web_sys::window().ok_or("window 不可用")?;
+        let constraints = MediaStreamConstraints::new();
+        constraints.set_audio(&JsValue::TRUE);
+        let stream: MediaStream = JsFuture::from(
+            window
+                .navigator()
+                .media_devices()?
+                .get_user_media_with_constraints(&constraints)?,
+        )
+        .await?
+        .dyn_into()?;
+
+        // 音量表：麦克风 → 分析节点，不接到扬声器
+        let context = AudioContext::new()?;
+        let analyser = context.create_analyser()?;
+        analyser.set_fft_size(1024);
+        context
+            .create_media_stream_source(&stream)?
+            .connect_with_audio_node(&analyser)?;
+
+        let recorder = MediaRecorder::new_with_media_stream(&stream)?;
+        let chunks = Array::new();
+        let on_data = Closure::<dyn FnMut(BlobEvent)>::new({
+            let chunks = chunks.clone();
+            move |ev: BlobEvent| {
+                if let Some(blob) = ev.data() {
+                    chunks.push(&blob);
+                }
+            }
+        });
+        let on_stop = Closure::<dyn FnMut()>::new(move || {
+            let chunks = chunks.clone();
+            spawn_local(async move {
+                if let Err(e) = self.finish(chunks).await {
+                    self.fail(&e);
+                }
+            });
+        });
+        recorder.set_ondataavailable(Some(on_data.as_ref().unchecked_ref()));
+        recorder.set_onstop(Some(on_stop.as_ref().unchecked_ref()));
+        recorder.start()?;
+
+        let timer = set_interval_with_handle(move || self.tick(), Duration::from_millis(50)).ok();
+        self.session.set_value(Some(Session {
+            recorder,
+            stream,
+            context,
+            analyser,
+            timer,
+            started_at: js_sys::Date::now(),
+            _on_data: on_data,
+            _on_stop: on_stop,
+        }));
+        self.elapsed.set(0.0);
+        self.state.set(RecorderState::Recording);
+        Ok(())
+    }
+
+    /// 定时刷新计时和音量，超过上限自动停止
+    fn tick(self) {
+        let Some((elapsed, rms)) = self.session.with_value(|session| {
+            session.as_ref().map(|s| {
+                let mut buffer = vec![0.0f32; s.analyser.fft_size() as usize];
+                s.analyser.get_float_time_domain_data(&mut buffer);
+                let rms = (buffer.iter().map(|v| v * v).sum::<f32>() / buffer.len() as f32).sqrt();
+                (((js_sys::Date::now() - s.started_at) / 1000.0) as f32, rms)
+            })
+        }) else {
+            return;
+        };
+        self.elapsed.set(elapsed);
+        // 语音的 RMS 通常在 0.3 以下，放大后更直观
+        self.level.set((rms * 4.0).min(1.0));
+        if elapsed >= MAX_UPLOAD_SECS {
+            self.stop();
+        }
+    }
+
+    fn stop(self) {
+        self.session.with_value(|session| {
+            if let Some(s) = session {
+                if let Some(timer) = s.timer {
+                    timer.clear();
+                }
+                let _ = s.recorder.stop();
+            }
+        });
+        self.level.set(0.0);
+        self.state.set(RecorderState::Processing);
+    }
+
+    /// 录音结束：用浏览器解码为 PCM (混为单声道)，默认保留全部
+    async fn finish(self, chunks: Array) -> Result<(), JsValue> {
+        let Some(session) = self.session.try_update_value(Option::take).flatten() else {
+            return Ok(());
+        };
+        let blob = Blob::new_with_blob_sequence(&chunks)?;
+        let bytes = JsFuture::from(blob.array_buffer()).await?;
+        let decoded = JsFuture::from(session.context.decode_audio_data(&bytes.dyn_into()?)?).await;
+        session.close();
+        let buffer: AudioBuffer = decoded?.dyn_into()?;
+
+        let channels = buffer.number_of_channels().max(1);
+        let mut samples = vec![0.0f32; buffer.length() as usize];
+        for ch in 0..channels {
+            for (mixed, s) in samples.iter_mut().zip(buffer.get_channel_data(ch)?) {
+                *mixed += s / channels as f32;
+            }
+        }
+        let pcm = Pcm {
+            sample_rate: buffer.sample_rate() as u32,
+            channels: 1,
+            samples,
+        };
+        self.trim.set((0.0, pcm.duration()));
+        self.recording.set(Some(pcm));
+        self.state.set(RecorderState::Recorded);
+        Ok(())
+    }
+
+    fn fail(self, e: &JsValue) {
+        if let Some(session) = self.session.try_update_value(Option::take).flatten() {
+            session.close();
+        }
+        self.error.set(Some(js_error(e)));
+        self.level.set(0.0);
+        self.state.set(RecorderState::Idle);
+    }
+
+    /// 丢弃当前录音，回到初始状态
+    fn reset(self) {
+        if let Some(session) = self.session.try_update_value(Option::take).flatten() {
+            session.close();
+        }
+        self.recording.set(None);
+        self.error.set(None);
+        self.elapsed.set(0.0);
+        self.level.set(0.0);
+        self.state.set(RecorderState::Idle);
+    }
+
+    /// 按保留区间裁剪后的录音
+    fn trimmed(self) -> Option<Pcm> {
+        let (start, end) = self.trim.get();
+        self.recording.with(|pcm| {
+            pcm.as_ref().map(|pcm| {
+                let sr = pcm.sample_rate as f32;
+                let len = pcm.samples.len();
+                let from = ((start * sr) as usize).min(len);
+                let to = ((end * sr) as usize).clamp(from, len);
+                Pcm {
+                    samples: pcm.samples[from..to].to_vec(),
+                    ..*pcm
+                }
+            })
+        })
+    }
+}
+
+fn js_error(e: &JsValue) -> String {
+    if let Some(e) = e.dyn_ref::<js_sys::Error>() {
+        return match String::from(e.name()).as_str() {
+            "NotAllowedError" => "没有麦克风权限，请在浏览器设置中允许访问".to_string(),
+            "NotFoundError" => "没有找到可用的麦克风".to_string(),
+            _ => String::from(e.message()),
+        };
+    }
+    e.as_string().unwrap_or_else(|| format!("{:?}", e))
+}
+
+fn wav_blob(pcm: &Pcm) -> Result<Blob, JsValue> {
+    let bytes = audio::encode_wav(pcm);
+    let options = BlobPropertyBag::new();
+    options.set_type("audio/wav");
+    Blob::new_with_u8_array_sequence_and_options(
+        &Array::of1(&Uint8Array::from(bytes.as_slice())),
+        &options,
+    )
+}
+
+fn format_secs(secs: f32) -> String {
+    let secs = secs.max(0.0);
+    format!("{:02}:{:04.1}", (secs / 60.0) as u32, secs % 60.0)
+}
+
+/// 麦克风录音：实时音量和计时，录完可裁剪、重录，确认后上传到片段库
+#[component]
+pub fn MicRecorder(
+    /// 上传成功后回调
+    on_recorded: Callback<AudioClip>,
+) -> impl IntoView {
+    let recorder = Recorder::new();
+    let Recorder {
+        state,
+        elapsed,
+        level,
+        recording,
+        trim,
+        error,
+        ..
+    } = recorder;
+    let duration = move || recording.with(|r| r.as_ref().map_or(0.0, Pcm::duration));
+
+    // 裁剪区间变化时重新生成试听地址
+    let preview_url = RwSignal::new(None::<String>);
+    Effect::new(move |_| {
+        let url = recorder
+            .trimmed()
+            .and_then(|pcm| wav_blob(&pcm).ok())
+            .and_then(|blob| Url::create_object_url_with_blob(&blob).ok());
+        if let Some(old) = preview_url.get_untracked() {
+            let _ = Url::revoke_object_url(&old);
+        }
+        preview_url.set(url);
+    });
+
+    on_cleanup(move || {
+        if let Some(session) = recorder.session.try_update_value(Option::take).flatten() {
+            session.close();
+        }
+        if let Some(url) = preview_url.get_untracked() {
+            let _ = Url::revoke_object_url(&url);
+        }
+    });
+
+    // FormData 不能跨线程，使用本地 Action
+    let upload_action = Action::new_local(move |_: &()| {
+        let form = untrack(|| recorder.trimmed())
+            .ok_or_else(|| JsValue::from_str("还没有录音"))
+            .and_then(|pcm| wav_blob(&pcm))
+            .and_then(|blob| {
+                let form = FormData::new()?;
+                form.append_with_blob_and_filename(UPLOAD_FIELD, &blob, "recording.wav")?;
+                Ok(form)
+            });
+        async move {
+            let form =
+                form.map_err(|e| -> ServerFnError { ServerFnError::ServerError(js_error(&e)) })?;
+            let clip = upload_recording(form.into()).await?;
+            on_recorded.run(clip);
+            Ok::<_, ServerFnError>(())
+        }
+    });
+
+    let start = move |_| {
+        error.set(None);
+        state.set(RecorderState::Starting);
+        spawn_local(async move {
+            if let Err(e) = recorder.start().await {
+                recorder.fail(&e);
+            }
+        });
+    };
+
+    view! {
+        <div class="border border-gray-100 rounded-xl p-4">
+            // 计时 + 音量
+            <div class="flex items-center gap-3 mb-3">
+                <span
+                    class="w-3 h-3 rounded-full"
+                    class=("bg-red-500", move || state.get() == RecorderState::Recording)
+                    class=("animate-pulse", move || state.get() == RecorderState::Recording)
+                    class=("bg-gray-300", move || state.get() != RecorderState::Recording)
+                ></span>
+                <span class="font-mono text-sm text-gray-600 w-16">
+                    {move || format_secs(elapsed.get())}
+                </span>
+                <div class="flex-1 h-2 bg-gray-100 rounded-full overflow-hidden">
+                    <div
+                        class="h-full bg-gradient-to-r from-green-400 via-yellow-400 to-red-500 transition-all duration-75"
+                        style:width=move || format!("{}%", level.get() * 100.0)
+                    ></div>
+                </div>
+            </div>
+
+            // 操作按钮
+            <div class="flex flex-wrap gap-2">
+                {move || match state.get() {
+                    RecorderState::Idle | RecorderState::Starting => view! {
+                        <button
+                            class="bg-red-500 hover:bg-red-600 text-white py-2 px-4 rounded-lg text-sm font-medium transition-colors flex items-center disabled:opacity-50"
+                            on:click=start
+                            disabled=move || state.get() == RecorderState::Starting
+                        >
+                            <i class="fa fa-microphone mr-2"></i>
+                            "开始录音"
+                        </button>
+                    }.into_any(),
+                    RecorderState::Recording => view! {
+                        <button
+                            class="bg-gray-700 hover:bg-gray-800 text-white py-2 px-4 rounded-lg text-sm font-medium transition-colors flex items-center"
+                            on:click=move |_| recorder.stop()
+                        >
+                            <i class="fa fa-stop mr-2"></i>
+                            "停止"
+                        </button>
+                    }.into_any(),
+                    RecorderState::Processing => view! {
+                        <span class="text-sm text-gray-500 py-2">
+                            <i class="fa fa-circle-o-notch fa-spin mr-2"></i>
+                            "正在处理录音..."
+                        </span>
+                    }.into_any(),
+                    RecorderState::Recorded => view! {
+                        <button
+                            class="border border-gray-200 text-gray-600 hover:border-primary hover:text-primary py-2 px-4 rounded-lg text-sm transition-colors flex items-center"
+                            on:click=move |_| recorder.reset()
+                        >
+                            <i class="fa fa-refresh mr-2"></i>
+                            "重录"
+                        </button>
+                        <button
+                            class="bg-primary hover:bg-primary-focus text-white py-2 px-4 rounded-lg text-sm font-medium transition-colors flex items-center disabled:opacity-50 disabled:cursor-not-allowed"
+                            on:click=move |_| { upload_action.dispatch_local(()); }
+                            disabled=move || upload_action.pending().get() || trim.with(|(s, e)| e <= s)
+                        >
+                            <i class="fa fa-check mr-2"></i>
+                            {move || if upload_action.pending().get() { "正在上传..." } else { "使用这段录音" }}
+                        </button>
+                    }.into_any(),
+                }}
+            </div>
+
+            // 裁剪与试听
+            <Show when=move || state.get() == RecorderState::Recorded>
+                <div class="mt-4 space-y-3 text-sm">
+                    <div>
+                        <div class="flex justify-between mb-1">
+                            <label>"起点"</label>
+                            <span class="text-primary">{move || format_secs(trim.get().0)}</span>
+                        </div>
+                        <input
+                            type="range"
+                            min="0"
+                            max=move || duration().to_string()
+                            step="0.1"
+                            class="w-full h-2 bg-gray-200 rounded-lg accent-primary"
+                            prop:value=move || trim.get().0.to_string()
+                            on:input=move |ev| {
+                                if let Ok(v) = event_target_value(&ev).parse::<f32>() {
+                                    trim.update(|(s, e)| *s = v.min(*e));
+                                }
+                            }
+                        />
+                    </div>
+                    <div>
+                        <div class="flex justify-between mb-1">
+                            <label>"终点"</label>
+                            <span class="text-primary">{move || format_secs(trim.get().1)}</span>
+                        </div>
+                        <input
+                            type="range"
+                            min="0"
+                            max=move || duration().to_string()
+                            step="0.1"
+                            class="w-full h-2 bg-gray-200 rounded-lg accent-primary"
+                            prop:value=move || trim.get().1.to_string()
+                            on:input=move |ev| {
+                                if let Ok(v) = event_target_value(&ev).parse::<f32>() {
+                                    trim.update(|(s, e)| *e = v.max(*s));
+                                }
+                            }
+                        />
+                    </div>
+                    {move || preview_url.get().map(|url| view! {
+                        <audio controls class="w-full" src=url></audio>
+                    })}
+                </div>
+            </Show>
+
+            {move || error.get().map(|e| view! { <p class="text-xs text-red-500 mt-2">{e}</p> })}
+            {move || upload_action.value().get().and_then(Result::err).map(|e| view! {
+                <p class="text-xs text-red-500 mt-2">{e.to_string()}</p>
+            })}
+        </div>
+    }
+    .into_any()
+}
@@ -0,0 +1,102 @@
+use crate::api::detect_language_segments;
+use crate::api::language::SynthesisLanguage;
+use crate::i18n::use_i18n;
+use crate::text::detect::LanguageSpan;
+use crate::text::normalize::NormalizeOptions;
+use leptos::prelude::*;
+
+/// 语言分段：预览自动识别的分段结果，并允许逐段修改语言
+#[component]
+pub fn LanguageSegmentsCard(
+    text: RwSignal<String>,
+    normalize: RwSignal<NormalizeOptions>,
+    /// 检测 (及用户修正) 后的分段，合成时作为语言修正提交
+    spans: RwSignal<Vec<LanguageSpan>>,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let detect_action = Action::new(move |_: &()| {
+        let (text, normalize) = (text.get_untracked(), normalize.get_untracked());
+        async move {
+            let detected = detect_language_segments(text, normalize).await?;
+            spans.set(detected);
+            Ok::<_, ServerFnError>(())
+        }
+    });
+    // 文本或规范化选项变化后，旧的分段不再对应，清空等待重新检测
+    Effect::watch(
+        move || {
+            text.track();
+            normalize.track();
+        },
+        move |_, _, _| spans.set(Vec::new()),
+        false,
+    );
+
+    view! {
+        <section class="bg-white rounded-xl p-6 shadow-soft transition-all duration-300 hover:shadow-hover">
+            <div class="flex justify-between items-center mb-2">
+                <h3 class="text-lg font-semibold flex items-center">
+                    <i class="fa fa-language text-primary mr-2"></i>
+                    {move || i18n.t().segments_title}
+                </h3>
+                <button
+                    class="text-sm text-primary hover:underline disabled:opacity-50"
+                    on:click=move |_| { detect_action.dispatch(()); }
+                    disabled=move || detect_action.pending().get() || text.with(|t| t.trim().is_empty())
+                >
+                    <i class="fa fa-search mr-1"></i>
+                    {move || i18n.t().detect_segments}
+                </button>
+            </div>
+            <p class="text-xs text-gray-400 mb-3">{move || i18n.t().segments_hint}</p>
+            {move || detect_action.value().get().and_then(Result::err).map(|e| view! {
+                <p class="text-xs text-red-500 mb-2">{e.to_string()}</p>
+            })}
+            <Show
+                when=move || spans.with(|s| !s.is_empty())
+                fallback=move || view! {
+                    <p class="text-xs text-gray-400 text-center py-2">{move || i18n.t().segments_empty}</p>
+                }
+            >
+                <div class="space-y-2">
+                    {move || {
+                        spans
+                            .get()
+                            .into_iter()
+                            .enumerate()
+                            .map(|(index, span)| view! {
+                                <div class="flex items-center gap-2">
+                                    <span class="flex-1 min-w-0 truncate text-sm text-gray-700" title=span.text.clone()>
+                                        {span.text.clone()}
+                                    </span>
+                                    <select
+                                        class="p-1 border border-gray-200 rounded-lg text-xs focus:outline-none focus:ring-2 focus:ring-primary/50"
+                                        on:change=move |ev| {
+                                            if let Some(language) = SynthesisLanguage::from_code(&event_target_value(&ev)) {
+                                                spans.update(|s| {
+                                                    if let Some(span) = s.get_mut(index) {
+                                                        span.language = language;
+                                                    }
+                                                });
+                                            }
+                                        }
+                                    >
+                                        {SynthesisLanguage::STANDARD
+                                            .into_iter()
+                                            .map(|option| view! {
+                                                <option value=option.code() selected=span.language == option>
+                                                    {move || option.native_name().unwrap_or(i18n.t().language_auto)}
+                                                </option>
+                                            })
+                                            .collect_view()}
+                                    </select>
+                                </div>
+                            })
+                            .collect_view()
+                    }}
+                </div>
+            </Show>
+        </section>
+    }
+    .into_any()
+}
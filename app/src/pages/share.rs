@@ -0,0 +1,248 @@
+use crate::api::share::{
+    create_share, embed_snippet, embed_url, get_shared_clip, oembed_url, share_url, ShareOptions,
+    SharedClipLookup, SharedClipView, EMBED_HEIGHT, MAX_SHARE_PASSWORD_CHARS, SHARE_EXPIRY_OPTIONS,
+};
+use crate::clips::clip_url;
+use crate::i18n::use_i18n;
+use crate::pages::waveform::WaveformPlayer;
+use crate::pages::{absolute_url, copy_link, copy_text, SharedLink};
+use leptos::prelude::*;
+use leptos_meta::{Link, Meta, Title};
+use leptos_router::hooks::use_params_map;
+
+/// 分享：把合成结果发布成公开链接，可选附带原文、有效期和访问密码
+#[component]
+pub fn SharePanel(
+    /// 片段库中合成结果的 ID
+    clip_id: String,
+    /// 合成时的文本和声线
+    text: String,
+    voice_id: String,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let options = RwSignal::new(ShareOptions {
+        include_text: true,
+        ..Default::default()
+    });
+
+    // 剪贴板 API 返回的 Future 不能跨线程，使用本地 Action
+    let share_action = Action::new_local(move |_: &()| {
+        let (clip_id, text, voice_id) = (clip_id.clone(), text.clone(), voice_id.clone());
+        let options = options.get_untracked();
+        async move {
+            let id = create_share(clip_id, text, voice_id, options).await?;
+            let (link, copied) = copy_link(&share_url(&id)).await;
+            let snippet = embed_snippet(&absolute_url(&embed_url(&id)), None, EMBED_HEIGHT);
+            Ok::<_, ServerFnError>((link, copied, snippet))
+        }
+    });
+
+    view! {
+        <details class="bg-white border border-green-200 rounded-lg text-sm">
+            <summary class="px-4 py-2 cursor-pointer text-green-700 select-none">
+                <i class="fa fa-share-alt mr-2"></i>
+                "分享链接"
+            </summary>
+            <div class="p-4 pt-2 space-y-3">
+                <label class="flex items-center cursor-pointer">
+                    <input
+                        type="checkbox"
+                        class="mr-2 accent-primary"
+                        prop:checked=move || options.with(|o| o.include_text)
+                        on:change=move |ev| options.update(|o| o.include_text = event_target_checked(&ev))
+                    />
+                    "在分享页展示原文"
+                </label>
+                <div class="grid grid-cols-2 gap-3">
+                    <select
+                        class="p-2 border border-gray-200 rounded-lg focus:outline-none focus:ring-2 focus:ring-primary/50"
+                        on:change=move |ev| {
+                            let index: usize = event_target_value(&ev).parse().unwrap_or_default();
+                            options.update(|o| o.expires_in_hours = SHARE_EXPIRY_OPTIONS[index].0);
+                        }
+                    >
+                        {SHARE_EXPIRY_OPTIONS
+                            .iter()
+                            .enumerate()
+                            .map(|(i, (_, label))| view! { <option value=i.to_string()>{move || label(i18n.t())}</option> })
+                            .collect_view()}
+                    </select>
+                    <input
+                        type="password"
+                        autocomplete="new-password"
+                        class="p-2 border border-gray-200 rounded-lg focus:outline-none focus:ring-2 focus:ring-primary/50"
+                        placeholder="访问密码 (可选)"
+                        maxlength=MAX_SHARE_PASSWORD_CHARS.to_string()
+                        prop:value=move || options.with(|o| o.password.clone())
+                        on:input=move |ev| options.update(|o| o.password = event_target_value(&ev))
+                    />
+                </div>
+                <button
+                    class="w-full bg-primary hover:bg-primary-focus text-white py-2 px-4 rounded-lg font-medium transition-all duration-300 flex items-center justify-center disabled:opacity-50 disabled:cursor-not-allowed"
+                    on:click=move |_| { share_action.dispatch_local(()); }
+                    disabled=move || share_action.pending().get()
+                >
+                    <i class="fa fa-link mr-2"></i>
+                    "生成分享链接"
+                </button>
+                {move || share_action.value().get().map(|result| match result {
+                    Ok((link, copied, snippet)) => view! {
+                        <SharedLink link=link copied=copied />
+                        <p class="text-xs text-gray-500 mt-3 mb-1">"嵌入代码："</p>
+                        <textarea
+                            readonly
+                            class="w-full h-16 p-1.5 border border-gray-200 rounded-lg text-xs text-gray-600 bg-gray-50 font-mono resize-none"
+                            prop:value=snippet.clone()
+                        ></textarea>
+                        <button
+                            class="text-xs text-primary hover:underline"
+                            on:click=move |_| {
+                                let snippet = snippet.clone();
+                                leptos::task::spawn_local(async move {
+                                    copy_text(&snippet).await;
+                                });
+                            }
+                        >
+                            <i class="fa fa-code mr-1"></i>
+                            "复制嵌入代码"
+                        </button>
+                    }.into_any(),
+                    Err(e) => view! { <p class="text-xs text-red-500">{e.to_string()}</p> }.into_any(),
+                })}
+            </div>
+        </details>
+    }
+    .into_any()
+}
+
+/// 公开的分享页 `/s/{id}`
+#[component]
+pub fn SharedClipPage() -> impl IntoView {
+    let params = use_params_map();
+    let id = move || params.with(|p| p.get("id").unwrap_or_default());
+    // 提交的访问密码
+    let password = RwSignal::new(None::<String>);
+    let password_input = RwSignal::new(String::new());
+    let shared = Resource::new(
+        move || (id(), password.get()),
+        |(id, password)| get_shared_clip(id, password),
+    );
+
+    view! {
+        <div class="min-h-screen bg-base-100 pb-12">
+            <div class="container mx-auto px-4 py-8 md:py-12 max-w-2xl">
+                <Suspense fallback=move || view! {
+                    <div class="text-center py-12 text-gray-400 animate-pulse">"加载中..."</div>
+                }>
+                    {move || shared.get().map(|result| match result {
+                        Ok(SharedClipLookup::Ready(clip)) => view! { <SharedClipCard clip=clip /> }.into_any(),
+                        Ok(SharedClipLookup::Locked { wrong_password }) => view! {
+                            <Title text="需要密码 - 耳朵"/>
+                            <section class="bg-white rounded-xl p-6 shadow-soft text-center">
+                                <i class="fa fa-lock text-3xl text-primary mb-4"></i>
+                                <p class="text-gray-600 mb-4">"这段音频设置了访问密码"</p>
+                                <form
+                                    class="flex gap-2"
+                                    on:submit=move |ev| {
+                                        ev.prevent_default();
+                                        password.set(Some(password_input.get_untracked()));
+                                    }
+                                >
+                                    <input
+                                        type="password"
+                                        class="flex-1 p-2 border border-gray-200 rounded-lg focus:outline-none focus:ring-2 focus:ring-primary/50"
+                                        placeholder="输入访问密码"
+                                        prop:value=move || password_input.get()
+                                        on:input=move |ev| password_input.set(event_target_value(&ev))
+                                    />
+                                    <button
+                                        type="submit"
+                                        class="bg-primary hover:bg-primary-focus text-white px-4 rounded-lg transition-colors"
+                                    >
+                                        "查看"
+                                    </button>
+                                </form>
+                                {wrong_password.then(|| view! {
+                                    <p class="text-xs text-red-500 mt-2">"密码不正确"</p>
+                                })}
+                            </section>
+                        }.into_any(),
+                        Err(e) => view! {
+                            <Title text="分享不可用 - 耳朵"/>
+                            <section class="bg-white rounded-xl p-6 shadow-soft text-center text-gray-500">
+                                <i class="fa fa-chain-broken text-3xl text-gray-300 mb-4"></i>
+                                <p>{e.to_string()}</p>
+                            </section>
+                        }.into_any(),
+                    })}
+                </Suspense>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn SharedClipCard(clip: SharedClipView) -> impl IntoView {
+    let title = format!("{} 的语音 - 耳朵", clip.voice_name);
+    let description = clip
+        .text
+        .as_ref()
+        .map(|t| t.chars().take(100).collect::<String>())
+        .unwrap_or_else(|| format!("一段由「{}」朗读的语音", clip.voice_name));
+    let expiry = clip.expires_in_hours.map(|hours| {
+        if hours > 48 {
+            format!("{} 天后过期", hours.div_ceil(24))
+        } else {
+            format!("{} 小时后过期", hours)
+        }
+    });
+
+    view! {
+        <Title text=title.clone()/>
+        <Meta name="description" content=description.clone()/>
+        <Meta property="og:type" content="music.song"/>
+        <Meta property="og:title" content=title/>
+        <Meta property="og:description" content=description/>
+        <Meta property="og:url" content=clip.page_url.clone()/>
+        <Meta property="og:audio" content=clip.audio_url.clone()/>
+        <Meta property="og:audio:secure_url" content=clip.audio_url.clone()/>
+        <Meta property="og:audio:type" content=clip.content_type.clone()/>
+        <Link
+            rel="alternate"
+            type_="application/json+oembed"
+            href=oembed_url(&clip.page_url)
+        />
+
+        <section class="bg-white rounded-xl p-6 shadow-soft">
+            <div class="flex items-center justify-between mb-4">
+                <h2 class="text-lg font-semibold flex items-center">
+                    <i class="fa fa-headphones text-primary mr-2"></i>
+                    {clip.voice_name.clone()}
+                </h2>
+                {expiry.map(|text| view! {
+                    <span class="text-xs text-gray-400">
+                        <i class="fa fa-clock-o mr-1"></i>
+                        {text}
+                    </span>
+                })}
+            </div>
+            <WaveformPlayer
+                src=clip_url(&clip.clip_id)
+                duration=clip.duration
+                peaks=clip.peaks
+                clip_id=clip.clip_id.clone()
+            />
+            {clip.text.map(|text| view! {
+                <p class="mt-4 text-gray-700 whitespace-pre-wrap leading-relaxed">{text}</p>
+            })}
+            <a
+                href=clip_url(&clip.clip_id)
+                download="shared_audio"
+                class="mt-4 bg-white border border-green-200 text-green-700 hover:bg-green-100 px-4 py-2 rounded-lg text-sm flex items-center justify-center transition-colors"
+            >
+                <i class="fa fa-download mr-2"></i>
+                "下载音频"
+            </a>
+        </section>
+    }
+}
@@ -0,0 +1,77 @@
+use crate::api::TimedText;
+use crate::i18n::use_i18n;
+use crate::text::subtitle::{
+    build_cues, render, SubtitleFormat, DEFAULT_LINE_WIDTH, MAX_LINE_WIDTH, MIN_LINE_WIDTH,
+};
+use leptos::prelude::*;
+
+/// 把字幕文本编码为 Data URI，便于直接用 `<a download>` 下载
+fn data_uri(mime: &str, text: &str) -> String {
+    let mut uri = format!("data:{};charset=utf-8,", mime);
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-_.~".contains(&byte) {
+            uri.push(byte as char);
+        } else {
+            uri.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    uri
+}
+
+/// 字幕下载：按句子时间戳生成 SRT / WebVTT，可调每行最大宽度
+#[component]
+pub fn SubtitleDownloads(sentences: Vec<TimedText>) -> impl IntoView {
+    let i18n = use_i18n();
+    let line_width = RwSignal::new(DEFAULT_LINE_WIDTH);
+    let sentences = StoredValue::new(sentences);
+
+    let href = move |format: SubtitleFormat| {
+        let width = line_width.get();
+        sentences.with_value(|sentences| {
+            let cues = build_cues(
+                sentences.iter().map(|s| (s.text.as_str(), s.start, s.end)),
+                width,
+            );
+            data_uri(format.mime_type(), &render(&cues, format))
+        })
+    };
+
+    view! {
+        <div class="flex flex-wrap items-center gap-3 text-sm">
+            <span class="text-gray-600">
+                <i class="fa fa-cc mr-1"></i>
+                {move || i18n.t().subtitles_title}
+            </span>
+            <label class="flex items-center text-xs text-gray-500">
+                {move || i18n.t().subtitle_line_width}
+                <input
+                    type="number"
+                    min=MIN_LINE_WIDTH.to_string()
+                    max=MAX_LINE_WIDTH.to_string()
+                    class="ml-2 w-16 p-1 border border-gray-200 rounded text-center focus:outline-none focus:ring-2 focus:ring-primary/50"
+                    title=move || i18n.t().subtitle_width_hint
+                    prop:value=move || line_width.get().to_string()
+                    on:change=move |ev| {
+                        if let Ok(v) = event_target_value(&ev).parse::<usize>() {
+                            line_width.set(v.clamp(MIN_LINE_WIDTH, MAX_LINE_WIDTH));
+                        }
+                    }
+                />
+            </label>
+            {[SubtitleFormat::Srt, SubtitleFormat::WebVtt]
+                .into_iter()
+                .map(|format| view! {
+                    <a
+                        href=move || href(format)
+                        download=format!("tts_audio.{}", format.extension())
+                        class="bg-white border border-green-200 text-green-700 hover:bg-green-100 px-3 py-1 rounded-lg text-xs transition-colors"
+                    >
+                        <i class="fa fa-download mr-1"></i>
+                        {format.extension().to_uppercase()}
+                    </a>
+                })
+                .collect_view()}
+        </div>
+    }
+    .into_any()
+}
@@ -0,0 +1,740 @@
+use crate::api::history::{list_recent_clips, HistoryEntry};
+use crate::api::timeline::{
+    delete_timeline_project, list_timeline_projects, load_timeline_project, render_timeline,
+    save_timeline_project, RenderFormat, TimelineItem, TimelineProject, TimelineTrack,
+    MAX_PROJECT_NAME_CHARS,
+};
+use crate::api::{generate_audio, get_voices};
+use crate::clips::clip_url;
+use crate::i18n::use_i18n;
+use crate::pages::homepage::{GenerateParams, VoiceParams};
+use crate::pages::waveform::WaveformPlayer;
+use leptos::prelude::*;
+
+/// 裁剪后片段的最短长度 (秒)
+const MIN_ITEM_SECS: f32 = 0.1;
+
+/// 轨道行高 (像素)
+const TRACK_HEIGHT: f32 = 56.0;
+
+/// 裁剪手柄宽度 (像素)
+const HANDLE_PX: f32 = 6.0;
+
+#[derive(Clone, Copy, PartialEq)]
+enum DragMode {
+    Move,
+    TrimStart,
+    TrimEnd,
+}
+
+/// 拖拽开始时的状态，移动量都相对于按下鼠标时计算
+#[derive(Clone)]
+struct Drag {
+    track: usize,
+    item: usize,
+    mode: DragMode,
+    origin_x: i32,
+    original: TimelineItem,
+}
+
+/// 按拖拽模式把水平位移 (秒) 应用到片段上
+fn apply_drag(item: &mut TimelineItem, original: &TimelineItem, mode: DragMode, delta: f32) {
+    match mode {
+        DragMode::Move => item.start = (original.start + delta).max(0.0),
+        DragMode::TrimStart => {
+            // 左边缘不能越过素材开头、时间轴起点，也不能把片段裁没
+            let delta = delta
+                .max(-original.offset)
+                .max(-original.start)
+                .min(original.length - MIN_ITEM_SECS);
+            item.start = original.start + delta;
+            item.offset = original.offset + delta;
+            item.length = original.length - delta;
+        }
+        DragMode::TrimEnd => {
+            item.length = (original.length + delta)
+                .min(original.source_duration - original.offset)
+                .max(MIN_ITEM_SECS);
+        }
+    }
+    item.fade_in = item.fade_in.min(item.length);
+    item.fade_out = item.fade_out.min(item.length);
+}
+
+/// 时间轴：把生成的片段摆到多条轨道上，裁剪、淡入淡出后导出成一个文件
+#[component]
+pub fn Timeline() -> impl IntoView {
+    let i18n = use_i18n();
+
+    // 状态
+    let project = RwSignal::new(TimelineProject::new(untrack(|| i18n.t())));
+    let selected = RwSignal::new(None::<(usize, usize)>);
+    // 从素材库添加片段时放到哪条轨道
+    let target_track = RwSignal::new(0usize);
+    let px_per_sec = RwSignal::new(40.0_f32);
+    let drag = StoredValue::new(None::<Drag>);
+
+    // --- 素材库 ---
+    let quick_text = RwSignal::new(String::new());
+    let quick_voice = RwSignal::new(String::new());
+    let voices = Resource::new(|| (), |_| get_voices());
+    let generate_action = Action::new(move |_: &()| {
+        let params = GenerateParams::new(
+            quick_text.get_untracked(),
+            quick_voice.get_untracked(),
+            VoiceParams::default(),
+        );
+        async move { generate_audio(params).await }
+    });
+    let recent = Resource::new(
+        move || generate_action.version().get(),
+        |_| list_recent_clips(),
+    );
+
+    let add_to_track = move |entry: HistoryEntry| {
+        project.update(|p| {
+            let index = target_track
+                .get_untracked()
+                .min(p.tracks.len().saturating_sub(1));
+            let Some(track) = p.tracks.get_mut(index) else {
+                return;
+            };
+            let start = track.end();
+            track.items.push(TimelineItem::new(
+                entry.clip_id,
+                entry.text,
+                entry.duration,
+                start,
+            ));
+            selected.set(Some((index, track.items.len() - 1)));
+        });
+    };
+
+    // --- 工程 ---
+    let save_action = Action::new(move |_: &()| {
+        let current = project.get_untracked();
+        async move {
+            let saved = save_timeline_project(current).await?;
+            project.update(|p| {
+                p.id = saved.id.clone();
+                p.updated_at = saved.updated_at;
+            });
+            Ok::<_, ServerFnError>(())
+        }
+    });
+    let load_action = Action::new(move |id: &String| {
+        let id = id.clone();
+        async move {
+            let loaded = load_timeline_project(id).await?;
+            selected.set(None);
+            target_track.set(0);
+            project.set(loaded);
+            Ok::<_, ServerFnError>(())
+        }
+    });
+    let delete_action = Action::new(move |id: &String| {
+        let id = id.clone();
+        async move {
+            delete_timeline_project(id.clone()).await?;
+            if project.with_untracked(|p| p.id.as_deref() == Some(id.as_str())) {
+                project.update(|p| p.id = None);
+            }
+            Ok::<_, ServerFnError>(())
+        }
+    });
+    let projects = Resource::new(
+        move || (save_action.version().get(), delete_action.version().get()),
+        |_| list_timeline_projects(),
+    );
+    let render_action = Action::new(move |format: &RenderFormat| {
+        let current = project.get_untracked();
+        let format = *format;
+        async move {
+            render_timeline(current, format)
+                .await
+                .map(|clip| (clip, format))
+        }
+    });
+
+    let project_error = move || {
+        [
+            save_action.value().get(),
+            load_action.value().get(),
+            delete_action.value().get(),
+        ]
+        .into_iter()
+        .flatten()
+        .find_map(Result::err)
+    };
+
+    // --- 拖拽 ---
+    let on_mouse_move = move |ev: leptos::ev::MouseEvent| {
+        let Some(d) = drag.get_value() else {
+            return;
+        };
+        let delta = (ev.client_x() - d.origin_x) as f32 / px_per_sec.get_untracked();
+        project.update(|p| {
+            if let Some(item) = p
+                .tracks
+                .get_mut(d.track)
+                .and_then(|t| t.items.get_mut(d.item))
+            {
+                apply_drag(item, &d.original, d.mode, delta);
+            }
+        });
+    };
+    let end_drag = move |_: leptos::ev::MouseEvent| drag.set_value(None);
+    let start_drag =
+        move |ev: leptos::ev::MouseEvent, track: usize, item: usize, mode: DragMode| {
+            ev.prevent_default();
+            ev.stop_propagation();
+            let Some(original) = project
+                .with_untracked(|p| p.tracks.get(track).and_then(|t| t.items.get(item)).cloned())
+            else {
+                return;
+            };
+            selected.set(Some((track, item)));
+            drag.set_value(Some(Drag {
+                track,
+                item,
+                mode,
+                origin_x: ev.client_x(),
+                original,
+            }));
+        };
+
+    // 修改选中的片段
+    let update_selected = move |f: &dyn Fn(&mut TimelineItem)| {
+        let Some((t, i)) = selected.get_untracked() else {
+            return;
+        };
+        project.update(|p| {
+            if let Some(item) = p.tracks.get_mut(t).and_then(|t| t.items.get_mut(i)) {
+                f(item);
+            }
+        });
+    };
+    let number_input = move |label: &'static str,
+                             step: &'static str,
+                             get: fn(&TimelineItem) -> f32,
+                             set: fn(&mut TimelineItem, f32)| {
+        view! {
+            <label class="block">
+                <span class="text-xs text-gray-500">{label}</span>
+                <input
+                    type="number"
+                    step=step
+                    class="w-full p-1.5 border border-gray-200 rounded-lg text-sm focus:outline-none focus:ring-2 focus:ring-primary/50"
+                    prop:value=move || {
+                        selected
+                            .get()
+                            .and_then(|(t, i)| {
+                                project.with(|p| p.tracks.get(t)?.items.get(i).map(|item| format!("{:.2}", get(item))))
+                            })
+                            .unwrap_or_default()
+                    }
+                    on:change=move |ev| {
+                        if let Ok(v) = event_target_value(&ev).parse::<f32>() {
+                            update_selected(&|item| set(item, v));
+                        }
+                    }
+                />
+            </label>
+        }
+    };
+
+    let ruler_marks = move || {
+        let pps = px_per_sec.get();
+        // 刻度间隔随缩放变化，保持大约 80 像素一格
+        let step = [1.0_f32, 2.0, 5.0, 10.0, 30.0, 60.0]
+            .into_iter()
+            .find(|s| s * pps >= 80.0)
+            .unwrap_or(60.0);
+        let total = project.with(|p| p.duration()) + 10.0;
+        (0..=(total / step) as usize)
+            .map(|n| {
+                let secs = n as f32 * step;
+                view! {
+                    <span
+                        class="absolute top-0 h-full border-l border-gray-200 pl-1 text-[10px] text-gray-400"
+                        style=format!("left: {}px", secs * pps)
+                    >
+                        {format!("{}:{:02}", secs as u32 / 60, secs as u32 % 60)}
+                    </span>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <div class="min-h-screen bg-base-100 pb-12">
+            <div class="container mx-auto px-4 py-8 md:py-12 max-w-6xl">
+
+                <section class="text-center mb-12">
+                    <h2 class="text-[clamp(1.8rem,4vw,2.5rem)] font-bold mb-4 text-shadow text-dark">
+                        "时间轴"
+                    </h2>
+                    <p class="text-gray-600 max-w-2xl mx-auto">
+                        "把多段合成语音和音效摆到多条轨道上，裁剪、淡入淡出后导出成一个文件"
+                    </p>
+                </section>
+
+                // 以下各区块分别擦除视图类型 (`into_any`)，整页的类型嵌套过深会超出编译器的递归上限
+                // --- 工程 ---
+                {view! {
+                    <section class="bg-white rounded-xl p-4 shadow-soft mb-8 flex flex-wrap items-center gap-3 text-sm">
+                        <input
+                            class="flex-1 min-w-[10rem] p-2 border border-gray-200 rounded-lg focus:outline-none focus:ring-2 focus:ring-primary/50"
+                            maxlength=MAX_PROJECT_NAME_CHARS.to_string()
+                            prop:value=move || project.with(|p| p.name.clone())
+                            on:input=move |ev| project.update(|p| p.name = event_target_value(&ev))
+                        />
+                        <Suspense fallback=|| ()>
+                            {move || projects.get().and_then(Result::ok).map(|list| view! {
+                                <select
+                                    class="p-2 border border-gray-200 rounded-lg focus:outline-none focus:ring-2 focus:ring-primary/50"
+                                    on:change=move |ev| {
+                                        let id = event_target_value(&ev);
+                                        if !id.is_empty() {
+                                            load_action.dispatch(id);
+                                        }
+                                    }
+                                >
+                                    <option value="">"打开已保存的工程..."</option>
+                                    {list.into_iter().map(|p| {
+                                        let id = p.id.clone();
+                                        view! {
+                                            <option
+                                                value=p.id
+                                                selected=move || project.with(|cur| cur.id.as_deref() == Some(id.as_str()))
+                                            >
+                                                {p.name}
+                                            </option>
+                                        }
+                                    }).collect_view()}
+                                </select>
+                            })}
+                        </Suspense>
+                        <button
+                            class="px-4 py-2 rounded-lg border border-gray-200 hover:bg-gray-50 transition-colors"
+                            on:click=move |_| {
+                                selected.set(None);
+                                target_track.set(0);
+                                project.set(TimelineProject::new(i18n.t()));
+                            }
+                        >
+                            <i class="fa fa-file-o mr-1"></i>
+                            "新建"
+                        </button>
+                        <button
+                            class="px-4 py-2 rounded-lg bg-primary hover:bg-primary-focus text-white transition-colors disabled:opacity-50"
+                            on:click=move |_| { save_action.dispatch(()); }
+                            disabled=move || save_action.pending().get()
+                        >
+                            <i class="fa fa-floppy-o mr-1"></i>
+                            "保存"
+                        </button>
+                        <button
+                            class="px-4 py-2 rounded-lg text-gray-400 hover:text-red-500 transition-colors disabled:opacity-50"
+                            title="删除工程"
+                            on:click=move |_| {
+                                if let Some(id) = project.with_untracked(|p| p.id.clone()) {
+                                    delete_action.dispatch(id);
+                                }
+                            }
+                            disabled=move || project.with(|p| p.id.is_none()) || delete_action.pending().get()
+                        >
+                            <i class="fa fa-trash-o"></i>
+                        </button>
+                        {move || project_error().map(|e| view! {
+                            <p class="w-full text-xs text-red-500">{e.to_string()}</p>
+                        })}
+                    </section>
+                }
+                .into_any()}
+
+                <div class="grid grid-cols-1 lg:grid-cols-4 gap-8">
+
+                    // --- 左侧栏 (素材库) ---
+                    {view! {
+                        <div class="lg:col-span-1 space-y-8">
+                            <section class="bg-white rounded-xl p-4 shadow-soft text-sm">
+                                <h3 class="text-base font-semibold mb-3 flex items-center">
+                                    <i class="fa fa-bolt text-primary mr-2"></i>
+                                    "快速生成"
+                                </h3>
+                                <textarea
+                                    class="w-full h-20 p-2 border border-gray-200 rounded-lg resize-none focus:outline-none focus:ring-2 focus:ring-primary/50"
+                                    placeholder="输入一句要合成的话"
+                                    prop:value=move || quick_text.get()
+                                    on:input=move |ev| quick_text.set(event_target_value(&ev))
+                                ></textarea>
+                                <Suspense fallback=|| ()>
+                                    {move || voices.get().and_then(Result::ok).map(|list| {
+                                        if quick_voice.with_untracked(String::is_empty) {
+                                            if let Some(first) = list.first() {
+                                                quick_voice.set(first.id.clone());
+                                            }
+                                        }
+                                        view! {
+                                            <select
+                                                class="w-full mt-2 p-2 border border-gray-200 rounded-lg focus:outline-none focus:ring-2 focus:ring-primary/50"
+                                                on:change=move |ev| quick_voice.set(event_target_value(&ev))
+                                            >
+                                                {list.into_iter().map(|v| {
+                                                    let id = v.id.clone();
+                                                    view! {
+                                                        <option value=v.id selected=move || quick_voice.get() == id>{v.name}</option>
+                                                    }
+                                                }).collect_view()}
+                                            </select>
+                                        }
+                                    })}
+                                </Suspense>
+                                <button
+                                    class="w-full mt-2 bg-primary hover:bg-primary-focus text-white py-2 rounded-lg transition-colors disabled:opacity-50 disabled:cursor-not-allowed"
+                                    on:click=move |_| { generate_action.dispatch(()); }
+                                    disabled=move || generate_action.pending().get() || quick_text.with(|t| t.trim().is_empty())
+                                >
+                                    {move || if generate_action.pending().get() {
+                                        view! { <> <i class="fa fa-circle-o-notch fa-spin mr-2"></i> "生成中..." </> }.into_view()
+                                    } else {
+                                        view! { <> <i class="fa fa-magic mr-2"></i> "生成到素材库" </> }.into_view()
+                                    }}
+                                </button>
+                                {move || generate_action.value().get().and_then(Result::err).map(|e| view! {
+                                    <p class="text-xs text-red-500 mt-2">{e.to_string()}</p>
+                                })}
+                            </section>
+
+                            <section class="bg-white rounded-xl p-4 shadow-soft text-sm">
+                                <h3 class="text-base font-semibold mb-3 flex items-center">
+                                    <i class="fa fa-history text-primary mr-2"></i>
+                                    "最近生成"
+                                </h3>
+                                <Suspense fallback=move || view! {
+                                    <div class="text-center py-4 text-gray-400 animate-pulse">"加载中..."</div>
+                                }>
+                                    {move || recent.get().map(|result| match result {
+                                        Err(e) => view! {
+                                            <p class="text-red-500">{format!("加载失败: {}", e)}</p>
+                                        }.into_any(),
+                                        Ok(list) if list.is_empty() => view! {
+                                            <p class="text-center py-4 text-gray-400">"暂无，先生成几段语音吧"</p>
+                                        }.into_any(),
+                                        Ok(list) => view! {
+                                            <ul class="space-y-2 max-h-96 overflow-y-auto">
+                                                {list.into_iter().filter(|e| e.duration > 0.0).map(|entry| {
+                                                    let label = format!("{} · {:.1}s", entry.text, entry.duration);
+                                                    view! {
+                                                        <li class="flex items-center justify-between gap-2 p-2 border border-gray-100 rounded-lg">
+                                                            <span class="truncate text-gray-600" title=label.clone()>{label.clone()}</span>
+                                                            <button
+                                                                class="shrink-0 text-primary hover:text-primary-focus"
+                                                                title="添加到轨道"
+                                                                on:click=move |_| add_to_track(entry.clone())
+                                                            >
+                                                                <i class="fa fa-plus-circle"></i>
+                                                            </button>
+                                                        </li>
+                                                    }
+                                                }).collect_view()}
+                                            </ul>
+                                        }.into_any(),
+                                    })}
+                                </Suspense>
+                            </section>
+                        </div>
+                    }
+                    .into_any()}
+
+                    // --- 右侧栏 (轨道与检查器) ---
+                    <div class="lg:col-span-3 space-y-8">
+                        {view! {
+                            <section class="bg-white rounded-xl p-4 shadow-soft text-sm">
+                                <div class="flex items-center justify-between mb-3">
+                                    <h3 class="text-base font-semibold flex items-center">
+                                        <i class="fa fa-film text-primary mr-2"></i>
+                                        "轨道"
+                                    </h3>
+                                    <div class="flex items-center gap-2 text-gray-500">
+                                        <i class="fa fa-search-minus"></i>
+                                        <input
+                                            type="range"
+                                            min="10"
+                                            max="200"
+                                            step="5"
+                                            class="w-32 h-2 bg-gray-200 rounded-lg accent-primary"
+                                            prop:value=move || px_per_sec.get().to_string()
+                                            on:input=move |ev| {
+                                                if let Ok(v) = event_target_value(&ev).parse() {
+                                                    px_per_sec.set(v);
+                                                }
+                                            }
+                                        />
+                                        <i class="fa fa-search-plus"></i>
+                                    </div>
+                                </div>
+
+                                <div class="flex">
+                                    // 轨道头
+                                    <div class="w-36 shrink-0 pt-5">
+                                        {move || (0..project.with(|p| p.tracks.len())).map(move |t| {
+                                            view! {
+                                                <div
+                                                    class=move || if target_track.get() == t {
+                                                        "flex flex-col justify-center px-2 border-b border-gray-100 cursor-pointer bg-primary/10"
+                                                    } else {
+                                                        "flex flex-col justify-center px-2 border-b border-gray-100 cursor-pointer"
+                                                    }
+                                                    style=format!("height: {}px", TRACK_HEIGHT)
+                                                    on:click=move |_| target_track.set(t)
+                                                >
+                                                    <div class="flex items-center justify-between">
+                                                        <input
+                                                            class="w-20 bg-transparent text-xs font-medium focus:outline-none"
+                                                            prop:value=move || project.with(|p| p.tracks.get(t).map(|t| t.name.clone()).unwrap_or_default())
+                                                            on:input=move |ev| project.update(|p| {
+                                                                if let Some(track) = p.tracks.get_mut(t) {
+                                                                    track.name = event_target_value(&ev);
+                                                                }
+                                                            })
+                                                        />
+                                                        <button
+                                                            class="text-xs"
+                                                            title="静音"
+                                                            class:text-red-500=move || project.with(|p| p.tracks.get(t).is_some_and(|t| t.muted))
+                                                            class:text-gray-400=move || project.with(|p| p.tracks.get(t).is_some_and(|t| !t.muted))
+                                                            on:click=move |_| project.update(|p| {
+                                                                if let Some(track) = p.tracks.get_mut(t) {
+                                                                    track.muted = !track.muted;
+                                                                }
+                                                            })
+                                                        >
+                                                            <i class="fa fa-volume-off"></i>
+                                                        </button>
+                                                    </div>
+                                                    <input
+                                                        type="range"
+                                                        min="-30"
+                                                        max="6"
+                                                        step="1"
+                                                        title="轨道音量 (dB)"
+                                                        class="w-full h-1 mt-1 bg-gray-200 rounded-lg accent-primary"
+                                                        prop:value=move || project.with(|p| p.tracks.get(t).map(|t| t.gain_db).unwrap_or_default()).to_string()
+                                                        on:input=move |ev| {
+                                                            if let Ok(v) = event_target_value(&ev).parse() {
+                                                                project.update(|p| {
+                                                                    if let Some(track) = p.tracks.get_mut(t) {
+                                                                        track.gain_db = v;
+                                                                    }
+                                                                });
+                                                            }
+                                                        }
+                                                    />
+                                                </div>
+                                            }.into_any()
+                                        }).collect_view()}
+                                        <button
+                                            class="w-full mt-2 text-xs text-gray-400 hover:text-primary transition-colors"
+                                            on:click=move |_| project.update(|p| {
+                                                let name = format!("轨道 {}", p.tracks.len() + 1);
+                                                p.tracks.push(TimelineTrack::new(name));
+                                            })
+                                        >
+                                            <i class="fa fa-plus mr-1"></i>
+                                            "添加轨道"
+                                        </button>
+                                    </div>
+
+                                    // 轨道内容，鼠标移动和松开在整个区域上处理，拖出片段也能继续
+                                    <div
+                                        class="flex-1 overflow-x-auto select-none"
+                                        on:mousemove=on_mouse_move
+                                        on:mouseup=end_drag
+                                        on:mouseleave=end_drag
+                                    >
+                                        <div
+                                            class="relative"
+                                            style=move || format!(
+                                                "width: {}px",
+                                                (project.with(|p| p.duration()) + 10.0) * px_per_sec.get(),
+                                            )
+                                        >
+                                            <div class="relative h-5">{ruler_marks}</div>
+                                            {move || (0..project.with(|p| p.tracks.len())).map(move |t| {
+                                                view! {
+                                                    <div
+                                                        class="relative border-b border-gray-100 bg-gray-50/50"
+                                                        class:opacity-40=move || project.with(|p| p.tracks.get(t).is_some_and(|t| t.muted))
+                                                        style=format!("height: {}px", TRACK_HEIGHT)
+                                                        on:mousedown=move |_| {
+                                                            target_track.set(t);
+                                                            selected.set(None);
+                                                        }
+                                                    >
+                                                        {move || {
+                                                            let pps = px_per_sec.get();
+                                                            project.with(|p| p.tracks.get(t).map(|track| track.items.clone()).unwrap_or_default())
+                                                                .into_iter()
+                                                                .enumerate()
+                                                                .map(|(i, item)| view! {
+                                                                    <div
+                                                                        class="absolute top-1 bottom-1 rounded-md bg-primary/20 border border-primary/40 text-[11px] text-dark overflow-hidden cursor-move"
+                                                                        class:ring-2=move || selected.get() == Some((t, i))
+                                                                        class:ring-primary=move || selected.get() == Some((t, i))
+                                                                        style=format!("left: {}px; width: {}px", item.start * pps, (item.length * pps).max(2.0))
+                                                                        title=item.label.clone()
+                                                                        on:mousedown=move |ev| start_drag(ev, t, i, DragMode::Move)
+                                                                    >
+                                                                        <div
+                                                                            class="absolute left-0 top-0 h-full cursor-ew-resize bg-primary/40"
+                                                                            style=format!("width: {}px", HANDLE_PX)
+                                                                            on:mousedown=move |ev| start_drag(ev, t, i, DragMode::TrimStart)
+                                                                        ></div>
+                                                                        <span class="block px-2 py-1 truncate pointer-events-none">{item.label.clone()}</span>
+                                                                        <div
+                                                                            class="absolute right-0 top-0 h-full cursor-ew-resize bg-primary/40"
+                                                                            style=format!("width: {}px", HANDLE_PX)
+                                                                            on:mousedown=move |ev| start_drag(ev, t, i, DragMode::TrimEnd)
+                                                                        ></div>
+                                                                    </div>
+                                                                })
+                                                                .collect_view()
+                                                        }}
+                                                    </div>
+                                                }.into_any()
+                                            }).collect_view()}
+                                        </div>
+                                    </div>
+                                </div>
+                                <p class="text-xs text-gray-400 mt-3">
+                                    "拖动片段调整位置，拖动左右边缘裁剪；点击轨道头选择从素材库添加到哪条轨道"
+                                </p>
+                            </section>
+                        }
+                        .into_any()}
+
+                        // --- 检查器 ---
+                        {move || selected.get().map(|(t, i)| view! {
+                            <section class="bg-white rounded-xl p-4 shadow-soft text-sm">
+                                <div class="flex items-center justify-between mb-3">
+                                    <h3 class="text-base font-semibold flex items-center">
+                                        <i class="fa fa-sliders text-primary mr-2"></i>
+                                        "片段属性"
+                                    </h3>
+                                    <button
+                                        class="text-gray-400 hover:text-red-500 transition-colors"
+                                        title="删除片段"
+                                        on:click=move |_| {
+                                            selected.set(None);
+                                            project.update(|p| {
+                                                if let Some(track) = p.tracks.get_mut(t) {
+                                                    if i < track.items.len() {
+                                                        track.items.remove(i);
+                                                    }
+                                                }
+                                            });
+                                        }
+                                    >
+                                        <i class="fa fa-trash-o"></i>
+                                    </button>
+                                </div>
+                                <div class="grid grid-cols-2 md:grid-cols-3 gap-3">
+                                    {number_input("起点 (秒)", "0.1", |item| item.start, |item, v| item.start = v.max(0.0))}
+                                    {number_input("素材偏移 (秒)", "0.1", |item| item.offset, |item, v| {
+                                        item.offset = v.min(item.source_duration - MIN_ITEM_SECS).max(0.0);
+                                        item.length = item.length.min(item.source_duration - item.offset);
+                                    })}
+                                    {number_input("长度 (秒)", "0.1", |item| item.length, |item, v| {
+                                        item.length = v.min(item.source_duration - item.offset).max(MIN_ITEM_SECS);
+                                    })}
+                                    {number_input("淡入 (秒)", "0.05", |item| item.fade_in, |item, v| item.fade_in = v.clamp(0.0, item.length))}
+                                    {number_input("淡出 (秒)", "0.05", |item| item.fade_out, |item, v| item.fade_out = v.clamp(0.0, item.length))}
+                                    {number_input("音量 (dB)", "1", |item| item.gain_db, |item, v| item.gain_db = v.clamp(-30.0, 12.0))}
+                                    <label class="block">
+                                        <span class="text-xs text-gray-500">"所在轨道"</span>
+                                        <select
+                                            class="w-full p-1.5 border border-gray-200 rounded-lg text-sm focus:outline-none focus:ring-2 focus:ring-primary/50"
+                                            on:change=move |ev| {
+                                                let Ok(to) = event_target_value(&ev).parse::<usize>() else {
+                                                    return;
+                                                };
+                                                project.update(|p| {
+                                                    if to == t || to >= p.tracks.len() || i >= p.tracks[t].items.len() {
+                                                        return;
+                                                    }
+                                                    let item = p.tracks[t].items.remove(i);
+                                                    p.tracks[to].items.push(item);
+                                                    selected.set(Some((to, p.tracks[to].items.len() - 1)));
+                                                });
+                                            }
+                                        >
+                                            {project.with_untracked(|p| {
+                                                p.tracks.iter().enumerate().map(|(n, track)| view! {
+                                                    <option value=n.to_string() selected=n == t>{track.name.clone()}</option>
+                                                }).collect_view()
+                                            })}
+                                        </select>
+                                    </label>
+                                </div>
+                            </section>
+                        }.into_any())}
+
+                        // --- 导出 ---
+                        {view! {
+                            <section class="bg-white rounded-xl p-4 shadow-soft text-sm">
+                                <div class="flex items-center gap-3">
+                                    <span class="text-gray-500 flex-1">
+                                        {move || format!("总时长 {:.1} 秒", project.with(|p| p.duration()))}
+                                    </span>
+                                    {[(RenderFormat::Wav, "导出 WAV"), (RenderFormat::Mp3, "导出 MP3")]
+                                        .into_iter()
+                                        .map(|(format, label)| view! {
+                                            <button
+                                                class="px-4 py-2 rounded-lg bg-primary hover:bg-primary-focus text-white transition-colors disabled:opacity-50 disabled:cursor-not-allowed"
+                                                on:click=move |_| { render_action.dispatch(format); }
+                                                disabled=move || render_action.pending().get() || project.with(|p| p.duration() <= 0.0)
+                                            >
+                                                <i class="fa fa-download mr-1"></i>
+                                                {label}
+                                            </button>
+                                        })
+                                        .collect_view()}
+                                </div>
+                                {move || render_action.pending().get().then(|| view! {
+                                    <p class="text-gray-400 mt-3 animate-pulse">"正在渲染..."</p>
+                                })}
+                                {move || render_action.value().get().map(|result| match result {
+                                    Ok((clip, format)) => {
+                                        let file_name = match format {
+                                            RenderFormat::Wav => "timeline.wav",
+                                            RenderFormat::Mp3 => "timeline.mp3",
+                                        };
+                                        view! {
+                                            <div class="mt-4">
+                                                <WaveformPlayer src=clip_url(&clip.id) duration=clip.duration peaks=clip.peaks />
+                                                <a
+                                                    href=clip_url(&clip.id)
+                                                    download=file_name
+                                                    class="mt-3 bg-white border border-green-200 text-green-700 hover:bg-green-100 px-4 py-2 rounded-lg text-sm flex items-center justify-center transition-colors"
+                                                >
+                                                    <i class="fa fa-download mr-2"></i>
+                                                    {format!("下载 {}", file_name)}
+                                                </a>
+                                            </div>
+                                        }.into_any()
+                                    }
+                                    Err(e) => view! {
+                                        <p class="mt-3 text-red-500 bg-red-50 rounded-lg p-3">{format!("渲染失败: {}", e)}</p>
+                                    }.into_any(),
+                                })}
+                            </section>
+                        }
+                        .into_any()}
+                    </div>
+                </div>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,52 @@
+use crate::api::{Alignment, TimedText};
+use leptos::prelude::*;
+
+/// 跟读文本：按对齐信息高亮正在朗读的词 (服务商提供时) 或句子，点击跳到对应位置
+#[component]
+pub fn Transcript(
+    alignment: Alignment,
+    /// 当前播放位置 (秒)
+    #[prop(into)]
+    position: Signal<f32>,
+    /// 点击某段文本时回调它的起始位置 (秒)
+    on_seek: Callback<f32>,
+) -> impl IntoView {
+    // 有词级时间戳时逐词高亮，否则逐句
+    let spans: Vec<TimedText> = if alignment.words.is_empty() {
+        alignment.sentences
+    } else {
+        alignment.words
+    };
+
+    view! {
+        <div class="max-h-48 overflow-y-auto p-3 bg-gray-50 rounded-lg text-sm leading-7">
+            {spans
+                .into_iter()
+                .map(|span| {
+                    let TimedText { text, start, end } = span;
+                    // 英文词句之间补回空格
+                    let gap = text.ends_with(|c: char| c.is_ascii()).then_some(" ");
+                    let class = move || {
+                        let now = position.get();
+                        if now >= start && now < end {
+                            "rounded px-0.5 bg-primary/15 text-primary font-medium"
+                        } else if now >= end {
+                            "rounded px-0.5 text-gray-800"
+                        } else {
+                            "rounded px-0.5 text-gray-400"
+                        }
+                    };
+                    view! {
+                        <span
+                            class=move || format!("{} cursor-pointer hover:bg-primary/5 transition-colors", class())
+                            on:click=move |_| on_seek.run(start)
+                        >
+                            {text}
+                        </span>
+                        {gap}
+                    }
+                })
+                .collect_view()}
+        </div>
+    }
+}
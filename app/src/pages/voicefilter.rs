@@ -0,0 +1,318 @@
+use crate::api::voicefilter::{
+    apply_voice_filter, upload_recording, AudioClip, VoiceFilterSettings, UPLOAD_FIELD,
+};
+use crate::clips::clip_url;
+use crate::pages::recorder::MicRecorder;
+use crate::pages::waveform::WaveformPlayer;
+use leptos::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{FormData, HtmlFormElement};
+
+/// 变声器：上传录音，调节效果后对比处理前后的声音
+#[component]
+pub fn Voicefilter() -> impl IntoView {
+    // 状态
+    let original = RwSignal::new(None::<AudioClip>);
+    let settings = RwSignal::new(VoiceFilterSettings::default());
+
+    let filter_action = Action::new(move |_: &()| {
+        let clip_id = original.with_untracked(|c| c.as_ref().map(|c| c.id.clone()));
+        let settings = settings.get_untracked();
+        async move {
+            let Some(clip_id) = clip_id else {
+                return Err(ServerFnError::ServerError("请先上传录音".to_string()));
+            };
+            apply_voice_filter(clip_id, settings).await
+        }
+    });
+
+    // 上传或录音完成后作为新的原声，清空上一次的处理结果
+    let on_recorded = Callback::new(move |clip: AudioClip| {
+        original.set(Some(clip));
+        filter_action.value().set(None);
+    });
+
+    // FormData 不能跨线程，使用本地 Action
+    let upload_action = Action::new_local(move |data: &FormData| {
+        let upload = upload_recording(data.clone().into());
+        async move {
+            on_recorded.run(upload.await?);
+            Ok::<_, ServerFnError>(())
+        }
+    });
+
+    let on_file_change = move |ev: leptos::ev::Event| {
+        let Some(form) = ev
+            .current_target()
+            .and_then(|t| t.dyn_into::<HtmlFormElement>().ok())
+        else {
+            return;
+        };
+        if let Ok(data) = FormData::new_with_form(&form) {
+            upload_action.dispatch_local(data);
+        }
+    };
+
+    view! {
+        <div class="min-h-screen bg-base-100 pb-12">
+            <div class="container mx-auto px-4 py-8 md:py-12 max-w-6xl">
+
+                <section class="text-center mb-12">
+                    <h2 class="text-[clamp(1.8rem,4vw,2.5rem)] font-bold mb-4 text-shadow text-dark">
+                        "变声器"
+                    </h2>
+                    <p class="text-gray-600 max-w-2xl mx-auto">
+                        "上传一段录音，叠加变调、机器人、混响等效果，前后对比试听"
+                    </p>
+                </section>
+
+                <div class="grid grid-cols-1 lg:grid-cols-3 gap-8">
+
+                    // --- 左侧栏 (上传 + 效果) ---
+                    <div class="lg:col-span-1 space-y-8">
+                        <section class="bg-white rounded-xl p-6 shadow-soft transition-all duration-300 hover:shadow-hover">
+                            <h3 class="text-lg font-semibold mb-4 flex items-center">
+                                <i class="fa fa-upload text-primary mr-2"></i>
+                                "上传录音"
+                            </h3>
+                            <form on:change=on_file_change>
+                                <input
+                                    type="file"
+                                    name=UPLOAD_FIELD
+                                    accept=".wav,audio/wav"
+                                    class="w-full text-sm text-gray-600 file:mr-3 file:py-2 file:px-4 file:rounded-lg file:border-0 file:bg-primary/10 file:text-primary hover:file:bg-primary/20"
+                                    disabled=move || upload_action.pending().get()
+                                />
+                            </form>
+                            <p class="text-xs text-gray-400 mt-2">"目前仅支持 WAV，最长 120 秒"</p>
+                            <div class="flex items-center my-4 text-xs text-gray-400">
+                                <div class="flex-1 border-t border-gray-100"></div>
+                                <span class="px-3">"或直接录音"</span>
+                                <div class="flex-1 border-t border-gray-100"></div>
+                            </div>
+                            <MicRecorder on_recorded=on_recorded />
+                            {move || upload_action.pending().get().then(|| view! {
+                                <p class="text-sm text-gray-500 mt-2">
+                                    <i class="fa fa-circle-o-notch fa-spin mr-2"></i>
+                                    "正在上传..."
+                                </p>
+                            })}
+                            {move || upload_action.value().get().and_then(Result::err).map(|e| view! {
+                                <p class="text-xs text-red-500 mt-2">{e.to_string()}</p>
+                            })}
+                        </section>
+
+                        <EffectsCard settings=settings />
+                    </div>
+
+                    // --- 右侧栏 (前后对比) ---
+                    <div class="lg:col-span-2 space-y-8">
+                        <section class="bg-white rounded-xl p-6 shadow-soft transition-all duration-300 hover:shadow-hover">
+                            <h3 class="text-lg font-semibold mb-4 flex items-center">
+                                <i class="fa fa-exchange text-primary mr-2"></i>
+                                "前后对比"
+                            </h3>
+
+                            <button
+                                class="bg-primary hover:bg-primary-focus text-white py-3 px-6 rounded-lg font-medium transition-all duration-300 flex items-center justify-center w-full shadow-md hover:shadow-lg active:scale-[0.98] disabled:opacity-50 disabled:cursor-not-allowed mb-6"
+                                on:click=move |_| { filter_action.dispatch(()); }
+                                disabled=move || filter_action.pending().get() || original.with(Option::is_none)
+                            >
+                                {move || if filter_action.pending().get() {
+                                    view! { <> <i class="fa fa-circle-o-notch fa-spin mr-2"></i> "正在处理..." </> }.into_view()
+                                } else {
+                                    view! { <> <i class="fa fa-magic mr-2"></i> "应用效果" </> }.into_view()
+                                }}
+                            </button>
+
+                            <div class="space-y-6">
+                                <div>
+                                    <p class="text-sm text-gray-600 mb-2">"原声"</p>
+                                    {move || match original.get() {
+                                        Some(clip) => view! {
+                                            <WaveformPlayer src=clip_url(&clip.id) duration=clip.duration peaks=clip.peaks clip_id=clip.id.clone() />
+                                        }.into_any(),
+                                        None => view! {
+                                            <div class="text-center py-8 text-gray-400 bg-gray-50 rounded-xl border border-dashed border-gray-200 text-sm">
+                                                "请先在左侧上传录音"
+                                            </div>
+                                        }.into_any(),
+                                    }}
+                                </div>
+                                <div>
+                                    <p class="text-sm text-gray-600 mb-2">"处理后"</p>
+                                    {move || match filter_action.value().get() {
+                                        Some(Ok(clip)) => view! {
+                                            <WaveformPlayer src=clip_url(&clip.id) duration=clip.duration peaks=clip.peaks clip_id=clip.id.clone() autoplay=true />
+                                            <a
+                                                href=clip_url(&clip.id)
+                                                download="voicefilter.wav"
+                                                class="mt-3 bg-white border border-green-200 text-green-700 hover:bg-green-100 px-4 py-2 rounded-lg text-sm flex items-center justify-center transition-colors"
+                                            >
+                                                <i class="fa fa-download mr-2"></i>
+                                                "下载音频"
+                                            </a>
+                                        }.into_any(),
+                                        Some(Err(e)) => view! {
+                                            <p class="text-sm text-red-500 bg-red-50 rounded-lg p-3">{format!("处理失败: {}", e)}</p>
+                                        }.into_any(),
+                                        None => view! {
+                                            <div class="text-center py-8 text-gray-400 bg-gray-50 rounded-xl border border-dashed border-gray-200 text-sm">
+                                                "调节效果后点击「应用效果」"
+                                            </div>
+                                        }.into_any(),
+                                    }}
+                                </div>
+                            </div>
+                        </section>
+                    </div>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn EffectsCard(settings: RwSignal<VoiceFilterSettings>) -> impl IntoView {
+    // 常用组合：(名称, 设置)
+    type Preset = (&'static str, fn() -> VoiceFilterSettings);
+    let presets: [Preset; 5] = [
+        ("男声变女声", || VoiceFilterSettings {
+            pitch: 5.0,
+            formant: 2.0,
+            ..Default::default()
+        }),
+        ("女声变男声", || VoiceFilterSettings {
+            pitch: -5.0,
+            formant: -2.0,
+            ..Default::default()
+        }),
+        ("机器人", || VoiceFilterSettings {
+            robot: true,
+            echo: 0.2,
+            ..Default::default()
+        }),
+        ("电话", || VoiceFilterSettings {
+            telephone: true,
+            ..Default::default()
+        }),
+        ("空旷大厅", || VoiceFilterSettings {
+            reverb: 0.6,
+            ..Default::default()
+        }),
+    ];
+    // (名称, 最小值, 最大值, 步长, 读取字段, 写入字段)
+    type Slider = (
+        &'static str,
+        f32,
+        f32,
+        f32,
+        fn(&VoiceFilterSettings) -> f32,
+        fn(&mut VoiceFilterSettings, f32),
+    );
+    let sliders: [Slider; 5] = [
+        (
+            "变调 (半音)",
+            -12.0,
+            12.0,
+            1.0,
+            |s| s.pitch,
+            |s, v| s.pitch = v,
+        ),
+        (
+            "共振峰 (半音)",
+            -6.0,
+            6.0,
+            0.5,
+            |s| s.formant,
+            |s, v| s.formant = v,
+        ),
+        ("合唱", 0.0, 1.0, 0.05, |s| s.chorus, |s, v| s.chorus = v),
+        ("回声", 0.0, 1.0, 0.05, |s| s.echo, |s, v| s.echo = v),
+        ("混响", 0.0, 1.0, 0.05, |s| s.reverb, |s, v| s.reverb = v),
+    ];
+    type Toggle = (
+        &'static str,
+        fn(&VoiceFilterSettings) -> bool,
+        fn(&mut VoiceFilterSettings, bool),
+    );
+    let toggles: [Toggle; 2] = [
+        ("机器人", |s| s.robot, |s, v| s.robot = v),
+        ("电话音", |s| s.telephone, |s, v| s.telephone = v),
+    ];
+
+    view! {
+        <section class="bg-white rounded-xl p-6 shadow-soft transition-all duration-300 hover:shadow-hover">
+            <h3 class="text-lg font-semibold mb-4 flex items-center">
+                <i class="fa fa-sliders text-primary mr-2"></i>
+                "效果"
+            </h3>
+
+            <div class="flex flex-wrap gap-2 mb-6">
+                {presets
+                    .into_iter()
+                    .map(|(label, preset)| view! {
+                        <button
+                            class="px-3 py-1 rounded-full text-xs border border-gray-200 text-gray-500 hover:border-primary hover:text-primary transition-colors duration-200"
+                            on:click=move |_| settings.set(preset())
+                        >
+                            {label}
+                        </button>
+                    })
+                    .collect_view()}
+                <button
+                    class="px-3 py-1 rounded-full text-xs text-gray-400 hover:text-red-500 transition-colors duration-200"
+                    on:click=move |_| settings.set(VoiceFilterSettings::default())
+                >
+                    <i class="fa fa-undo mr-1"></i>
+                    "重置"
+                </button>
+            </div>
+
+            <div class="space-y-4">
+                {sliders
+                    .into_iter()
+                    .map(|(label, min, max, step, get, set)| view! {
+                        <div>
+                            <div class="flex justify-between text-sm mb-1">
+                                <label>{label}</label>
+                                <span class="text-primary">{move || format!("{:.2}", settings.with(get))}</span>
+                            </div>
+                            <input
+                                type="range"
+                                min=min.to_string()
+                                max=max.to_string()
+                                step=step.to_string()
+                                class="w-full h-2 bg-gray-200 rounded-lg accent-primary"
+                                prop:value=move || settings.with(get).to_string()
+                                on:input=move |ev| {
+                                    if let Ok(v) = event_target_value(&ev).parse() {
+                                        settings.update(|s| set(s, v));
+                                    }
+                                }
+                            />
+                        </div>
+                    })
+                    .collect_view()}
+            </div>
+
+            <div class="flex gap-4 mt-4 text-sm">
+                {toggles
+                    .into_iter()
+                    .map(|(label, get, set)| view! {
+                        <label class="flex items-center cursor-pointer">
+                            <input
+                                type="checkbox"
+                                class="mr-2 accent-primary"
+                                prop:checked=move || settings.with(get)
+                                on:change=move |ev| settings.update(|s| set(s, event_target_checked(&ev)))
+                            />
+                            {label}
+                        </label>
+                    })
+                    .collect_view()}
+            </div>
+        </section>
+    }
+    .into_any()
+}
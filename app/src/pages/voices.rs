@@ -0,0 +1,244 @@
+use crate::api::cloning::{
+    delete_custom_voice, enroll_custom_voice, list_custom_voices, voice_cloning_available,
+    MAX_SAMPLE_SECS, MAX_VOICE_NAME_CHARS, MIN_SAMPLE_SECS,
+};
+use crate::api::voicefilter::{upload_recording, AudioClip, UPLOAD_FIELD};
+use crate::clips::clip_url;
+use crate::pages::recorder::MicRecorder;
+use crate::pages::waveform::WaveformPlayer;
+use leptos::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{FormData, HtmlFormElement};
+
+/// 我的声音：上传或录制参考音频，确认授权后复刻为自定义声线
+#[component]
+pub fn MyVoices() -> impl IntoView {
+    // 状态
+    let sample = RwSignal::new(None::<AudioClip>);
+    let name = RwSignal::new(String::new());
+    let consent = RwSignal::new(false);
+
+    let on_recorded = Callback::new(move |clip: AudioClip| sample.set(Some(clip)));
+
+    // FormData 不能跨线程，使用本地 Action
+    let upload_action = Action::new_local(move |data: &FormData| {
+        let upload = upload_recording(data.clone().into());
+        async move {
+            on_recorded.run(upload.await?);
+            Ok::<_, ServerFnError>(())
+        }
+    });
+    let on_file_change = move |ev: leptos::ev::Event| {
+        let Some(form) = ev
+            .current_target()
+            .and_then(|t| t.dyn_into::<HtmlFormElement>().ok())
+        else {
+            return;
+        };
+        if let Ok(data) = FormData::new_with_form(&form) {
+            upload_action.dispatch_local(data);
+        }
+    };
+
+    let enroll_action = Action::new(move |_: &()| {
+        let clip_id = sample.with_untracked(|c| c.as_ref().map(|c| c.id.clone()));
+        let voice_name = name.get_untracked();
+        let consented = consent.get_untracked();
+        async move {
+            let Some(clip_id) = clip_id else {
+                return Err(ServerFnError::ServerError(
+                    "请先上传或录制参考音频".to_string(),
+                ));
+            };
+            let voice = enroll_custom_voice(clip_id, voice_name, consented).await?;
+            sample.set(None);
+            name.set(String::new());
+            consent.set(false);
+            Ok::<_, ServerFnError>(voice)
+        }
+    });
+    let delete_action = Action::new(move |id: &String| delete_custom_voice(id.clone()));
+
+    let available = Resource::new(|| (), |_| voice_cloning_available());
+    // 复刻或删除后重新拉取列表
+    let voices = Resource::new(
+        move || (enroll_action.version().get(), delete_action.version().get()),
+        |_| list_custom_voices(),
+    );
+
+    let can_submit = move || {
+        !enroll_action.pending().get()
+            && sample.with(Option::is_some)
+            && consent.get()
+            && name.with(|n| !n.trim().is_empty())
+    };
+
+    view! {
+        <div class="min-h-screen bg-base-100 pb-12">
+            <div class="container mx-auto px-4 py-8 md:py-12 max-w-6xl">
+
+                <section class="text-center mb-12">
+                    <h2 class="text-[clamp(1.8rem,4vw,2.5rem)] font-bold mb-4 text-shadow text-dark">
+                        "我的声音"
+                    </h2>
+                    <p class="text-gray-600 max-w-2xl mx-auto">
+                        "录一段清晰的朗读，复刻出专属声线，之后在「声线选择」里直接使用"
+                    </p>
+                </section>
+
+                <Suspense fallback=|| ()>
+                    {move || matches!(available.get(), Some(Ok(false))).then(|| view! {
+                        <div class="mb-8 p-4 rounded-xl border border-yellow-200 bg-yellow-50 text-sm text-yellow-700 flex items-center">
+                            <i class="fa fa-info-circle mr-2"></i>
+                            "当前服务商未开启声音复刻，请联系管理员配置"
+                        </div>
+                    })}
+                </Suspense>
+
+                <div class="grid grid-cols-1 lg:grid-cols-3 gap-8">
+
+                    // --- 左侧栏 (复刻) ---
+                    <div class="lg:col-span-2 space-y-8">
+                        <section class="bg-white rounded-xl p-6 shadow-soft transition-all duration-300 hover:shadow-hover">
+                            <h3 class="text-lg font-semibold mb-4 flex items-center">
+                                <i class="fa fa-clone text-primary mr-2"></i>
+                                "复刻新声音"
+                            </h3>
+
+                            // 1. 参考音频
+                            <p class="text-sm font-medium mb-2">"1. 参考音频"</p>
+                            <p class="text-xs text-gray-400 mb-3">
+                                {format!(
+                                    "{}~{} 秒，安静环境下用正常语速朗读，避免背景音乐和多人说话",
+                                    MIN_SAMPLE_SECS, MAX_SAMPLE_SECS
+                                )}
+                            </p>
+                            {move || match sample.get() {
+                                Some(clip) => view! {
+                                    <WaveformPlayer src=clip_url(&clip.id) duration=clip.duration peaks=clip.peaks />
+                                    <button
+                                        class="mt-2 text-xs text-gray-400 hover:text-red-500 transition-colors"
+                                        on:click=move |_| sample.set(None)
+                                    >
+                                        <i class="fa fa-refresh mr-1"></i>
+                                        "换一段"
+                                    </button>
+                                }.into_any(),
+                                None => view! {
+                                    <div class="grid grid-cols-1 md:grid-cols-2 gap-4">
+                                        <MicRecorder on_recorded=on_recorded />
+                                        <div class="border border-dashed border-gray-200 rounded-xl p-4">
+                                            <p class="text-sm text-gray-500 mb-2">"或上传 WAV 文件"</p>
+                                            <form on:change=on_file_change>
+                                                <input
+                                                    type="file"
+                                                    name=UPLOAD_FIELD
+                                                    accept=".wav,audio/wav"
+                                                    class="w-full text-sm text-gray-600 file:mr-3 file:py-2 file:px-4 file:rounded-lg file:border-0 file:bg-primary/10 file:text-primary hover:file:bg-primary/20"
+                                                    disabled=move || upload_action.pending().get()
+                                                />
+                                            </form>
+                                            {move || upload_action.value().get().and_then(Result::err).map(|e| view! {
+                                                <p class="text-xs text-red-500 mt-2">{e.to_string()}</p>
+                                            })}
+                                        </div>
+                                    </div>
+                                }.into_any(),
+                            }}
+
+                            // 2. 名称
+                            <p class="text-sm font-medium mt-6 mb-2">"2. 声线名称"</p>
+                            <input
+                                class="w-full p-2 border border-gray-200 rounded-lg text-sm focus:outline-none focus:ring-2 focus:ring-primary/50"
+                                placeholder="如 品牌播报"
+                                maxlength=MAX_VOICE_NAME_CHARS.to_string()
+                                prop:value=move || name.get()
+                                on:input=move |ev| name.set(event_target_value(&ev))
+                            />
+
+                            // 3. 授权确认
+                            <p class="text-sm font-medium mt-6 mb-2">"3. 授权确认"</p>
+                            <label class="flex items-start text-sm text-gray-600 cursor-pointer">
+                                <input
+                                    type="checkbox"
+                                    class="mr-2 mt-1 accent-primary"
+                                    prop:checked=move || consent.get()
+                                    on:change=move |ev| consent.set(event_target_checked(&ev))
+                                />
+                                "我确认参考音频是我本人的声音，或已获得声音主人的明确授权；复刻的声线不会用于冒充他人或其他违法用途。"
+                            </label>
+
+                            <button
+                                class="mt-6 bg-primary hover:bg-primary-focus text-white py-3 px-6 rounded-lg font-medium transition-all duration-300 flex items-center justify-center w-full shadow-md hover:shadow-lg active:scale-[0.98] disabled:opacity-50 disabled:cursor-not-allowed"
+                                on:click=move |_| { enroll_action.dispatch(()); }
+                                disabled=move || !can_submit()
+                            >
+                                {move || if enroll_action.pending().get() {
+                                    view! { <> <i class="fa fa-circle-o-notch fa-spin mr-2"></i> "正在复刻..." </> }.into_view()
+                                } else {
+                                    view! { <> <i class="fa fa-magic mr-2"></i> "开始复刻" </> }.into_view()
+                                }}
+                            </button>
+                            {move || enroll_action.value().get().map(|result| match result {
+                                Ok(voice) => view! {
+                                    <p class="text-sm text-green-600 mt-3">
+                                        <i class="fa fa-check mr-1"></i>
+                                        {format!("「{}」复刻完成，可以在声线选择中使用了", voice.name)}
+                                    </p>
+                                }.into_any(),
+                                Err(e) => view! {
+                                    <p class="text-sm text-red-500 mt-3">{e.to_string()}</p>
+                                }.into_any(),
+                            })}
+                        </section>
+                    </div>
+
+                    // --- 右侧栏 (已有声线) ---
+                    <div class="lg:col-span-1 space-y-8">
+                        <section class="bg-white rounded-xl p-6 shadow-soft transition-all duration-300 hover:shadow-hover">
+                            <h3 class="text-lg font-semibold mb-4 flex items-center">
+                                <i class="fa fa-list text-primary mr-2"></i>
+                                "已复刻的声音"
+                            </h3>
+                            <Suspense fallback=move || view! {
+                                <div class="text-center py-4 text-gray-400 text-sm animate-pulse">"加载中..."</div>
+                            }>
+                                {move || voices.get().map(|result| match result {
+                                    Err(e) => view! {
+                                        <p class="text-sm text-red-500">{format!("加载失败: {}", e)}</p>
+                                    }.into_any(),
+                                    Ok(list) if list.is_empty() => view! {
+                                        <p class="text-center py-4 text-gray-400 text-sm">"暂无"</p>
+                                    }.into_any(),
+                                    Ok(list) => view! {
+                                        <ul class="space-y-2">
+                                            {list.into_iter().map(|voice| {
+                                                let id = voice.id.clone();
+                                                view! {
+                                                    <li class="flex items-center justify-between text-sm p-3 border border-gray-100 rounded-lg">
+                                                        <span class="font-medium truncate">{voice.name}</span>
+                                                        <button
+                                                            class="text-gray-300 hover:text-red-500 transition-colors disabled:opacity-50"
+                                                            title="删除"
+                                                            on:click=move |_| { delete_action.dispatch(id.clone()); }
+                                                            disabled=move || delete_action.pending().get()
+                                                        >
+                                                            <i class="fa fa-trash-o"></i>
+                                                        </button>
+                                                    </li>
+                                                }
+                                            }).collect_view()}
+                                        </ul>
+                                    }.into_any(),
+                                })}
+                            </Suspense>
+                            {move || delete_action.value().get().and_then(Result::err).map(|e| view! {
+                                <p class="text-xs text-red-500 mt-2">{e.to_string()}</p>
+                            })}
+                        </section>
+                    </div>
+                </div>
+            </div>
+        </div>
+    }
+}
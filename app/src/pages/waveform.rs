@@ -0,0 +1,295 @@
+use crate::api::{get_spectrogram, Alignment, Spectrogram};
+use crate::pages::transcript::Transcript;
+use leptos::prelude::*;
+use wasm_bindgen::{Clamped, JsCast};
+use web_sys::{CanvasRenderingContext2d, ImageData};
+
+/// 峰值达到该值 (0 ~ 255) 视为削波
+const CLIP_LEVEL: u8 = 254;
+
+/// 按点击位置换算成目标时间 (秒)
+fn seek_ratio(ev: &leptos::ev::MouseEvent) -> Option<f32> {
+    let target = ev.current_target()?.dyn_into::<web_sys::Element>().ok()?;
+    let rect = target.get_bounding_client_rect();
+    (rect.width() > 0.0)
+        .then(|| ((ev.client_x() as f64 - rect.left()) / rect.width()).clamp(0.0, 1.0) as f32)
+}
+
+/// 波形概览：按峰值绘制柱状图，已播放部分高亮、削波处标红，点击跳转播放位置
+#[component]
+pub fn Waveform(
+    /// 波形峰值 (0 ~ 255)
+    peaks: Vec<u8>,
+    /// 本段音频时长 (秒)
+    duration: f32,
+    /// 横轴对应的总时长 (秒)；多条波形对齐时传入其中最长的时长
+    #[prop(into)]
+    scale: Signal<f32>,
+    /// 当前播放位置 (秒)
+    #[prop(into)]
+    position: Signal<f32>,
+    /// 点击波形时回调目标位置 (秒)
+    on_seek: Callback<f32>,
+) -> impl IntoView {
+    let count = peaks.len().max(1) as f32;
+    // 本段音频占整条时间轴的比例
+    let width = move || {
+        let scale = scale.get();
+        if scale > 0.0 {
+            (duration / scale).clamp(0.0, 1.0) * 100.0
+        } else {
+            100.0
+        }
+    };
+    let played = move || {
+        if duration > 0.0 {
+            (position.get() / duration).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    };
+
+    let on_click = move |ev: leptos::ev::MouseEvent| {
+        if let Some(ratio) = seek_ratio(&ev) {
+            on_seek.run(ratio * duration);
+        }
+    };
+
+    view! {
+        <div class="w-full h-16 bg-gray-50 rounded-lg overflow-hidden">
+            <svg
+                class="h-full cursor-pointer"
+                style:width=move || format!("{}%", width())
+                viewBox=format!("0 0 {} 100", count)
+                preserveAspectRatio="none"
+                on:click=on_click
+            >
+                {peaks
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, peak)| {
+                        let height = (peak as f32 / 255.0 * 100.0).max(2.0);
+                        let clipped = peak >= CLIP_LEVEL;
+                        view! {
+                            <rect
+                                x=i.to_string()
+                                y=((100.0 - height) / 2.0).to_string()
+                                width="0.7"
+                                height=height.to_string()
+                                class=move || if clipped {
+                                    "fill-red-500"
+                                } else if (i as f32 + 0.5) / count <= played() {
+                                    "fill-primary"
+                                } else {
+                                    "fill-gray-300"
+                                }
+                            />
+                        }
+                    })
+                    .collect_view()}
+            </svg>
+        </div>
+    }
+}
+
+/// 语谱图配色：由暗到亮的若干色标，按能量线性插值
+const PALETTE: [[u8; 3]; 5] = [
+    [15, 10, 40],
+    [80, 20, 120],
+    [190, 50, 110],
+    [250, 140, 50],
+    [255, 245, 180],
+];
+
+fn palette(value: u8) -> [u8; 3] {
+    let pos = value as f32 / 255.0 * (PALETTE.len() - 1) as f32;
+    let i = (pos as usize).min(PALETTE.len() - 2);
+    let t = pos - i as f32;
+    let (a, b) = (PALETTE[i], PALETTE[i + 1]);
+    [0, 1, 2].map(|c| (a[c] as f32 + (b[c] as f32 - a[c] as f32) * t).round() as u8)
+}
+
+/// 把语谱图画到画布上：一列一个像素，低频在下
+fn draw_spectrogram(canvas: &web_sys::HtmlCanvasElement, spec: &Spectrogram) {
+    if spec.columns == 0 || spec.bands == 0 || spec.data.len() < spec.columns * spec.bands {
+        return;
+    }
+    canvas.set_width(spec.columns as u32);
+    canvas.set_height(spec.bands as u32);
+    let Some(ctx) = canvas
+        .get_context("2d")
+        .ok()
+        .flatten()
+        .and_then(|c| c.dyn_into::<CanvasRenderingContext2d>().ok())
+    else {
+        return;
+    };
+    let mut rgba = vec![255u8; spec.columns * spec.bands * 4];
+    for c in 0..spec.columns {
+        for b in 0..spec.bands {
+            let y = spec.bands - 1 - b;
+            let at = (y * spec.columns + c) * 4;
+            rgba[at..at + 3].copy_from_slice(&palette(spec.data[c * spec.bands + b]));
+        }
+    }
+    if let Ok(image) = ImageData::new_with_u8_clamped_array_and_sh(
+        Clamped(&rgba),
+        spec.columns as u32,
+        spec.bands as u32,
+    ) {
+        let _ = ctx.put_image_data(&image, 0.0, 0.0);
+    }
+}
+
+/// 语谱图：打开时向服务端请求，带播放头，点击跳转播放位置
+#[component]
+pub fn SpectrogramView(
+    /// 片段库中的音频 ID
+    clip_id: String,
+    duration: f32,
+    /// 当前播放位置 (秒)
+    #[prop(into)]
+    position: Signal<f32>,
+    /// 点击时回调目标位置 (秒)
+    on_seek: Callback<f32>,
+) -> impl IntoView {
+    let spectrogram = Resource::new(|| (), move |_| get_spectrogram(clip_id.clone()));
+    let canvas_ref = NodeRef::<leptos::html::Canvas>::new();
+
+    Effect::new(move |_| {
+        if let (Some(canvas), Some(Ok(spec))) = (canvas_ref.get(), spectrogram.get()) {
+            draw_spectrogram(&canvas, &spec);
+        }
+    });
+
+    let playhead = move || {
+        if duration > 0.0 {
+            (position.get() / duration).clamp(0.0, 1.0) * 100.0
+        } else {
+            0.0
+        }
+    };
+    let on_click = move |ev: leptos::ev::MouseEvent| {
+        if let Some(ratio) = seek_ratio(&ev) {
+            on_seek.run(ratio * duration);
+        }
+    };
+
+    view! {
+        <div
+            class="relative w-full h-32 bg-gray-900 rounded-lg overflow-hidden cursor-pointer"
+            on:click=on_click
+        >
+            <canvas node_ref=canvas_ref class="w-full h-full"></canvas>
+            <div
+                class="absolute top-0 bottom-0 w-px bg-white/80 pointer-events-none"
+                style:left=move || format!("{}%", playhead())
+            ></div>
+            {move || match spectrogram.get() {
+                None => Some(view! {
+                    <div class="absolute inset-0 flex items-center justify-center text-xs text-gray-400 animate-pulse">
+                        "正在计算语谱图..."
+                    </div>
+                }.into_any()),
+                Some(Err(e)) => Some(view! {
+                    <div class="absolute inset-0 flex items-center justify-center text-xs text-red-400">
+                        {format!("语谱图加载失败: {}", e)}
+                    </div>
+                }.into_any()),
+                Some(Ok(_)) => None,
+            }}
+        </div>
+    }
+}
+
+/// 带波形的播放器：原生播放控件 + 与播放进度同步的波形，点击波形跳转。
+/// 传入 `clip_id` 时可展开语谱图，传入 `alignment` 时显示随播放高亮的文本
+#[component]
+pub fn WaveformPlayer(
+    /// 音频地址 (URL 或 Data URI)
+    src: String,
+    duration: f32,
+    peaks: Vec<u8>,
+    /// 片段库中的音频 ID，用于按需加载语谱图
+    #[prop(optional, into)]
+    clip_id: Option<String>,
+    /// 文本对齐信息
+    #[prop(optional, into)]
+    alignment: Option<Alignment>,
+    /// 是否加载后自动播放
+    #[prop(optional)]
+    autoplay: bool,
+) -> impl IntoView {
+    let audio_ref = NodeRef::<leptos::html::Audio>::new();
+    let position = RwSignal::new(0.0_f32);
+    let show_spectrogram = RwSignal::new(false);
+    let seek = Callback::new(move |secs: f32| {
+        position.set(secs);
+        if let Some(audio) = audio_ref.get_untracked() {
+            audio.set_current_time(secs as f64);
+        }
+    });
+    let clipped = peaks.iter().filter(|&&p| p >= CLIP_LEVEL).count();
+    let has_spectrogram = clip_id.is_some();
+
+    view! {
+        <div class="flex flex-col gap-2">
+            {(!peaks.is_empty()).then(|| view! {
+                <Waveform
+                    peaks=peaks
+                    duration=duration
+                    scale=Signal::stored(duration)
+                    position=position
+                    on_seek=seek
+                />
+            })}
+            {(clipped > 0 || has_spectrogram).then(|| view! {
+                <div class="flex items-center justify-between text-xs">
+                    <span class="text-red-500">
+                        {(clipped > 0).then(|| view! {
+                            <i class="fa fa-exclamation-triangle mr-1"></i>
+                            {format!("检测到 {} 处削波 (红色)", clipped)}
+                        })}
+                    </span>
+                    {has_spectrogram.then(|| view! {
+                        <button
+                            class="text-gray-400 hover:text-primary transition-colors"
+                            on:click=move |_| show_spectrogram.update(|v| *v = !*v)
+                        >
+                            <i class="fa fa-bar-chart mr-1"></i>
+                            {move || if show_spectrogram.get() { "收起语谱图" } else { "语谱图" }}
+                        </button>
+                    })}
+                </div>
+            })}
+            {clip_id.map(|clip_id| view! {
+                <Show when=move || show_spectrogram.get()>
+                    <SpectrogramView
+                        clip_id=clip_id.clone()
+                        duration=duration
+                        position=position
+                        on_seek=seek
+                    />
+                </Show>
+            })}
+            <audio
+                node_ref=audio_ref
+                controls
+                autoplay=autoplay
+                class="w-full"
+                src=src
+                on:timeupdate=move |_| {
+                    if let Some(audio) = audio_ref.get_untracked() {
+                        position.set(audio.current_time() as f32);
+                    }
+                }
+            ></audio>
+            {alignment
+                .filter(|a| !a.sentences.is_empty() || !a.words.is_empty())
+                .map(|alignment| view! {
+                    <Transcript alignment=alignment position=position on_seek=seek />
+                })}
+        </div>
+    }
+    .into_any()
+}
@@ -0,0 +1,189 @@
+//! 安全相关：响应安全头、CSRF token 与请求体大小限制
+//!
+//! 页面所需的脚本、样式、图标都由本站提供，不依赖任何第三方 CDN，
+//! 因此内容安全策略 (CSP) 可以只放行 `'self'`。
+//! 服务端中间件 (HSTS、CORS、请求体大小限制等) 在 `server/src/main.rs` 中组装，
+//! CSRF token 在 `session::user_id` 中校验。
+use futures::{Sink, Stream};
+#[cfg(feature = "ssr")]
+use http::{header, HeaderMap, HeaderValue};
+#[cfg(feature = "ssr")]
+use leptos::prelude::*;
+#[cfg(feature = "ssr")]
+use leptos_axum::ResponseOptions;
+use server_fn::client::{browser::BrowserClient, Client};
+use server_fn::error::FromServerFnError;
+use server_fn::request::browser::BrowserRequest;
+use server_fn::response::browser::BrowserResponse;
+use server_fn::Bytes;
+use std::future::Future;
+
+/// 保存 CSRF token 的 Cookie，浏览器端脚本需要读取，所以不设 HttpOnly
+pub const CSRF_COOKIE: &str = "eardo_csrf";
+/// 服务端函数请求携带 CSRF token 的请求头
+pub const CSRF_HEADER: &str = "x-csrf-token";
+
+/// 普通请求的请求体上限
+pub const BODY_LIMIT_BYTES: usize = 2 * 1024 * 1024;
+/// 音频上传接口的请求体上限 (文件本身另有限制，这里留出 multipart 的开销)
+pub const UPLOAD_BODY_LIMIT_BYTES: usize = 24 * 1024 * 1024;
+/// 音频上传的服务端函数路径，见 `api::voicefilter::upload_recording`
+pub const UPLOAD_PATH: &str = "/api/upload_recording";
+
+/// 可以被其他站点用 iframe 嵌入的页面
+pub fn is_embeddable(path: &str) -> bool {
+    path.starts_with("/embed/")
+}
+
+/// 服务端函数的客户端：在浏览器 `fetch` 的基础上附带 CSRF token 请求头
+///
+/// 所有使用 Cookie 识别用户的服务端函数都应声明 `#[server(client = CsrfClient)]`，
+/// 否则请求会被服务端的 CSRF 校验拒绝。
+pub struct CsrfClient;
+
+impl<E, IS, OS> Client<E, IS, OS> for CsrfClient
+where
+    E: FromServerFnError,
+    IS: FromServerFnError,
+    OS: FromServerFnError,
+{
+    type Request = BrowserRequest;
+    type Response = BrowserResponse;
+
+    fn send(req: BrowserRequest) -> impl Future<Output = Result<BrowserResponse, E>> + Send {
+        #[cfg(target_arch = "wasm32")]
+        {
+            if let Some(token) = csrf_token() {
+                req.headers().set(CSRF_HEADER, &token);
+            }
+        }
+        <BrowserClient as Client<E, IS, OS>>::send(req)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn open_websocket(
+        path: &str,
+    ) -> impl Future<
+        Output = Result<
+            (
+                impl Stream<Item = Result<Bytes, Bytes>> + Send + 'static,
+                impl Sink<Bytes> + Send + 'static,
+            ),
+            E,
+        >,
+    > + Send {
+        <BrowserClient as Client<E, IS, OS>>::open_websocket(path)
+    }
+
+    fn spawn(future: impl Future<Output = ()> + Send + 'static) {
+        <BrowserClient as Client<E, IS, OS>>::spawn(future)
+    }
+}
+
+/// 从 `document.cookie` 读取 CSRF token
+#[cfg(target_arch = "wasm32")]
+fn csrf_token() -> Option<String> {
+    use wasm_bindgen::JsCast;
+    let cookies = web_sys::window()?
+        .document()?
+        .dyn_into::<web_sys::HtmlDocument>()
+        .ok()?
+        .cookie()
+        .ok()?;
+    cookies
+        .split(';')
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == CSRF_COOKIE)
+        .map(|(_, value)| value.to_string())
+}
+
+/// 请求头中的 CSRF token 与 Cookie 一致 (双重提交)
+///
+/// 其他站点可以让浏览器带上 Cookie 发请求，但读不到 Cookie 的值，也就无法伪造请求头。
+#[cfg(feature = "ssr")]
+pub fn csrf_token_valid(headers: &HeaderMap) -> bool {
+    let Some(cookie) = crate::session::read_cookie(headers, CSRF_COOKIE) else {
+        return false;
+    };
+    let Some(token) = headers.get(CSRF_HEADER).and_then(|v| v.to_str().ok()) else {
+        return false;
+    };
+    !cookie.is_empty() && constant_time_eq(cookie.as_bytes(), token.as_bytes())
+}
+
+/// 逐字节比较完再给出结果，避免按耗时猜测 token
+#[cfg(feature = "ssr")]
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// 与 `EARDO_ADMIN_TOKEN` 一致；未配置该变量时没有人是管理员
+#[cfg(feature = "ssr")]
+pub fn admin_token_valid(token: &str) -> bool {
+    std::env::var("EARDO_ADMIN_TOKEN").is_ok_and(|admin| {
+        !admin.is_empty() && constant_time_eq(admin.as_bytes(), token.as_bytes())
+    })
+}
+
+/// 当前页面的 CSP
+///
+/// - 内联脚本只有 hydration / 热重载脚本，它们带有每个请求随机生成的 nonce
+/// - wasm 需要 `'wasm-unsafe-eval'` 才能编译
+/// - 组件里有 `style=` 属性 (波形宽度、进度条等)，只对属性放行内联样式
+/// - 录音和合成结果通过 `blob:` / `data:` URL 播放
+/// - 只有嵌入播放器允许被其他站点的 iframe 加载
+#[cfg(feature = "ssr")]
+fn content_security_policy(nonce: Option<&str>, embeddable: bool) -> String {
+    let script_src = match nonce {
+        Some(nonce) => format!("'self' 'nonce-{}' 'wasm-unsafe-eval'", nonce),
+        None => "'self' 'wasm-unsafe-eval'".to_string(),
+    };
+    // 开发模式下的热重载通过 WebSocket 连接 cargo-leptos
+    let connect_src = if cfg!(debug_assertions) {
+        "'self' ws: wss:"
+    } else {
+        "'self'"
+    };
+    let frame_ancestors = if embeddable { "*" } else { "'none'" };
+    [
+        "default-src 'self'".to_string(),
+        format!("script-src {}", script_src),
+        "style-src 'self'".to_string(),
+        "style-src-attr 'unsafe-inline'".to_string(),
+        "img-src 'self' data: blob:".to_string(),
+        "media-src 'self' data: blob:".to_string(),
+        format!("connect-src {}", connect_src),
+        "font-src 'self'".to_string(),
+        "object-src 'none'".to_string(),
+        "base-uri 'self'".to_string(),
+        "form-action 'self'".to_string(),
+        format!("frame-ancestors {}", frame_ancestors),
+    ]
+    .join("; ")
+}
+
+/// 为当前 SSR 页面加上 CSP，并在首次访问时发放 CSRF token，在 `shell` 中调用
+#[cfg(feature = "ssr")]
+pub fn apply_headers() {
+    let Some(response) = use_context::<ResponseOptions>() else {
+        return;
+    };
+    let parts = use_context::<http::request::Parts>();
+    let embeddable = parts
+        .as_ref()
+        .is_some_and(|parts| is_embeddable(parts.uri.path()));
+    let nonce = leptos::nonce::use_nonce().map(|nonce| nonce.to_string());
+    if let Ok(value) = HeaderValue::from_str(&content_security_policy(nonce.as_deref(), embeddable))
+    {
+        response.insert_header(header::CONTENT_SECURITY_POLICY, value);
+    }
+
+    let has_token = parts
+        .as_ref()
+        .and_then(|parts| crate::session::read_cookie(&parts.headers, CSRF_COOKIE))
+        .is_some_and(|token| !token.is_empty());
+    if !has_token {
+        let token = uuid::Uuid::new_v4().simple().to_string();
+        crate::session::set_cookie(CSRF_COOKIE, &token, false);
+    }
+}
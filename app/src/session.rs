@@ -0,0 +1,106 @@
+//! 匿名用户会话
+//!
+//! 登录功能尚未上线，先用一个长期 Cookie 区分用户，
+//! 用户级数据 (词典、预设等) 都以这个 ID 为键保存。
+use crate::security;
+use axum::extract::ConnectInfo;
+use http::{header, HeaderMap, HeaderValue, Method};
+use leptos::prelude::*;
+use leptos_axum::ResponseOptions;
+use std::net::{IpAddr, SocketAddr};
+
+const USER_COOKIE: &str = "eardo_uid";
+/// 管理员 Cookie，保存管理员令牌本身，只在服务端读取
+const ADMIN_COOKIE: &str = "eardo_admin";
+/// Cookie 有效期：一年
+const MAX_AGE_SECS: u64 = 365 * 24 * 60 * 60;
+
+/// 从请求头中读取指定 Cookie
+pub fn read_cookie(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value.to_string())
+}
+
+/// 在响应中写入 Cookie
+pub fn set_cookie(name: &str, value: &str, http_only: bool) {
+    let mut cookie = format!(
+        "{}={}; Path=/; Max-Age={}; SameSite=Lax",
+        name, value, MAX_AGE_SECS
+    );
+    if http_only {
+        cookie.push_str("; HttpOnly");
+    }
+    if let (Some(response), Ok(value)) = (
+        use_context::<ResponseOptions>(),
+        HeaderValue::from_str(&cookie),
+    ) {
+        response.append_header(header::SET_COOKIE, value);
+    }
+}
+
+/// 请求中已有的用户 ID，不会生成新的
+pub fn read_user_id(headers: &HeaderMap) -> Option<String> {
+    read_cookie(headers, USER_COOKIE)
+        .filter(|id| !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric()))
+}
+
+/// 当前用户 ID，首次访问时生成并写入 Cookie
+///
+/// 用户数据只凭 Cookie 识别，所以服务端函数请求 (非 GET) 必须同时带上有效的 CSRF token；
+/// SSR 渲染页面时直接调用服务端函数，请求是 GET，不需要校验。
+pub async fn user_id() -> Result<String, ServerFnError> {
+    let (method, headers): (Method, HeaderMap) = leptos_axum::extract().await?;
+    if method != Method::GET && !security::csrf_token_valid(&headers) {
+        return Err(ServerFnError::ServerError(
+            "请求校验失败，请刷新页面后重试".to_string(),
+        ));
+    }
+    if let Some(id) = read_user_id(&headers) {
+        return Ok(id);
+    }
+    let id = uuid::Uuid::new_v4().simple().to_string();
+    set_cookie(USER_COOKIE, &id, true);
+    Ok(id)
+}
+
+/// 发起请求的客户端地址
+///
+/// 默认取 TCP 连接的对端地址；部署在反向代理后面时设置 `EARDO_TRUST_PROXY=true`，
+/// 改用代理追加在 `X-Forwarded-For` 末尾的地址。未设置时不读取该请求头，避免客户端伪造
+pub async fn client_ip() -> Option<IpAddr> {
+    let headers: HeaderMap = leptos_axum::extract().await.ok()?;
+    if std::env::var("EARDO_TRUST_PROXY").is_ok_and(|v| v == "true" || v == "1") {
+        return headers
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .next_back()
+            .and_then(|ip| ip.trim().parse().ok());
+    }
+    let ConnectInfo(addr): ConnectInfo<SocketAddr> = leptos_axum::extract().await.ok()?;
+    Some(addr.ip())
+}
+
+/// 当前请求是否带有有效的管理员令牌
+pub async fn is_admin() -> bool {
+    let headers: HeaderMap = leptos_axum::extract().await.unwrap_or_default();
+    read_cookie(&headers, ADMIN_COOKIE).is_some_and(|token| security::admin_token_valid(&token))
+}
+
+/// 校验管理员令牌，正确时写入管理员 Cookie
+pub async fn sign_in_admin(token: &str) -> Result<(), ServerFnError> {
+    // 顺带完成 CSRF 校验
+    user_id().await?;
+    if !security::admin_token_valid(token) {
+        return Err(ServerFnError::ServerError("管理员令牌不正确".to_string()));
+    }
+    set_cookie(ADMIN_COOKIE, token, true);
+    Ok(())
+}
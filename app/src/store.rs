@@ -0,0 +1,133 @@
+//! 服务端 JSON 文件存储
+//!
+//! 数据保存在 `EARDO_DATA_DIR` (默认 `data/`) 下，每个文件对应一份可序列化的数据；
+//! 音频等二进制文件也存放在同一目录下。
+use serde::{de::DeserializeOwned, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, LazyLock, Mutex};
+
+/// 串行化写入，避免并发请求写坏同一个文件
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+/// 每个数据文件一把锁，保证 [`update`] 的 "读取 → 修改 → 写回" 不会与另一次修改交错
+static UPDATE_LOCKS: LazyLock<Mutex<HashMap<String, Arc<Mutex<()>>>>> =
+    LazyLock::new(Default::default);
+
+fn data_dir() -> PathBuf {
+    std::env::var("EARDO_DATA_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("data"))
+}
+
+/// `name` 为相对数据目录的路径 (不含扩展名)，如 `lexicon/global`
+fn path_of(name: &str) -> PathBuf {
+    data_dir().join(format!("{}.json", name))
+}
+
+/// 读取数据；文件不存在或内容损坏时返回默认值
+pub fn load<T: DeserializeOwned + Default>(name: &str) -> T {
+    std::fs::read(path_of(name))
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .unwrap_or_default()
+}
+
+/// 写入数据：先写临时文件再重命名，保证文件总是完整的
+pub fn save<T: Serialize>(name: &str, value: &T) -> std::io::Result<()> {
+    let path = path_of(name);
+    let _guard = WRITE_LOCK.lock().unwrap();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_vec_pretty(value)?)?;
+    std::fs::rename(tmp, path)
+}
+
+/// 在阻塞线程池中读取数据，供异步代码使用
+pub async fn read<T: DeserializeOwned + Default + Send + 'static>(name: &str) -> T {
+    let name = name.to_string();
+    tokio::task::spawn_blocking(move || load(&name))
+        .await
+        .unwrap_or_default()
+}
+
+/// 读取数据、交给 `f` 修改后写回；`f` 返回 `Err` 时不写回，原样返回这个错误
+///
+/// 同一文件的修改按顺序进行，并发请求不会互相覆盖对方的改动。
+/// 文件读写在阻塞线程池中执行，不占用异步运行时的线程。外层的 `Err` 是读写文件本身的错误
+pub async fn update<T, R, E>(
+    name: &str,
+    f: impl FnOnce(&mut T) -> Result<R, E> + Send + 'static,
+) -> std::io::Result<Result<R, E>>
+where
+    T: Serialize + DeserializeOwned + Default,
+    R: Send + 'static,
+    E: Send + 'static,
+{
+    let name = name.to_string();
+    tokio::task::spawn_blocking(move || {
+        let lock = UPDATE_LOCKS
+            .lock()
+            .unwrap()
+            .entry(name.clone())
+            .or_default()
+            .clone();
+        let _guard = lock.lock().unwrap();
+        let mut value: T = load(&name);
+        match f(&mut value) {
+            Ok(result) => save(&name, &value).map(|()| Ok(result)),
+            Err(e) => Ok(Err(e)),
+        }
+    })
+    .await
+    .map_err(std::io::Error::other)?
+}
+
+/// 读取二进制文件，`name` 为相对数据目录的路径 (含扩展名)
+pub fn load_file(name: &str) -> Option<Vec<u8>> {
+    std::fs::read(data_dir().join(name)).ok()
+}
+
+/// 写入二进制文件，同样先写临时文件再重命名
+pub fn save_file(name: &str, bytes: &[u8]) -> std::io::Result<()> {
+    let path = data_dir().join(name);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, bytes)?;
+    std::fs::rename(tmp, path)
+}
+
+/// 删除数据文件，文件本就不存在时也视为成功
+pub fn remove(name: &str) -> std::io::Result<()> {
+    remove_path(path_of(name))
+}
+
+/// 删除二进制文件，`name` 为相对数据目录的路径 (含扩展名)
+pub fn remove_file(name: &str) -> std::io::Result<()> {
+    remove_path(data_dir().join(name))
+}
+
+fn remove_path(path: PathBuf) -> std::io::Result<()> {
+    let _guard = WRITE_LOCK.lock().unwrap();
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// 列出目录下的数据文件名 (不含扩展名)，如 `list("shares")`
+pub fn list(dir: &str) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(data_dir().join(dir)) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            name.strip_suffix(".json").map(str::to_string)
+        })
+        .collect()
+}
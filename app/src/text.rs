@@ -0,0 +1,243 @@
+//! 文本切分工具：按句子切分长文本，供分段合成使用，并记录分段与句子的对应关系
+
+pub mod detect;
+pub mod lexicon;
+pub mod normalize;
+pub mod pinyin;
+pub mod ssml;
+pub mod subtitle;
+
+/// 句末标点 (中英文)
+const SENTENCE_ENDS: &[char] = &['。', '！', '？', '；', '!', '?', ';', '\n', '…'];
+
+/// 按句末标点切分文本，标点保留在句子末尾，空白句子会被丢弃
+///
+/// 英文句号只有后面跟空白时才算句末，避免把 `3.5` 之类的小数切开
+pub fn split_sentences(text: &str) -> Vec<String> {
+    let mut sentences = Vec::new();
+    let mut current = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        current.push(ch);
+        let is_end = SENTENCE_ENDS.contains(&ch)
+            || (ch == '.' && chars.peek().is_none_or(|c| c.is_whitespace()));
+        if is_end {
+            push_trimmed(&mut sentences, &mut current);
+        }
+    }
+    push_trimmed(&mut sentences, &mut current);
+    sentences
+}
+
+fn push_trimmed(out: &mut Vec<String>, current: &mut String) {
+    let trimmed = current.trim();
+    if !trimmed.is_empty() {
+        out.push(trimmed.to_string());
+    }
+    current.clear();
+}
+
+/// 一个合成分段，以及它由哪些句子组成
+#[derive(Clone, Debug, PartialEq)]
+pub struct Chunk {
+    pub text: String,
+    /// (句子序号, 该句落在本段中的字符数)；超长句子被硬切时会出现在相邻的多个分段里
+    pub sentences: Vec<(usize, usize)>,
+}
+
+/// 把句子合并成不超过 `max_chars` 个字符的分段；单句超长时按字符硬切
+pub fn chunk_text(text: &str, max_chars: usize) -> Vec<String> {
+    chunk_sentences(&split_sentences(text), max_chars)
+        .into_iter()
+        .map(|chunk| chunk.text)
+        .collect()
+}
+
+/// 同 [`chunk_text`]，但输入已切好的句子，并记录每个分段包含哪些句子
+pub fn chunk_sentences(sentences: &[String], max_chars: usize) -> Vec<Chunk> {
+    let max_chars = max_chars.max(1);
+    let mut chunks = Vec::new();
+    let mut current = Chunk {
+        text: String::new(),
+        sentences: Vec::new(),
+    };
+    let mut current_len = 0;
+
+    for (index, sentence) in sentences.iter().enumerate() {
+        let len = sentence.chars().count();
+        if current_len > 0 && current_len + len > max_chars {
+            chunks.push(std::mem::replace(
+                &mut current,
+                Chunk {
+                    text: String::new(),
+                    sentences: Vec::new(),
+                },
+            ));
+            current_len = 0;
+        }
+        if len > max_chars {
+            let chars: Vec<char> = sentence.chars().collect();
+            for piece in chars.chunks(max_chars) {
+                chunks.push(Chunk {
+                    text: piece.iter().collect(),
+                    sentences: vec![(index, piece.len())],
+                });
+            }
+            continue;
+        }
+        // 英文句子之间补回被 trim 掉的空格
+        if current.text.ends_with(|c: char| c.is_ascii())
+            && sentence.starts_with(|c: char| c.is_ascii())
+        {
+            current.text.push(' ');
+            current_len += 1;
+        }
+        current.text.push_str(sentence);
+        current.sentences.push((index, len));
+        current_len += len;
+    }
+    if !current.text.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// 对白脚本中的一行
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScriptLine {
+    pub speaker: String,
+    pub text: String,
+}
+
+/// 没有标注说话人的开头几行归给旁白
+pub const NARRATOR: &str = "旁白";
+
+/// 说话人名称最多几个字符，避免把正文里的冒号误认为说话人标记
+const MAX_SPEAKER_CHARS: usize = 16;
+
+/// 声明新说话人的行首标记，如 `@小明: 你好`
+pub const SPEAKER_MARKER: char = '@';
+
+/// 可以作为说话人名称：不含空白和 `/` (排除网址等)，且不超过 [`MAX_SPEAKER_CHARS`] 个字符
+fn valid_speaker(name: &str) -> bool {
+    !name.is_empty()
+        && name.chars().count() <= MAX_SPEAKER_CHARS
+        && !name.chars().any(|c| c.is_whitespace() || c == '/')
+}
+
+/// 解析 `说话人: 台词` 格式的脚本 (中英文冒号均可)
+///
+/// 正文里也常出现冒号 (「他说：……」、网址等)，所以只有已知的说话人才按前缀识别：
+/// `known` 中的名称 (通常是声线 ID 和名称)，或者此前用 `@名称:` 声明过的说话人。
+/// 没有说话人前缀的行沿用上一行的说话人，空行被忽略。
+pub fn parse_script(script: &str, known: &[String]) -> Vec<ScriptLine> {
+    let mut lines = Vec::new();
+    let mut declared: Vec<String> = Vec::new();
+    let mut speaker = NARRATOR.to_string();
+    for raw in script.lines() {
+        let raw = raw.trim();
+        if raw.is_empty() {
+            continue;
+        }
+        let (marked, body) = match raw.strip_prefix(SPEAKER_MARKER) {
+            Some(body) => (true, body),
+            None => (false, raw),
+        };
+        let prefix = body
+            .split_once([':', '：'])
+            .map(|(name, rest)| (name.trim(), rest))
+            .filter(|(name, _)| valid_speaker(name))
+            .filter(|(name, _)| marked || known.iter().chain(&declared).any(|known| known == name));
+        let text = match prefix {
+            Some((name, rest)) => {
+                if marked && !declared.iter().any(|d| d == name) {
+                    declared.push(name.to_string());
+                }
+                speaker = name.to_string();
+                rest.trim()
+            }
+            None => raw,
+        };
+        if !text.is_empty() {
+            lines.push(ScriptLine {
+                speaker: speaker.clone(),
+                text: text.to_string(),
+            });
+        }
+    }
+    lines
+}
+
+/// 按首次出现的顺序列出脚本中的说话人
+pub fn script_speakers(lines: &[ScriptLine]) -> Vec<String> {
+    let mut speakers: Vec<String> = Vec::new();
+    for line in lines {
+        if !speakers.contains(&line.speaker) {
+            speakers.push(line.speaker.clone());
+        }
+    }
+    speakers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn speakers_and_text(script: &str, known: &[&str]) -> Vec<(String, String)> {
+        let known: Vec<String> = known.iter().map(|k| k.to_string()).collect();
+        parse_script(script, &known)
+            .into_iter()
+            .map(|line| (line.speaker, line.text))
+            .collect()
+    }
+
+    fn line(speaker: &str, text: &str) -> (String, String) {
+        (speaker.to_string(), text.to_string())
+    }
+
+    #[test]
+    fn known_speakers_are_recognized() {
+        assert_eq!(
+            speakers_and_text(
+                "Cherry: 欢迎收听！\nEthan：今天聊聊夏天。\n继续说。",
+                &["Cherry", "Ethan"]
+            ),
+            vec![
+                line("Cherry", "欢迎收听！"),
+                line("Ethan", "今天聊聊夏天。"),
+                line("Ethan", "继续说。"),
+            ]
+        );
+    }
+
+    #[test]
+    fn urls_and_quotes_are_not_speakers() {
+        assert_eq!(
+            speakers_and_text("https://example.com\n他说：明天见", &["Cherry"]),
+            vec![
+                line(NARRATOR, "https://example.com"),
+                line(NARRATOR, "他说：明天见"),
+            ]
+        );
+    }
+
+    #[test]
+    fn marker_declares_new_speakers() {
+        assert_eq!(
+            speakers_and_text("@小明：你好\n旁白插一句\n小明: 再见", &[]),
+            vec![
+                line("小明", "你好"),
+                line("小明", "旁白插一句"),
+                line("小明", "再见"),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_marked_names_stay_in_text() {
+        assert_eq!(
+            speakers_and_text("@two words: hi\n@a/b: hi", &[]),
+            vec![line(NARRATOR, "@two words: hi"), line(NARRATOR, "@a/b: hi")]
+        );
+    }
+}
@@ -0,0 +1,195 @@
+//! 按文字系统识别语言，把中英日韩混排的文本切成单一语言的片段
+//!
+//! 汉字与假名同属一段，段内出现假名时按日文处理；数字、标点和空白跟随前面的片段。
+//! 夹在其他语言中的零星英文单词 (如缩写) 不单独成段，避免一句话被切得太碎。
+use crate::api::language::SynthesisLanguage;
+use serde::{Deserialize, Serialize};
+
+/// 英文片段至少包含的单词数，不足时并入相邻片段
+const MIN_LATIN_WORDS: usize = 2;
+
+/// 结束一段的标点：其后即使文字系统相同也另起一段，方便中日文分句识别
+const SEGMENT_ENDS: &[char] = &['。', '！', '？', '!', '?', '\n'];
+
+/// 单一语言的一段文本，所有片段按顺序拼接即为原文
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LanguageSpan {
+    pub text: String,
+    pub language: SynthesisLanguage,
+}
+
+/// 按字符所属的文字系统判断语言，数字、标点等返回 `None`
+fn char_language(c: char) -> Option<SynthesisLanguage> {
+    match c {
+        '\u{4E00}'..='\u{9FFF}' | '\u{3400}'..='\u{4DBF}' => Some(SynthesisLanguage::Chinese),
+        '\u{3040}'..='\u{30FF}' | '\u{31F0}'..='\u{31FF}' | '\u{FF66}'..='\u{FF9F}' => {
+            Some(SynthesisLanguage::Japanese)
+        }
+        '\u{AC00}'..='\u{D7AF}' | '\u{1100}'..='\u{11FF}' | '\u{3130}'..='\u{318F}' => {
+            Some(SynthesisLanguage::Korean)
+        }
+        c if c.is_ascii_alphabetic() || ('\u{00C0}'..='\u{024F}').contains(&c) => {
+            Some(SynthesisLanguage::English)
+        }
+        _ => None,
+    }
+}
+
+/// 汉字和假名可以出现在同一段里
+fn same_script(a: SynthesisLanguage, b: SynthesisLanguage) -> bool {
+    let cjk = |l| matches!(l, SynthesisLanguage::Chinese | SynthesisLanguage::Japanese);
+    a == b || (cjk(a) && cjk(b))
+}
+
+struct Run {
+    text: String,
+    language: SynthesisLanguage,
+    /// 英文单词数
+    words: usize,
+}
+
+/// 把文本切成单一语言的片段；没有任何文字时整段作为 [`SynthesisLanguage::Auto`]
+pub fn segment(text: &str) -> Vec<LanguageSpan> {
+    let mut runs: Vec<Run> = Vec::new();
+    // 第一个文字之前的数字、标点
+    let mut leading = String::new();
+    let mut closed = false;
+    let mut in_word = false;
+    for c in text.chars() {
+        let Some(language) = char_language(c) else {
+            match runs.last_mut() {
+                Some(run) => run.text.push(c),
+                None => leading.push(c),
+            }
+            closed |= SEGMENT_ENDS.contains(&c);
+            in_word = false;
+            continue;
+        };
+        let starts_word = language == SynthesisLanguage::English && !in_word;
+        in_word = language == SynthesisLanguage::English;
+        match runs.last_mut() {
+            Some(run) if !closed && same_script(run.language, language) => {
+                if language == SynthesisLanguage::Japanese {
+                    run.language = language;
+                }
+                run.text.push(c);
+                run.words += usize::from(starts_word);
+            }
+            _ => {
+                let mut text = std::mem::take(&mut leading);
+                text.push(c);
+                runs.push(Run {
+                    text,
+                    language,
+                    words: usize::from(starts_word),
+                });
+            }
+        }
+        closed = false;
+    }
+
+    // 零星的英文并入前一段 (开头的并入后一段)，再合并相邻的同语言片段
+    let total = runs.len();
+    let mut spans: Vec<LanguageSpan> = Vec::new();
+    let mut carry = String::new();
+    for run in runs {
+        if total > 1 && run.language == SynthesisLanguage::English && run.words < MIN_LATIN_WORDS {
+            match spans.last_mut() {
+                Some(previous) => previous.text.push_str(&run.text),
+                None => carry.push_str(&run.text),
+            }
+            continue;
+        }
+        let text = std::mem::take(&mut carry) + &run.text;
+        match spans.last_mut() {
+            Some(previous) if previous.language == run.language => previous.text.push_str(&text),
+            _ => spans.push(LanguageSpan {
+                text,
+                language: run.language,
+            }),
+        }
+    }
+    if !carry.is_empty() {
+        spans.push(LanguageSpan {
+            text: carry,
+            language: SynthesisLanguage::English,
+        });
+    }
+    if spans.is_empty() && !leading.trim().is_empty() {
+        spans.push(LanguageSpan {
+            text: leading,
+            language: SynthesisLanguage::Auto,
+        });
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use SynthesisLanguage::*;
+
+    fn languages(text: &str) -> Vec<(String, SynthesisLanguage)> {
+        segment(text)
+            .into_iter()
+            .map(|span| (span.text, span.language))
+            .collect()
+    }
+
+    #[test]
+    fn mixed_chinese_and_english() {
+        assert_eq!(
+            languages("今天我们来讲 machine learning 的基础。"),
+            vec![
+                ("今天我们来讲 ".to_string(), Chinese),
+                ("machine learning ".to_string(), English),
+                ("的基础。".to_string(), Chinese),
+            ]
+        );
+    }
+
+    #[test]
+    fn short_latin_words_stay_inline() {
+        assert_eq!(
+            languages("AI 正在合成 TTS 音频"),
+            vec![("AI 正在合成 TTS 音频".to_string(), Chinese)]
+        );
+    }
+
+    #[test]
+    fn kana_marks_japanese() {
+        assert_eq!(
+            languages("你好。今日は良い天気ですね。"),
+            vec![
+                ("你好。".to_string(), Chinese),
+                ("今日は良い天気ですね。".to_string(), Japanese),
+            ]
+        );
+    }
+
+    #[test]
+    fn korean_and_english() {
+        assert_eq!(
+            languages("안녕하세요, nice to meet you!"),
+            vec![
+                ("안녕하세요, ".to_string(), Korean),
+                ("nice to meet you!".to_string(), English),
+            ]
+        );
+    }
+
+    #[test]
+    fn spans_cover_the_whole_text() {
+        for text in [
+            "2026 年 Hello world",
+            "  hi ",
+            "123",
+            "Leptos 框架 and Rust",
+        ] {
+            let joined: String = segment(text).into_iter().map(|span| span.text).collect();
+            assert_eq!(joined, text);
+        }
+        assert_eq!(languages("123"), vec![("123".to_string(), Auto)]);
+        assert!(segment("").is_empty());
+    }
+}
@@ -0,0 +1,177 @@
+//! 发音词典：在合成前把容易读错的词替换成指定写法
+use serde::{Deserialize, Serialize};
+
+use super::pinyin;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplacementKind {
+    /// 直接替换为另一段文本，如 "重庆" → "崇庆"、"SQL" → "sequel"
+    #[default]
+    Text,
+    /// 标注拼音，如 "重庆" → "chong2 qing4"，合成时换成同音字 (见 [`pinyin`])
+    Pinyin,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LexiconEntry {
+    pub word: String,
+    pub replacement: String,
+    #[serde(default)]
+    pub kind: ReplacementKind,
+}
+
+/// 一条词典规则在文本中的命中情况
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AppliedEntry {
+    pub word: String,
+    pub replacement: String,
+    pub kind: ReplacementKind,
+    pub count: usize,
+    /// 替换内容无法使用 (如早先保存、无法识别的拼音)，实际按原文朗读
+    pub skipped: bool,
+}
+
+/// 合并用户词典与全局词典：同一个词以用户词典为准
+pub fn merge(user: &[LexiconEntry], global: &[LexiconEntry]) -> Vec<LexiconEntry> {
+    let mut merged = user.to_vec();
+    for entry in global {
+        if !merged.iter().any(|e| e.word == entry.word) {
+            merged.push(entry.clone());
+        }
+    }
+    merged
+}
+
+/// 校验并规范化词条
+pub fn normalize_entry(entry: LexiconEntry) -> Result<LexiconEntry, String> {
+    let word = entry.word.trim().to_string();
+    let replacement = entry.replacement.trim().to_string();
+    if word.is_empty() {
+        return Err("词条不能为空".to_string());
+    }
+    if replacement.is_empty() {
+        return Err(format!("「{}」的替换内容不能为空", word));
+    }
+    if entry.kind == ReplacementKind::Pinyin {
+        if let Err(syllable) = pinyin::to_homophones(&replacement) {
+            return Err(format!(
+                "「{}」的拼音「{}」无法识别，应为空格分隔的带调拼音，如 chong2 qing4",
+                word, syllable
+            ));
+        }
+    }
+    Ok(LexiconEntry {
+        word,
+        replacement,
+        kind: entry.kind,
+    })
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric()
+}
+
+/// 在 `text` 的 `at` 处尝试匹配 `word`：英文不区分大小写且要求单词边界
+fn matches_at(text: &str, at: usize, word: &str) -> bool {
+    let Some(candidate) = text.get(at..at + word.len()) else {
+        return false;
+    };
+    if !candidate.eq_ignore_ascii_case(word) {
+        return false;
+    }
+    let before = text[..at].chars().next_back();
+    let after = text[at + word.len()..].chars().next();
+    let starts_ascii = word.starts_with(is_word_char);
+    let ends_ascii = word.ends_with(is_word_char);
+    // 英文词的开头/结尾紧挨着其他字母数字时，说明只是更长单词的一部分
+    let joined_before = starts_ascii && before.is_some_and(is_word_char);
+    let joined_after = ends_ascii && after.is_some_and(is_word_char);
+    !joined_before && !joined_after
+}
+
+/// 应用词典：长词优先匹配，返回替换后的文本和命中记录
+///
+/// 接入的服务商都不支持音标，拼音词条换成读音相同的字再合成。
+pub fn apply(text: &str, entries: &[LexiconEntry]) -> (String, Vec<AppliedEntry>) {
+    let mut sorted: Vec<&LexiconEntry> = entries.iter().filter(|e| !e.word.is_empty()).collect();
+    sorted.sort_by_key(|e| std::cmp::Reverse(e.word.len()));
+
+    let mut out = String::with_capacity(text.len());
+    let mut applied: Vec<AppliedEntry> = Vec::new();
+    let mut pos = 0;
+    while pos < text.len() {
+        let hit = sorted.iter().find(|e| matches_at(text, pos, &e.word));
+        let Some(entry) = hit else {
+            let ch = text[pos..].chars().next().unwrap_or_default();
+            out.push(ch);
+            pos += ch.len_utf8().max(1);
+            continue;
+        };
+
+        let replacement = match entry.kind {
+            ReplacementKind::Text => Some(entry.replacement.clone()),
+            ReplacementKind::Pinyin => pinyin::to_homophones(&entry.replacement).ok(),
+        };
+        let skipped = replacement.is_none();
+        out.push_str(
+            replacement
+                .as_deref()
+                .unwrap_or(&text[pos..pos + entry.word.len()]),
+        );
+        match applied.iter_mut().find(|a| a.word == entry.word) {
+            Some(a) => a.count += 1,
+            None => applied.push(AppliedEntry {
+                word: entry.word.clone(),
+                replacement: entry.replacement.clone(),
+                kind: entry.kind,
+                count: 1,
+                skipped,
+            }),
+        }
+        pos += entry.word.len();
+    }
+    (out, applied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(word: &str, replacement: &str, kind: ReplacementKind) -> LexiconEntry {
+        LexiconEntry {
+            word: word.to_string(),
+            replacement: replacement.to_string(),
+            kind,
+        }
+    }
+
+    #[test]
+    fn pinyin_entries_are_read_as_homophones() {
+        let entries = [
+            entry("重庆", "chong2 qing4", ReplacementKind::Pinyin),
+            entry("SQL", "sequel", ReplacementKind::Text),
+        ];
+        let (text, applied) = apply("重庆的sql和SQLite", &entries);
+        assert_eq!(text, "虫庆的sequel和SQLite");
+        assert_eq!(applied.len(), 2);
+        assert!(applied.iter().all(|a| a.count == 1 && !a.skipped));
+    }
+
+    #[test]
+    fn unreadable_pinyin_is_rejected_on_save_and_skipped_on_apply() {
+        assert_eq!(
+            normalize_entry(entry("重庆", "chong qing", ReplacementKind::Pinyin)),
+            Err(
+                "「重庆」的拼音「chong」无法识别，应为空格分隔的带调拼音，如 chong2 qing4"
+                    .to_string()
+            )
+        );
+        let (text, applied) = apply(
+            "重庆",
+            &[entry("重庆", "chong qing", ReplacementKind::Pinyin)],
+        );
+        assert_eq!(text, "重庆");
+        assert!(applied[0].skipped);
+    }
+}
@@ -0,0 +1,669 @@
+//! 文本规范化：把数字、日期、金额、百分比、电话号码和网址改写成适合朗读的文字
+//!
+//! 例如中文语境下 `2026-10-17` → `二〇二六年十月十七日`、`¥35.5` → `三十五点五元`、
+//! `12.5%` → `百分之十二点五`，英文语境下 `2026-10-17` → `October 17, 2026`。
+//! 纯函数实现，不依赖服务端环境。
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NormalizeLanguage {
+    /// 根据文本中中文字符的占比自动判断
+    #[default]
+    Auto,
+    Chinese,
+    English,
+}
+
+/// 每次请求可单独配置的规范化选项
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NormalizeOptions {
+    pub enabled: bool,
+    pub language: NormalizeLanguage,
+    pub dates: bool,
+    pub times: bool,
+    pub currency: bool,
+    pub percentages: bool,
+    pub phone_numbers: bool,
+    pub urls: bool,
+    pub numbers: bool,
+}
+
+impl Default for NormalizeOptions {
+    fn default() -> Self {
+        NormalizeOptions {
+            enabled: true,
+            language: NormalizeLanguage::Auto,
+            dates: true,
+            times: true,
+            currency: true,
+            percentages: true,
+            phone_numbers: true,
+            urls: true,
+            numbers: true,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Lang {
+    Zh,
+    En,
+}
+
+const ZH_DIGITS: [&str; 10] = ["零", "一", "二", "三", "四", "五", "六", "七", "八", "九"];
+const EN_MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// 按选项规范化文本
+pub fn normalize(text: &str, options: &NormalizeOptions) -> String {
+    if !options.enabled {
+        return text.to_string();
+    }
+    let lang = match options.language {
+        NormalizeLanguage::Chinese => Lang::Zh,
+        NormalizeLanguage::English => Lang::En,
+        NormalizeLanguage::Auto => detect_language(text),
+    };
+
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < chars.len() {
+        // 数字、符号只在不紧跟字母数字的位置开始匹配，避免切开 "abc123" 之类的标识符
+        let boundary = i == 0 || !chars[i - 1].is_ascii_alphanumeric();
+        let matched = if boundary {
+            [
+                (options.urls, match_url as Matcher),
+                (options.dates, match_date),
+                (options.times, match_time),
+                (options.currency, match_currency),
+                (options.percentages, match_percent),
+                (options.phone_numbers, match_phone),
+                (options.numbers, match_number),
+            ]
+            .into_iter()
+            .filter(|(on, _)| *on)
+            .find_map(|(_, matcher)| matcher(&chars[i..], lang))
+        } else {
+            None
+        };
+
+        match matched {
+            Some((len, replacement)) => {
+                out.push_str(&replacement);
+                i += len;
+            }
+            None => {
+                out.push(chars[i]);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+/// 中文字符占比足够高时按中文处理
+fn detect_language(text: &str) -> Lang {
+    let cjk = text.chars().filter(|c| is_cjk(*c)).count();
+    let latin = text.chars().filter(char::is_ascii_alphabetic).count();
+    if cjk > 0 && cjk * 3 >= latin {
+        Lang::Zh
+    } else {
+        Lang::En
+    }
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c, '\u{4E00}'..='\u{9FFF}' | '\u{3400}'..='\u{4DBF}')
+}
+
+/// 匹配成功时返回 (消耗的字符数, 替换文本)
+type Matcher = fn(&[char], Lang) -> Option<(usize, String)>;
+
+/// 从开头读取连续数字，返回 (数字串, 长度)
+fn take_digits(chars: &[char]) -> (String, usize) {
+    let digits: String = chars.iter().take_while(|c| c.is_ascii_digit()).collect();
+    let len = digits.len();
+    (digits, len)
+}
+
+/// 匹配结束处不能紧跟数字，否则说明只匹配到了更长数字的一部分
+fn ends_cleanly(chars: &[char], len: usize) -> bool {
+    chars.get(len).is_none_or(|c| !c.is_ascii_digit())
+}
+
+// --- 读数 ---
+
+/// 四位以内的中文读法，如 1005 → 一千零五
+fn zh_section(n: u64) -> String {
+    let digits = [n / 1000 % 10, n / 100 % 10, n / 10 % 10, n % 10];
+    let units = ["千", "百", "十", ""];
+    let mut out = String::new();
+    let mut started = false;
+    let mut zero = false;
+    for (d, unit) in digits.into_iter().zip(units) {
+        if d == 0 {
+            zero = started;
+            continue;
+        }
+        if zero {
+            out.push('零');
+            zero = false;
+        }
+        out.push_str(ZH_DIGITS[d as usize]);
+        out.push_str(unit);
+        started = true;
+    }
+    out
+}
+
+/// 整数的中文读法，支持到万亿级
+fn zh_cardinal(n: u64) -> String {
+    if n == 0 {
+        return "零".to_string();
+    }
+    let units = ["", "万", "亿", "万亿"];
+    let mut groups = Vec::new();
+    let mut rest = n;
+    while rest > 0 {
+        groups.push(rest % 10_000);
+        rest /= 10_000;
+    }
+
+    let mut out = String::new();
+    let mut pending_zero = false;
+    for (idx, group) in groups.iter().enumerate().rev() {
+        if *group == 0 {
+            pending_zero = !out.is_empty();
+            continue;
+        }
+        if !out.is_empty() && (pending_zero || *group < 1000) {
+            out.push('零');
+        }
+        out.push_str(&zh_section(*group));
+        out.push_str(units[idx.min(units.len() - 1)]);
+        pending_zero = false;
+    }
+    // 口语习惯：一十五 → 十五
+    match out.strip_prefix("一十") {
+        Some(rest) => format!("十{}", rest),
+        None => out,
+    }
+}
+
+/// 逐位读数字，`yao` 为真时 1 读作 "幺" (电话号码习惯)
+fn zh_digits(digits: &str, yao: bool) -> String {
+    digits
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .map(|d| {
+            if yao && d == 1 {
+                "幺"
+            } else {
+                ZH_DIGITS[d as usize]
+            }
+        })
+        .collect()
+}
+
+/// 年份逐位读，0 写作 "〇"
+fn zh_year(digits: &str) -> String {
+    digits
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .map(|d| if d == 0 { "〇" } else { ZH_DIGITS[d as usize] })
+        .collect()
+}
+
+/// 数字串 (可含小数点) 的中文读法；以 0 开头或过长的整数逐位读
+fn zh_number(int_part: &str, frac_part: Option<&str>) -> String {
+    let int_text = if int_part.len() > 1 && int_part.starts_with('0') || int_part.len() > 16 {
+        zh_digits(int_part, false)
+    } else {
+        zh_cardinal(int_part.parse().unwrap_or(0))
+    };
+    match frac_part {
+        Some(frac) if !frac.is_empty() => format!("{}点{}", int_text, zh_digits(frac, false)),
+        _ => int_text,
+    }
+}
+
+// --- 匹配器 ---
+
+/// 网址：去掉协议，中文读作 "点"/"斜杠"，英文读作 "dot"/"slash"
+fn match_url(chars: &[char], lang: Lang) -> Option<(usize, String)> {
+    let text: String = chars.iter().take(8).collect();
+    let scheme_len = ["https://", "http://", "www."]
+        .iter()
+        .find(|p| text.to_ascii_lowercase().starts_with(*p))
+        .map(|p| if p.starts_with("www") { 0 } else { p.len() })?;
+
+    let len = chars
+        .iter()
+        .take_while(|c| c.is_ascii_graphic() && !matches!(c, '"' | '\'' | '<' | '>' | ')'))
+        .count();
+    // 句末的标点不属于网址
+    let len = len
+        - chars[..len]
+            .iter()
+            .rev()
+            .take_while(|c| matches!(c, '.' | ',' | '!' | '?' | ';' | ':'))
+            .count();
+    let body: String = chars[scheme_len..len].iter().collect();
+    if body.is_empty() {
+        return None;
+    }
+
+    let (dot, slash) = match lang {
+        Lang::Zh => (" 点 ", " 斜杠 "),
+        Lang::En => (" dot ", " slash "),
+    };
+    let spoken = body
+        .trim_end_matches('/')
+        .replace('.', dot)
+        .replace('/', slash);
+    Some((len, spoken))
+}
+
+/// 日期：YYYY-MM-DD、YYYY/MM/DD、YYYY.MM.DD 与 YYYY年M月D日
+fn match_date(chars: &[char], lang: Lang) -> Option<(usize, String)> {
+    let (year, y_len) = take_digits(chars);
+    if y_len != 4 {
+        return None;
+    }
+    let sep = *chars.get(4)?;
+    if !matches!(sep, '-' | '/' | '.' | '年') {
+        return None;
+    }
+    let (month, m_len) = take_digits(&chars[5..]);
+    if !(1..=2).contains(&m_len) {
+        return None;
+    }
+    let month_sep_at = 5 + m_len;
+    let expected_sep = if sep == '年' { '月' } else { sep };
+    if chars.get(month_sep_at) != Some(&expected_sep) {
+        return None;
+    }
+    let (day, d_len) = take_digits(&chars[month_sep_at + 1..]);
+    if !(1..=2).contains(&d_len) {
+        return None;
+    }
+    let mut len = month_sep_at + 1 + d_len;
+    if sep == '年' && matches!(chars.get(len), Some('日') | Some('号')) {
+        len += 1;
+    }
+    if !ends_cleanly(chars, len) {
+        return None;
+    }
+
+    let month: u64 = month.parse().ok()?;
+    let day: u64 = day.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    let spoken = match lang {
+        Lang::Zh => format!(
+            "{}年{}月{}日",
+            zh_year(&year),
+            zh_cardinal(month),
+            zh_cardinal(day)
+        ),
+        Lang::En => format!("{} {}, {}", EN_MONTHS[month as usize - 1], day, year),
+    };
+    Some((len, spoken))
+}
+
+/// 时间：H:MM 或 HH:MM(:SS)，仅中文改写
+fn match_time(chars: &[char], lang: Lang) -> Option<(usize, String)> {
+    if lang == Lang::En {
+        return None;
+    }
+    let (hour, h_len) = take_digits(chars);
+    if !(1..=2).contains(&h_len) || chars.get(h_len) != Some(&':') {
+        return None;
+    }
+    let (minute, m_len) = take_digits(&chars[h_len + 1..]);
+    if m_len != 2 {
+        return None;
+    }
+    let mut len = h_len + 1 + m_len;
+    let mut second = None;
+    if chars.get(len) == Some(&':') {
+        let (s, s_len) = take_digits(&chars[len + 1..]);
+        if s_len == 2 {
+            second = Some(s);
+            len += 1 + s_len;
+        }
+    }
+    if !ends_cleanly(chars, len) {
+        return None;
+    }
+
+    let hour: u64 = hour.parse().ok()?;
+    let minute: u64 = minute.parse().ok()?;
+    let second: Option<u64> = second.and_then(|s| s.parse().ok());
+    if hour > 23 || minute > 59 || second.is_some_and(|s| s > 59) {
+        return None;
+    }
+    let mut spoken = format!("{}点", zh_cardinal(hour));
+    match (minute, second) {
+        (0, None) => spoken.push('整'),
+        (m, _) => {
+            if m > 0 && m < 10 {
+                spoken.push('零');
+            }
+            if m > 0 || second.is_some() {
+                spoken.push_str(&format!("{}分", zh_cardinal(m)));
+            }
+        }
+    }
+    if let Some(s) = second {
+        spoken.push_str(&format!("{}秒", zh_cardinal(s)));
+    }
+    Some((len, spoken))
+}
+
+/// 读取 "1,234.56" 形式的数字，返回 (整数部分, 小数部分, 长度)
+fn take_amount(chars: &[char]) -> Option<(String, Option<String>, usize)> {
+    let (mut int_part, mut len) = take_digits(chars);
+    if len == 0 {
+        return None;
+    }
+    // 千分位逗号：后面必须恰好跟三位数字
+    while chars.get(len) == Some(&',') {
+        let (group, g_len) = take_digits(&chars[len + 1..]);
+        if g_len != 3 {
+            break;
+        }
+        int_part.push_str(&group);
+        len += 1 + g_len;
+    }
+    let mut frac = None;
+    if chars.get(len) == Some(&'.') {
+        let (f, f_len) = take_digits(&chars[len + 1..]);
+        if f_len > 0 {
+            frac = Some(f);
+            len += 1 + f_len;
+        }
+    }
+    Some((int_part, frac, len))
+}
+
+/// 金额：¥35.5、￥35.5、$1,200、€9.99、£5
+fn match_currency(chars: &[char], lang: Lang) -> Option<(usize, String)> {
+    let (zh_unit, en_unit, en_cents) = match chars.first()? {
+        '¥' | '￥' => ("元", "yuan", None),
+        '$' => ("美元", "dollars", Some("cents")),
+        '€' => ("欧元", "euros", Some("cents")),
+        '£' => ("英镑", "pounds", Some("pence")),
+        _ => return None,
+    };
+    let (int_part, frac, len) = take_amount(&chars[1..])?;
+    let len = len + 1;
+
+    let spoken = match lang {
+        Lang::Zh => format!("{}{}", zh_number(&int_part, frac.as_deref()), zh_unit),
+        Lang::En => match (frac, en_cents) {
+            // 两位小数按 "分" 读：$35.50 → 35 dollars 50 cents
+            (Some(f), Some(cents)) if f.len() == 2 => {
+                let cents_value: u64 = f.parse().ok()?;
+                if cents_value == 0 {
+                    format!("{} {}", int_part, en_unit)
+                } else {
+                    format!("{} {} {} {}", int_part, en_unit, cents_value, cents)
+                }
+            }
+            (Some(f), _) => format!("{}.{} {}", int_part, f, en_unit),
+            (None, _) => format!("{} {}", int_part, en_unit),
+        },
+    };
+    Some((len, spoken))
+}
+
+/// 百分比：12.5% → 百分之十二点五，-3% → 负百分之三
+fn match_percent(chars: &[char], lang: Lang) -> Option<(usize, String)> {
+    let negative = chars.first() == Some(&'-');
+    let start = negative as usize;
+    let (int_part, frac, len) = take_amount(&chars[start..])?;
+    let len = start + len;
+    if !matches!(chars.get(len), Some('%') | Some('％')) {
+        return None;
+    }
+    let sign = if negative { "负" } else { "" };
+    let spoken = match lang {
+        Lang::Zh => format!("{}百分之{}", sign, zh_number(&int_part, frac.as_deref())),
+        Lang::En => {
+            let minus = if negative { "minus " } else { "" };
+            match frac {
+                Some(f) => format!("{}{}.{} percent", minus, int_part, f),
+                None => format!("{}{} percent", minus, int_part),
+            }
+        }
+    };
+    Some((len + 1, spoken))
+}
+
+/// 电话号码：手机号 1[3-9]xxxxxxxxx、带区号的固话 0xx-xxxxxxxx、400/800 热线，可带 +86
+fn match_phone(chars: &[char], lang: Lang) -> Option<(usize, String)> {
+    let mut pos = 0;
+    let mut prefix = "";
+    let head: String = chars.iter().take(3).collect();
+    if head == "+86" {
+        pos = 3;
+        prefix = match lang {
+            Lang::Zh => "加八六，",
+            Lang::En => "plus 8 6, ",
+        };
+        if matches!(chars.get(pos), Some(' ') | Some('-')) {
+            pos += 1;
+        }
+    }
+
+    // 按分隔符切成若干组
+    let mut groups: Vec<String> = Vec::new();
+    loop {
+        let (group, g_len) = take_digits(&chars[pos..]);
+        if g_len == 0 {
+            break;
+        }
+        groups.push(group);
+        pos += g_len;
+        let next_is_digit = chars.get(pos + 1).is_some_and(|c| c.is_ascii_digit());
+        if matches!(chars.get(pos), Some('-') | Some(' ')) && next_is_digit && groups.len() < 3 {
+            pos += 1;
+        } else {
+            break;
+        }
+    }
+    // 空格分隔的数字只在确实拼成电话号码时才算，否则回退到只看第一组
+    let joined: String = groups.concat();
+    let is_mobile = joined.len() == 11
+        && joined.starts_with('1')
+        && joined.as_bytes()[1].is_ascii_digit()
+        && (b'3'..=b'9').contains(&joined.as_bytes()[1]);
+    let is_landline = groups.len() == 2
+        && groups[0].starts_with('0')
+        && (3..=4).contains(&groups[0].len())
+        && (7..=8).contains(&groups[1].len());
+    let is_hotline = groups.len() == 3
+        && (groups[0] == "400" || groups[0] == "800")
+        && groups[1].len() == 3
+        && groups[2].len() == 4;
+    if !(is_mobile && (groups.len() == 1 || groups.len() == 3) || is_landline || is_hotline) {
+        return None;
+    }
+    if !ends_cleanly(chars, pos) {
+        return None;
+    }
+
+    let spoken_groups: Vec<String> = groups
+        .iter()
+        .map(|g| match lang {
+            Lang::Zh => zh_digits(g, true),
+            Lang::En => g.chars().map(String::from).collect::<Vec<_>>().join(" "),
+        })
+        .collect();
+    let separator = match lang {
+        Lang::Zh => "，",
+        Lang::En => ", ",
+    };
+    Some((pos, format!("{}{}", prefix, spoken_groups.join(separator))))
+}
+
+/// 普通数字：中文改写为读法，英文保持原样交给 TTS
+fn match_number(chars: &[char], lang: Lang) -> Option<(usize, String)> {
+    if lang == Lang::En {
+        return None;
+    }
+    let negative = chars.first() == Some(&'-');
+    let start = negative as usize;
+    let (int_part, frac, len) = take_amount(&chars[start..])?;
+    // "-" 后面不是数字时不算负号
+    if negative && len == 0 {
+        return None;
+    }
+    let sign = if negative { "负" } else { "" };
+    Some((
+        start + len,
+        format!("{}{}", sign, zh_number(&int_part, frac.as_deref())),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zh(text: &str) -> String {
+        normalize(
+            text,
+            &NormalizeOptions {
+                language: NormalizeLanguage::Chinese,
+                ..Default::default()
+            },
+        )
+    }
+
+    fn en(text: &str) -> String {
+        normalize(
+            text,
+            &NormalizeOptions {
+                language: NormalizeLanguage::English,
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn cardinal_numbers() {
+        assert_eq!(zh_cardinal(0), "零");
+        assert_eq!(zh_cardinal(10), "十");
+        assert_eq!(zh_cardinal(15), "十五");
+        assert_eq!(zh_cardinal(110), "一百一十");
+        assert_eq!(zh_cardinal(1005), "一千零五");
+        assert_eq!(zh_cardinal(1050), "一千零五十");
+        assert_eq!(zh_cardinal(10086), "一万零八十六");
+        assert_eq!(zh_cardinal(100000), "十万");
+        assert_eq!(zh_cardinal(100_000_001), "一亿零一");
+        assert_eq!(zh_cardinal(120_030_000), "一亿二千零三万");
+    }
+
+    #[test]
+    fn dates() {
+        assert_eq!(zh("2026-10-17"), "二〇二六年十月十七日");
+        assert_eq!(zh("今天是2026/1/5。"), "今天是二〇二六年一月五日。");
+        assert_eq!(zh("2026年10月17日"), "二〇二六年十月十七日");
+        assert_eq!(en("2026-10-17"), "October 17, 2026");
+        // 月份不合法时不当作日期
+        assert!(!zh("2026-13-01").contains('年'));
+    }
+
+    #[test]
+    fn times() {
+        assert_eq!(zh("10:30出发"), "十点三十分出发");
+        assert_eq!(zh("8:05"), "八点零五分");
+        assert_eq!(zh("9:00"), "九点整");
+        assert_eq!(en("at 10:30"), "at 10:30");
+    }
+
+    #[test]
+    fn currency() {
+        assert_eq!(zh("¥35.5"), "三十五点五元");
+        assert_eq!(zh("￥1,200"), "一千二百元");
+        assert_eq!(zh("$9.99"), "九点九九美元");
+        assert_eq!(en("$35.50"), "35 dollars 50 cents");
+        assert_eq!(en("$35.00"), "35 dollars");
+        assert_eq!(en("¥35.5"), "35.5 yuan");
+    }
+
+    #[test]
+    fn percentages() {
+        assert_eq!(zh("增长12.5%"), "增长百分之十二点五");
+        assert_eq!(zh("-3%"), "负百分之三");
+        assert_eq!(en("up 50%"), "up 50 percent");
+    }
+
+    #[test]
+    fn phone_numbers() {
+        assert_eq!(zh("13812345678"), "幺三八幺二三四五六七八");
+        assert_eq!(zh("138-1234-5678"), "幺三八，幺二三四，五六七八");
+        assert_eq!(zh("010-12345678"), "零幺零，幺二三四五六七八");
+        assert_eq!(zh("400-800-8888"), "四零零，八零零，八八八八");
+        assert_eq!(zh("+86 13812345678"), "加八六，幺三八幺二三四五六七八");
+        assert_eq!(en("13812345678"), "1 3 8 1 2 3 4 5 6 7 8");
+    }
+
+    #[test]
+    fn urls() {
+        assert_eq!(
+            zh("访问 https://example.com/docs。"),
+            "访问 example 点 com 斜杠 docs。"
+        );
+        assert_eq!(en("see www.example.com."), "see www dot example dot com.");
+    }
+
+    #[test]
+    fn plain_numbers() {
+        assert_eq!(zh("共3个"), "共三个");
+        assert_eq!(zh("温度-5度"), "温度负五度");
+        assert_eq!(zh("编号007"), "编号零零七");
+        assert_eq!(zh("圆周率3.14"), "圆周率三点一四");
+        // 紧跟字母的数字属于标识符，不改写
+        assert_eq!(zh("型号A320"), "型号A320");
+    }
+
+    #[test]
+    fn auto_language() {
+        let auto = NormalizeOptions::default();
+        assert_eq!(normalize("花了¥35.5", &auto), "花了三十五点五元");
+        assert_eq!(normalize("It costs $5.00", &auto), "It costs 5 dollars");
+    }
+
+    #[test]
+    fn disabled_or_partial() {
+        let off = NormalizeOptions {
+            enabled: false,
+            ..Default::default()
+        };
+        assert_eq!(normalize("2026-10-17", &off), "2026-10-17");
+
+        let no_numbers = NormalizeOptions {
+            language: NormalizeLanguage::Chinese,
+            numbers: false,
+            ..Default::default()
+        };
+        assert_eq!(normalize("共3个，12%", &no_numbers), "共3个，百分之十二");
+    }
+}
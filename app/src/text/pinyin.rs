@@ -0,0 +1,464 @@
+//! 带调拼音转同音字
+//!
+//! 接入的服务商都不支持按音标朗读，所以把 `chong2 qing4` 这样的拼音换成读音相同的常用字
+//! (如「虫庆」)，再当作普通文本合成。每个音节只挑一个没有多音的字，没有合适用字的音节会报错。
+
+/// 每行：`音节 一声二声三声四声[轻声]`，`〇` 表示该声调没有合适的字；ü 写作 v
+const TABLE: &str = "\
+a 阿〇〇〇啊
+ai 哀皑矮爱
+an 安〇俺岸
+ang 肮昂〇盎
+ao 凹熬袄傲
+ba 八拔把爸吧
+bai 掰白百拜
+ban 班〇板半
+bang 帮〇绑棒
+bao 包雹宝报
+bei 杯〇北被
+ben 奔〇本笨
+beng 崩甭〇蹦
+bi 逼鼻比必
+bian 边〇扁变
+biao 标〇表〇
+bie 憋别瘪〇
+bin 宾〇〇鬓
+bing 冰〇饼病
+bo 波博跛簸
+bu 〇〇补布
+ca 擦〇〇〇
+cai 猜才彩菜
+can 参残惨灿
+cang 仓藏〇〇
+cao 糙曹草〇
+ce 〇〇〇册
+cen 〇岑〇〇
+ceng 噌层〇蹭
+cha 插茶〇岔
+chai 拆柴〇〇
+chan 搀缠产颤
+chang 昌肠厂唱
+chao 超潮吵〇
+che 车〇扯彻
+chen 琛陈〇趁
+cheng 称成逞秤
+chi 吃迟尺赤
+chong 充虫宠〇
+chou 抽愁丑臭
+chu 出除楚触
+chuai 〇〇〇踹
+chuan 川船喘串
+chuang 窗床闯创
+chui 吹垂〇〇
+chun 春纯蠢〇
+chuo 戳〇〇绰
+ci 疵词此次
+cong 聪从〇〇
+cou 〇〇〇凑
+cu 粗〇〇醋
+cuan 蹿〇〇窜
+cui 催〇〇脆
+cun 村存〇寸
+cuo 搓〇〇错
+da 搭达打大
+dai 呆〇歹代
+dan 单〇胆但
+dang 当〇党荡
+dao 刀〇岛到
+de 〇德〇〇的
+deng 灯〇等瞪
+di 低敌底帝
+dian 颠〇点电
+diao 雕〇〇掉
+die 爹蝶〇〇
+ding 丁〇顶定
+diu 丢〇〇〇
+dong 东〇懂动
+dou 兜〇抖豆
+du 督毒赌杜
+duan 端〇短断
+dui 堆〇〇对
+dun 蹲〇盹顿
+duo 多夺朵剁
+e 婀鹅〇饿
+en 恩〇〇摁
+er 〇儿耳二
+fa 发罚法〇
+fan 翻烦反饭
+fang 方房访放
+fei 飞肥匪费
+fen 分坟粉粪
+feng 风逢讽凤
+fo 〇佛〇〇
+fou 〇〇否〇
+fu 夫福府父
+ga 嘎〇〇〇
+gai 该〇改盖
+gan 甘〇敢赣
+gang 刚〇港杠
+gao 高〇搞告
+ge 哥格〇个
+gei 〇〇给〇
+gen 根〇〇亘
+geng 耕〇梗〇
+gong 工〇巩共
+gou 沟〇狗够
+gu 姑〇古顾
+gua 瓜〇寡挂
+guai 乖〇拐怪
+guan 关〇管惯
+guang 光〇广逛
+gui 归〇鬼贵
+gun 〇〇滚棍
+guo 锅国果过
+ha 哈〇〇〇
+hai 〇孩海害
+han 憨含喊汉
+hang 〇航〇〇
+hao 蒿豪〇耗
+he 喝河〇贺
+hei 黑〇〇〇
+hen 〇痕很恨
+heng 哼横〇〇
+hong 烘红哄〇
+hou 〇猴吼后
+hu 呼湖虎护
+hua 花华〇话
+huai 〇怀〇坏
+huan 欢环缓换
+huang 荒黄谎〇
+hui 灰回毁惠
+hun 昏魂〇混
+huo 〇活火货
+ji 机急挤记
+jia 家荚甲价
+jian 尖〇减见
+jiang 江〇讲酱
+jiao 交嚼脚叫
+jie 接节姐借
+jin 金〇紧进
+jing 京〇井静
+jiong 〇〇窘〇
+jiu 纠〇九旧
+ju 居局举句
+juan 捐〇卷倦
+jue 撅决〇倔
+jun 军〇〇俊
+ka 咖〇卡〇
+kai 开〇凯〇
+kan 刊〇砍看
+kang 康扛〇抗
+kao 〇〇考靠
+ke 科壳可课
+ken 〇〇肯〇
+keng 坑〇〇〇
+kong 空〇孔控
+kou 抠〇口扣
+ku 哭〇苦酷
+kua 夸〇垮跨
+kuai 〇〇〇快
+kuan 宽〇款〇
+kuang 筐狂〇矿
+kui 亏葵〇愧
+kun 昆〇捆困
+kuo 〇〇〇扩
+la 拉〇〇辣
+lai 〇来〇赖
+lan 〇蓝懒烂
+lang 〇狼朗浪
+lao 捞劳老涝
+le 〇〇〇〇了
+lei 〇雷垒泪
+leng 〇〇冷愣
+li 〇离里力
+lian 〇连脸练
+liang 〇凉两亮
+liao 撩聊〇料
+lie 〇〇〇烈
+lin 〇林凛吝
+ling 〇灵领另
+liu 溜留柳六
+long 〇龙拢〇
+lou 〇楼搂漏
+lu 〇炉鲁路
+lv 〇驴旅绿
+luan 〇〇卵乱
+lve 〇〇〇略
+lun 〇轮〇论
+luo 〇罗裸落
+ma 妈麻马骂吗
+mai 〇埋买卖
+man 〇蛮满慢
+mang 〇忙莽〇
+mao 猫毛〇帽
+me 〇〇〇〇么
+mei 〇没美妹
+men 〇门〇焖们
+meng 〇盟猛梦
+mi 眯迷米密
+mian 〇棉免面
+miao 喵苗秒庙
+mie 〇〇〇灭
+min 〇民敏〇
+ming 〇明〇命
+miu 〇〇〇谬
+mo 摸魔〇墨
+mou 〇谋某〇
+mu 〇〇母木
+na 〇拿哪纳
+nai 〇〇奶耐
+nan 〇男〇〇
+nang 〇囊〇〇
+nao 〇挠脑闹
+ne 〇〇〇〇呢
+nei 〇〇〇内
+nen 〇〇〇嫩
+neng 〇能〇〇
+ni 〇泥你逆
+nian 〇年碾念
+niang 〇娘〇酿
+niao 〇〇鸟尿
+nie 捏〇〇聂
+nin 〇您〇〇
+ning 〇凝〇〇
+niu 妞牛扭〇
+nong 〇农〇弄
+nu 〇奴努怒
+nv 〇〇女〇
+nuan 〇〇暖〇
+nve 〇〇〇虐
+nuo 〇挪〇诺
+o 噢〇〇〇
+ou 欧〇偶〇
+pa 趴爬〇怕
+pai 拍排〇派
+pan 攀盘〇盼
+pang 〇旁〇胖
+pao 抛袍跑泡
+pei 呸陪〇配
+pen 喷盆〇〇
+peng 烹朋捧碰
+pi 批皮匹屁
+pian 偏〇〇骗
+piao 飘嫖〇票
+pie 瞥〇撇〇
+pin 拼贫品聘
+ping 乒平〇〇
+po 坡婆叵破
+pou 剖〇〇〇
+pu 扑葡普瀑
+qi 七骑起气
+qia 掐〇〇恰
+qian 千钱浅欠
+qiang 枪墙抢〇
+qiao 敲桥巧俏
+qie 〇茄且窃
+qin 亲琴〇沁
+qing 青晴请庆
+qiong 〇穷〇〇
+qiu 秋求〇〇
+qu 区渠取去
+quan 圈全犬劝
+que 缺瘸〇却
+qun 〇群〇〇
+ran 〇然染〇
+rang 〇瓤壤让
+rao 〇饶扰绕
+re 〇〇惹热
+ren 〇人忍认
+reng 扔仍〇〇
+ri 〇〇〇日
+rong 〇容冗〇
+rou 〇柔〇肉
+ru 〇如乳入
+ruan 〇〇软〇
+rui 〇〇〇锐
+run 〇〇〇润
+ruo 〇〇〇弱
+sa 仨〇洒萨
+sai 腮〇〇赛
+san 三〇伞〇
+sang 桑〇嗓〇
+sao 骚〇扫〇
+se 〇〇〇涩
+sen 森〇〇〇
+seng 僧〇〇〇
+sha 沙啥傻〇
+shai 筛〇〇晒
+shan 山〇闪善
+shang 伤〇赏上
+shao 烧勺〇哨
+she 奢蛇〇射
+shei 〇谁〇〇
+shen 身神审肾
+sheng 生绳省圣
+shi 诗十史是
+shou 收〇手瘦
+shu 书熟鼠树
+shua 刷〇耍〇
+shuai 摔〇甩帅
+shuan 拴〇〇涮
+shuang 双〇爽〇
+shui 〇〇水睡
+shun 〇〇吮顺
+shuo 说〇〇硕
+si 思〇死四
+song 松〇耸送
+sou 搜〇〇〇
+su 苏俗〇速
+suan 酸〇〇算
+sui 虽随髓岁
+sun 孙〇损〇
+suo 缩〇锁〇
+ta 他〇塔踏
+tai 胎台〇太
+tan 贪谈坦叹
+tang 汤糖躺烫
+tao 掏桃讨套
+te 〇〇〇特
+teng 〇疼〇〇
+ti 梯提体替
+tian 天田舔〇
+tiao 〇条〇跳
+tie 贴〇铁〇
+ting 听停挺〇
+tong 通同桶痛
+tou 偷头〇透
+tu 突图土兔
+tuan 〇团〇〇
+tui 推〇腿退
+tun 吞屯〇〇
+tuo 拖驼妥唾
+wa 挖娃瓦袜
+wai 歪〇〇外
+wan 弯完晚万
+wang 汪王网忘
+wei 微围伟卫
+wen 温文稳问
+weng 翁〇〇瓮
+wo 窝〇我握
+wu 乌无五物
+xi 西习洗细
+xia 虾霞〇夏
+xian 先闲显现
+xiang 香详想向
+xiao 消〇小笑
+xie 歇鞋写谢
+xin 心〇〇信
+xing 星形醒幸
+xiong 凶熊〇〇
+xiu 修〇朽秀
+xu 需徐许续
+xuan 宣玄选炫
+xue 靴学雪〇
+xun 熏寻〇训
+ya 压牙哑亚
+yan 烟严眼燕
+yang 央羊养样
+yao 腰摇咬药
+ye 椰爷也夜
+yi 衣姨以亿
+yin 音银引印
+ying 英赢影硬
+yo 哟〇〇〇
+yong 拥〇勇用
+you 优由有又
+yu 迂鱼雨玉
+yuan 冤元远院
+yue 约〇〇月
+yun 〇云允运
+za 〇杂〇〇
+zai 灾〇宰在
+zan 〇咱攒赞
+zang 赃〇〇葬
+zao 遭凿早造
+ze 〇泽〇〇
+zei 〇贼〇〇
+zen 〇〇怎〇
+zeng 增〇〇赠
+zha 渣闸眨榨
+zhai 摘宅窄债
+zhan 沾〇展站
+zhang 张〇掌帐
+zhao 招〇找照
+zhe 遮哲者这
+zhen 真〇枕阵
+zheng 蒸〇整正
+zhi 知直纸志
+zhong 钟〇肿众
+zhou 周轴肘皱
+zhu 猪竹主住
+zhua 抓〇〇〇
+zhuan 砖〇〇赚
+zhuang 装〇〇撞
+zhui 追〇〇坠
+zhun 〇〇准〇
+zhuo 桌浊〇〇
+zi 资〇子字
+zong 宗〇总纵
+zou 邹〇走奏
+zu 租足组〇
+zui 〇〇嘴最
+zun 尊〇〇〇
+zuo 〇昨左做
+";
+
+/// 没有合适用字的占位
+const NONE: char = '〇';
+
+/// 单个带调音节 (如 `qing4`、`lv3`、`lü3`) 转成同音字；声调 5 或 0 为轻声
+fn syllable(input: &str) -> Option<char> {
+    let lower = input.to_lowercase().replace('ü', "v");
+    let tone = lower.chars().last()?.to_digit(10)?;
+    let base = &lower[..lower.len() - 1];
+    let index = match tone {
+        1..=4 => tone as usize - 1,
+        0 | 5 => 4,
+        _ => return None,
+    };
+    let chars = TABLE
+        .lines()
+        .find_map(|line| line.strip_prefix(base)?.strip_prefix(' '))?;
+    chars.chars().nth(index).filter(|c| *c != NONE)
+}
+
+/// 把空格分隔的带调拼音转成同音字，失败时返回无法转换的音节
+pub fn to_homophones(pinyin: &str) -> Result<String, String> {
+    let syllables: Vec<&str> = pinyin.split_whitespace().collect();
+    if syllables.is_empty() {
+        return Err(String::new());
+    }
+    syllables
+        .into_iter()
+        .map(|s| syllable(s).ok_or_else(|| s.to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_toned_syllables() {
+        assert_eq!(to_homophones("chong2 qing4"), Ok("虫庆".to_string()));
+        assert_eq!(to_homophones("Lv3 xing2"), Ok("旅形".to_string()));
+        assert_eq!(to_homophones("lü4 ma5"), Ok("绿吗".to_string()));
+    }
+
+    #[test]
+    fn reports_unconvertible_syllables() {
+        assert_eq!(to_homophones("chong qing4"), Err("chong".to_string()));
+        assert_eq!(to_homophones("biao4"), Err("biao4".to_string()));
+        assert_eq!(to_homophones("xyz1"), Err("xyz1".to_string()));
+        assert_eq!(to_homophones("  "), Err(String::new()));
+    }
+
+    #[test]
+    fn table_rows_are_well_formed() {
+        for line in TABLE.lines() {
+            let (base, chars) = line.split_once(' ').expect(line);
+            assert!(base.chars().all(|c| c.is_ascii_lowercase()), "{line}");
+            assert!((4..=5).contains(&chars.chars().count()), "{line}");
+        }
+    }
+}
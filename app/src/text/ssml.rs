@@ -0,0 +1,707 @@
+//! SSML 子集解析
+//!
+//! 支持 `<speak>`、`<break>`、`<prosody rate/pitch>`、`<say-as>`、`<sub>` 和拼音 `<phoneme>`。
+//! 接入的服务商都无法按音标朗读，拼音会换成同音字读出；IPA 等其他音标直接报错，
+//! 而不是悄悄按原文读出。
+//! 解析结果是一串带语速/音高参数的 [`Segment`]，由调用方逐段合成，
+//! 并在 [`Segment::Break`] 处插入静音，因此不依赖 TTS 服务商原生支持 SSML。
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::pinyin;
+
+/// 支持的标签，用来判断一段文本是否按 SSML 解析
+const TAGS: &[&str] = &["speak", "break", "prosody", "say-as", "phoneme", "sub"];
+
+/// 单个 `<break>` 最长停顿 (秒)
+const MAX_BREAK_SECS: f32 = 10.0;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum SsmlError {
+    #[error("第 {pos} 个字符处：标签没有闭合的 '>'")]
+    UnterminatedTag { pos: usize },
+    #[error(
+        "第 {pos} 个字符处：不支持的标签 <{tag}>，可用标签为 speak、break、prosody、say-as、sub、phoneme"
+    )]
+    UnknownTag { pos: usize, tag: String },
+    #[error("第 {pos} 个字符处：</{found}> 与 <{expected}> 不匹配")]
+    MismatchedClose {
+        pos: usize,
+        expected: String,
+        found: String,
+    },
+    #[error("第 {pos} 个字符处：多余的结束标签 </{tag}>")]
+    UnexpectedClose { pos: usize, tag: String },
+    #[error("第 {pos} 个字符处：标签 <{tag}> 没有结束")]
+    Unclosed { pos: usize, tag: String },
+    #[error("第 {pos} 个字符处：<{tag}> 缺少属性 {attr}")]
+    MissingAttribute {
+        pos: usize,
+        tag: String,
+        attr: &'static str,
+    },
+    #[error("第 {pos} 个字符处：<{tag}> 的属性 {attr}=\"{value}\" 无效，{hint}")]
+    InvalidAttribute {
+        pos: usize,
+        tag: String,
+        attr: String,
+        value: String,
+        hint: &'static str,
+    },
+    #[error("第 {pos} 个字符处：属性写法有误，应为 name=\"value\"")]
+    MalformedAttribute { pos: usize },
+    #[error("第 {pos} 个字符处：<{tag}> 不能包含内容，请写成 <{tag}/>")]
+    NotEmpty { pos: usize, tag: String },
+    #[error("第 {pos} 个字符处：未知的转义 &{entity};")]
+    UnknownEntity { pos: usize, entity: String },
+    #[error("第 {pos} 个字符处：暂不支持 <{tag}>，{hint}")]
+    Unsupported {
+        pos: usize,
+        tag: String,
+        hint: &'static str,
+    },
+}
+
+/// 渲染后的一段
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Segment {
+    /// 需要合成的文本，`rate` 为语速倍率，`pitch` 为音高偏移 (半音)
+    Speech { text: String, rate: f32, pitch: f32 },
+    /// 停顿 (秒)
+    Break(f32),
+}
+
+/// 文本中是否含有受支持的 SSML 标签
+pub fn looks_like_ssml(text: &str) -> bool {
+    text.match_indices('<').any(|(i, _)| {
+        let rest = text[i + 1..].trim_start_matches('/');
+        TAGS.iter().any(|tag| {
+            rest.starts_with(tag)
+                && rest[tag.len()..]
+                    .starts_with(|c: char| c == '>' || c == '/' || c.is_whitespace())
+        })
+    })
+}
+
+/// 当前生效的韵律参数
+#[derive(Clone, Copy)]
+struct Prosody {
+    rate: f32,
+    pitch: f32,
+}
+
+/// 已打开、尚未闭合的标签
+struct OpenTag {
+    name: String,
+    pos: usize,
+    prosody: Prosody,
+    /// 该标签内文本的替换方式
+    content: Content,
+}
+
+#[derive(Clone)]
+enum Content {
+    Keep,
+    /// `<sub alias>`：用给定文本替换标签内容
+    Replace(String),
+    /// `<say-as>`：按规则改写标签内容
+    SayAs(SayAs),
+}
+
+#[derive(Clone, Copy)]
+enum SayAs {
+    Characters,
+    Digits,
+    Telephone,
+}
+
+/// 解析 SSML 并渲染为分段
+pub fn parse(input: &str) -> Result<Vec<Segment>, SsmlError> {
+    // 内部按字节定位，报错时换算成从 1 开始的字符序号
+    parse_segments(input).map_err(|mut e| {
+        let pos = e.pos_mut();
+        *pos = input[..*pos].chars().count() + 1;
+        e
+    })
+}
+
+impl SsmlError {
+    fn pos_mut(&mut self) -> &mut usize {
+        match self {
+            SsmlError::UnterminatedTag { pos }
+            | SsmlError::UnknownTag { pos, .. }
+            | SsmlError::MismatchedClose { pos, .. }
+            | SsmlError::UnexpectedClose { pos, .. }
+            | SsmlError::Unclosed { pos, .. }
+            | SsmlError::MissingAttribute { pos, .. }
+            | SsmlError::InvalidAttribute { pos, .. }
+            | SsmlError::MalformedAttribute { pos }
+            | SsmlError::NotEmpty { pos, .. }
+            | SsmlError::UnknownEntity { pos, .. }
+            | SsmlError::Unsupported { pos, .. } => pos,
+        }
+    }
+}
+
+fn parse_segments(input: &str) -> Result<Vec<Segment>, SsmlError> {
+    let mut out = Renderer::default();
+    let mut stack: Vec<OpenTag> = Vec::new();
+    let mut prosody = Prosody {
+        rate: 1.0,
+        pitch: 0.0,
+    };
+    let mut pending = String::new();
+    let mut pos = 0;
+
+    while pos < input.len() {
+        let rest = &input[pos..];
+        if !rest.starts_with('<') {
+            let end = rest.find('<').map_or(input.len(), |i| pos + i);
+            pending.push_str(&decode_entities(&input[pos..end], pos)?);
+            pos = end;
+            continue;
+        }
+
+        let close = rest.find('>').ok_or(SsmlError::UnterminatedTag { pos })?;
+        let raw = &rest[1..close];
+        let tag_pos = pos;
+        pos += close + 1;
+
+        // 注释和 XML 声明直接跳过
+        if raw.starts_with('!') || raw.starts_with('?') {
+            continue;
+        }
+
+        if let Some(name) = raw.strip_prefix('/') {
+            let name = name.trim();
+            let Some(open) = stack.pop() else {
+                return Err(SsmlError::UnexpectedClose {
+                    pos: tag_pos,
+                    tag: name.to_string(),
+                });
+            };
+            if open.name != name {
+                return Err(SsmlError::MismatchedClose {
+                    pos: tag_pos,
+                    expected: open.name,
+                    found: name.to_string(),
+                });
+            }
+            let text = std::mem::take(&mut pending);
+            match open.content {
+                Content::Keep => out.speech(&text, prosody),
+                Content::Replace(replacement) => out.speech(&replacement, prosody),
+                Content::SayAs(mode) => out.speech(&say_as(mode, &text), prosody),
+            }
+            prosody = open.prosody;
+            continue;
+        }
+
+        // 进入新标签前先输出之前累积的文本
+        if !pending.is_empty() {
+            let text = std::mem::take(&mut pending);
+            match stack.last().map(|t| t.content.clone()) {
+                Some(Content::SayAs(mode)) => out.speech(&say_as(mode, &text), prosody),
+                Some(Content::Replace(_)) => {
+                    return Err(SsmlError::NotEmpty {
+                        pos: tag_pos,
+                        tag: stack.last().map(|t| t.name.clone()).unwrap_or_default(),
+                    })
+                }
+                _ => out.speech(&text, prosody),
+            }
+        }
+
+        let self_closing = raw.ends_with('/');
+        let raw = raw.trim_end_matches('/');
+        let (name, attrs) = raw.split_once(char::is_whitespace).unwrap_or((raw, ""));
+        let attrs = parse_attributes(attrs, tag_pos)?;
+        let attr = |key: &str| {
+            attrs
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.as_str())
+        };
+
+        let content = match name {
+            "speak" => Content::Keep,
+            "break" => {
+                if !self_closing {
+                    return Err(SsmlError::NotEmpty {
+                        pos: tag_pos,
+                        tag: name.to_string(),
+                    });
+                }
+                out.pause(parse_break(attr("time"), attr("strength"), tag_pos)?);
+                continue;
+            }
+            "prosody" => {
+                let mut next = prosody;
+                if let Some(rate) = attr("rate") {
+                    next.rate *= parse_rate(rate).ok_or_else(|| {
+                        invalid(
+                            tag_pos,
+                            name,
+                            "rate",
+                            rate,
+                            "可用 x-slow/slow/medium/fast/x-fast、百分比 (如 120%) 或倍率 (如 1.2)",
+                        )
+                    })?;
+                }
+                if let Some(pitch) = attr("pitch") {
+                    next.pitch += parse_pitch(pitch).ok_or_else(|| {
+                        invalid(
+                            tag_pos,
+                            name,
+                            "pitch",
+                            pitch,
+                            "可用 x-low/low/medium/high/x-high、半音 (如 +2st) 或百分比 (如 -10%)",
+                        )
+                    })?;
+                }
+                if !self_closing {
+                    stack.push(OpenTag {
+                        name: name.to_string(),
+                        pos: tag_pos,
+                        prosody,
+                        content: Content::Keep,
+                    });
+                    prosody = next;
+                }
+                continue;
+            }
+            "say-as" => {
+                let kind = attr("interpret-as").ok_or(SsmlError::MissingAttribute {
+                    pos: tag_pos,
+                    tag: name.to_string(),
+                    attr: "interpret-as",
+                })?;
+                Content::SayAs(match kind {
+                    "characters" | "spell-out" => SayAs::Characters,
+                    "digits" => SayAs::Digits,
+                    "telephone" => SayAs::Telephone,
+                    _ => {
+                        return Err(invalid(
+                            tag_pos,
+                            name,
+                            "interpret-as",
+                            kind,
+                            "可用 characters/spell-out/digits/telephone",
+                        ))
+                    }
+                })
+            }
+            "sub" => {
+                let alias = attr("alias").ok_or(SsmlError::MissingAttribute {
+                    pos: tag_pos,
+                    tag: name.to_string(),
+                    attr: "alias",
+                })?;
+                Content::Replace(alias.to_string())
+            }
+            "phoneme" => {
+                let ph = attr("ph").ok_or(SsmlError::MissingAttribute {
+                    pos: tag_pos,
+                    tag: name.to_string(),
+                    attr: "ph",
+                })?;
+                match attr("alphabet").unwrap_or("pinyin") {
+                    // 拼音换成同音字，和 <sub alias> 一样替换标签内容
+                    "pinyin" => Content::Replace(pinyin::to_homophones(ph).map_err(|_| {
+                        invalid(
+                            tag_pos,
+                            name,
+                            "ph",
+                            ph,
+                            "应为空格分隔的带调拼音，如 chong2 qing4",
+                        )
+                    })?),
+                    "ipa" | "x-sampa" => {
+                        return Err(SsmlError::Unsupported {
+                            pos: tag_pos,
+                            tag: name.to_string(),
+                            hint: "目前只支持 alphabet=\"pinyin\"，其他音标可改用 <sub alias=\"读音相同的字\">原文</sub>",
+                        })
+                    }
+                    alphabet => {
+                        return Err(invalid(
+                            tag_pos,
+                            name,
+                            "alphabet",
+                            alphabet,
+                            "可用 pinyin/ipa/x-sampa",
+                        ))
+                    }
+                }
+            }
+            _ => {
+                return Err(SsmlError::UnknownTag {
+                    pos: tag_pos,
+                    tag: name.to_string(),
+                })
+            }
+        };
+
+        if self_closing {
+            if let Content::Replace(replacement) = &content {
+                out.speech(replacement, prosody);
+            }
+            continue;
+        }
+        stack.push(OpenTag {
+            name: name.to_string(),
+            pos: tag_pos,
+            prosody,
+            content,
+        });
+    }
+
+    if let Some(open) = stack.pop() {
+        return Err(SsmlError::Unclosed {
+            pos: open.pos,
+            tag: open.name,
+        });
+    }
+    out.speech(&pending, prosody);
+    Ok(out.segments)
+}
+
+/// 输出分段，相邻且参数相同的文本会被合并
+#[derive(Default)]
+struct Renderer {
+    segments: Vec<Segment>,
+}
+
+impl Renderer {
+    fn speech(&mut self, text: &str, prosody: Prosody) {
+        if text.trim().is_empty() {
+            return;
+        }
+        if let Some(Segment::Speech {
+            text: last,
+            rate,
+            pitch,
+        }) = self.segments.last_mut()
+        {
+            if *rate == prosody.rate && *pitch == prosody.pitch {
+                last.push_str(text);
+                return;
+            }
+        }
+        self.segments.push(Segment::Speech {
+            text: text.to_string(),
+            rate: prosody.rate,
+            pitch: prosody.pitch,
+        });
+    }
+
+    fn pause(&mut self, secs: f32) {
+        if secs <= 0.0 {
+            return;
+        }
+        match self.segments.last_mut() {
+            Some(Segment::Break(last)) => *last = (*last + secs).min(MAX_BREAK_SECS),
+            _ => self.segments.push(Segment::Break(secs)),
+        }
+    }
+}
+
+fn invalid(pos: usize, tag: &str, attr: &str, value: &str, hint: &'static str) -> SsmlError {
+    SsmlError::InvalidAttribute {
+        pos,
+        tag: tag.to_string(),
+        attr: attr.to_string(),
+        value: value.to_string(),
+        hint,
+    }
+}
+
+/// 解析 `name="value"` 形式的属性列表 (单双引号均可)
+fn parse_attributes(raw: &str, pos: usize) -> Result<Vec<(String, String)>, SsmlError> {
+    let mut attrs = Vec::new();
+    let mut rest = raw.trim();
+    while !rest.is_empty() {
+        let (name, after) = rest
+            .split_once('=')
+            .ok_or(SsmlError::MalformedAttribute { pos })?;
+        let after = after.trim_start();
+        let quote = after
+            .chars()
+            .next()
+            .filter(|c| *c == '"' || *c == '\'')
+            .ok_or(SsmlError::MalformedAttribute { pos })?;
+        let end = after[1..]
+            .find(quote)
+            .ok_or(SsmlError::MalformedAttribute { pos })?;
+        attrs.push((
+            name.trim().to_string(),
+            decode_entities(&after[1..1 + end], pos)?,
+        ));
+        rest = after[end + 2..].trim_start();
+    }
+    Ok(attrs)
+}
+
+fn decode_entities(text: &str, pos: usize) -> Result<String, SsmlError> {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        let after = &rest[amp + 1..];
+        let end = after.find(';').ok_or_else(|| SsmlError::UnknownEntity {
+            pos: pos + text.len() - rest.len() + amp,
+            entity: after.chars().take(8).collect(),
+        })?;
+        let entity = &after[..end];
+        out.push(match entity {
+            "amp" => '&',
+            "lt" => '<',
+            "gt" => '>',
+            "quot" => '"',
+            "apos" => '\'',
+            _ => {
+                return Err(SsmlError::UnknownEntity {
+                    pos: pos + text.len() - rest.len() + amp,
+                    entity: entity.to_string(),
+                })
+            }
+        });
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+fn parse_break(time: Option<&str>, strength: Option<&str>, pos: usize) -> Result<f32, SsmlError> {
+    if let Some(time) = time {
+        let secs = if let Some(ms) = time.strip_suffix("ms") {
+            ms.trim().parse::<f32>().ok().map(|v| v / 1000.0)
+        } else if let Some(s) = time.strip_suffix('s') {
+            s.trim().parse::<f32>().ok()
+        } else {
+            None
+        };
+        return secs
+            .filter(|s| (0.0..=MAX_BREAK_SECS).contains(s))
+            .ok_or_else(|| {
+                invalid(
+                    pos,
+                    "break",
+                    "time",
+                    time,
+                    "应为 0~10s 之间的时长，如 500ms 或 1.5s",
+                )
+            });
+    }
+    match strength.unwrap_or("medium") {
+        "none" => Ok(0.0),
+        "x-weak" => Ok(0.1),
+        "weak" => Ok(0.25),
+        "medium" => Ok(0.4),
+        "strong" => Ok(0.7),
+        "x-strong" => Ok(1.0),
+        other => Err(invalid(
+            pos,
+            "break",
+            "strength",
+            other,
+            "可用 none/x-weak/weak/medium/strong/x-strong",
+        )),
+    }
+}
+
+/// 语速：关键字、百分比或倍率，限制在 0.5 ~ 2.0 倍
+fn parse_rate(value: &str) -> Option<f32> {
+    let rate = match value {
+        "x-slow" => 0.5,
+        "slow" => 0.75,
+        "medium" | "default" => 1.0,
+        "fast" => 1.25,
+        "x-fast" => 1.5,
+        _ => match value.strip_suffix('%') {
+            Some(pct) => pct.trim().parse::<f32>().ok()? / 100.0,
+            None => value.trim().parse::<f32>().ok()?,
+        },
+    };
+    (0.5..=2.0).contains(&rate).then_some(rate)
+}
+
+/// 音高：关键字、半音或百分比，统一换算成半音，限制在 ±12 半音
+fn parse_pitch(value: &str) -> Option<f32> {
+    let semitones = match value {
+        "x-low" => -6.0,
+        "low" => -3.0,
+        "medium" | "default" => 0.0,
+        "high" => 3.0,
+        "x-high" => 6.0,
+        _ => {
+            if let Some(st) = value.strip_suffix("st") {
+                st.trim().parse::<f32>().ok()?
+            } else if let Some(pct) = value.strip_suffix('%') {
+                let ratio = 1.0 + pct.trim().parse::<f32>().ok()? / 100.0;
+                if ratio <= 0.0 {
+                    return None;
+                }
+                12.0 * ratio.log2()
+            } else {
+                return None;
+            }
+        }
+    };
+    (-12.0..=12.0).contains(&semitones).then_some(semitones)
+}
+
+/// 按 `<say-as>` 的类型改写文本
+fn say_as(mode: SayAs, text: &str) -> String {
+    let chars = text.chars().filter(|c| !c.is_whitespace());
+    match mode {
+        // 逐字朗读：字符之间加空格
+        SayAs::Characters => chars.map(String::from).collect::<Vec<_>>().join(" "),
+        // 逐位朗读数字，并把 0 读作 "零"
+        SayAs::Digits => chars
+            .map(|c| match c {
+                '0'..='9' => DIGITS[c as usize - '0' as usize].to_string(),
+                other => other.to_string(),
+            })
+            .collect(),
+        // 电话号码：逐位朗读，1 读作 "幺"，连字符处停顿
+        SayAs::Telephone => chars
+            .map(|c| match c {
+                '1' => "幺".to_string(),
+                '0'..='9' => DIGITS[c as usize - '0' as usize].to_string(),
+                '-' => "，".to_string(),
+                other => other.to_string(),
+            })
+            .collect(),
+    }
+}
+
+const DIGITS: [&str; 10] = ["零", "一", "二", "三", "四", "五", "六", "七", "八", "九"];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn speech(text: &str, rate: f32, pitch: f32) -> Segment {
+        Segment::Speech {
+            text: text.to_string(),
+            rate,
+            pitch,
+        }
+    }
+
+    #[test]
+    fn detects_supported_tags() {
+        assert!(looks_like_ssml("你好<break time=\"1s\"/>"));
+        assert!(looks_like_ssml("<speak>你好</speak>"));
+        assert!(!looks_like_ssml("a < b，<div>不是 SSML</div>"));
+    }
+
+    #[test]
+    fn renders_breaks_prosody_and_rewrites() {
+        assert_eq!(
+            parse(
+                "<speak>开始<break time=\"500ms\"/><prosody rate=\"fast\" pitch=\"+2st\">快</prosody>\
+                 <say-as interpret-as=\"telephone\">110</say-as><sub alias=\"世卫组织\">WHO</sub></speak>"
+            ),
+            Ok(vec![
+                speech("开始", 1.0, 0.0),
+                Segment::Break(0.5),
+                speech("快", 1.25, 2.0),
+                speech("幺幺零世卫组织", 1.0, 0.0),
+            ])
+        );
+    }
+
+    #[test]
+    fn reports_structure_errors_with_char_positions() {
+        assert_eq!(
+            parse("你好<prosody rate=\"fast\">快")
+                .unwrap_err()
+                .to_string(),
+            "第 3 个字符处：标签 <prosody> 没有结束"
+        );
+        assert_eq!(
+            parse("<speak>嗨</prosody>").unwrap_err().to_string(),
+            "第 9 个字符处：</prosody> 与 <speak> 不匹配"
+        );
+        assert_eq!(
+            parse("嗨<emphasis>重点</emphasis>")
+                .unwrap_err()
+                .to_string(),
+            "第 2 个字符处：不支持的标签 <emphasis>，可用标签为 speak、break、prosody、say-as、sub、phoneme"
+        );
+        assert_eq!(
+            parse("嗨<break time=\"1s\">").unwrap_err(),
+            SsmlError::NotEmpty {
+                pos: 2,
+                tag: "break".to_string()
+            }
+        );
+        assert_eq!(
+            parse("<speak>&nbsp;</speak>").unwrap_err(),
+            SsmlError::UnknownEntity {
+                pos: 8,
+                entity: "nbsp".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn reports_invalid_attributes() {
+        assert_eq!(
+            parse("<break time=\"20s\"/>").unwrap_err().to_string(),
+            "第 1 个字符处：<break> 的属性 time=\"20s\" 无效，应为 0~10s 之间的时长，如 500ms 或 1.5s"
+        );
+        assert_eq!(
+            parse("<say-as>12</say-as>").unwrap_err(),
+            SsmlError::MissingAttribute {
+                pos: 1,
+                tag: "say-as".to_string(),
+                attr: "interpret-as"
+            }
+        );
+        assert_eq!(
+            parse("<prosody rate=fast>快</prosody>").unwrap_err(),
+            SsmlError::MalformedAttribute { pos: 1 }
+        );
+    }
+
+    #[test]
+    fn pinyin_phoneme_is_read_as_homophones() {
+        assert_eq!(
+            parse("去<phoneme alphabet=\"pinyin\" ph=\"chong2 qing4\">重庆</phoneme>").unwrap(),
+            vec![speech("去虫庆", 1.0, 0.0)]
+        );
+        assert_eq!(
+            parse("<phoneme ph=\"hang2\">行</phoneme>").unwrap(),
+            vec![speech("航", 1.0, 0.0)]
+        );
+        assert!(matches!(
+            parse("<phoneme ph=\"chong qing\">重庆</phoneme>"),
+            Err(SsmlError::InvalidAttribute { .. })
+        ));
+    }
+
+    #[test]
+    fn other_phoneme_alphabets_are_rejected_instead_of_ignored() {
+        assert_eq!(
+            parse("<phoneme alphabet=\"ipa\" ph=\"təˈmɑːtəʊ\">tomato</phoneme>")
+                .unwrap_err()
+                .to_string(),
+            "第 1 个字符处：暂不支持 <phoneme>，目前只支持 alphabet=\"pinyin\"，其他音标可改用 <sub alias=\"读音相同的字\">原文</sub>"
+        );
+        assert_eq!(
+            parse("<phoneme>重庆</phoneme>").unwrap_err(),
+            SsmlError::MissingAttribute {
+                pos: 1,
+                tag: "phoneme".to_string(),
+                attr: "ph"
+            }
+        );
+        assert!(matches!(
+            parse("<phoneme alphabet=\"zhuyin\" ph=\"x\">重</phoneme>"),
+            Err(SsmlError::InvalidAttribute { .. })
+        ));
+    }
+}
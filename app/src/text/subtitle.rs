@@ -0,0 +1,318 @@
+//! 字幕导出：把带时间戳的句子渲染成 SRT / WebVTT
+//!
+//! 行宽按显示宽度计算 (中日韩文字与全角标点算 2，其余算 1)。中日韩文字可在任意两字之间换行，
+//! 英文只在空格处换行；句读、右括号等不放在行首，左括号、左引号不留在行尾。
+//! 一条字幕最多两行，更长的句子按字数比例拆成多条。
+
+use serde::{Deserialize, Serialize};
+
+/// 每条字幕的最大行数
+const MAX_LINES: usize = 2;
+
+/// 默认每行最大显示宽度 (约 21 个汉字或 42 个英文字符)
+pub const DEFAULT_LINE_WIDTH: usize = 42;
+
+/// 可设置的行宽范围
+pub const MIN_LINE_WIDTH: usize = 10;
+pub const MAX_LINE_WIDTH: usize = 80;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SubtitleFormat {
+    Srt,
+    WebVtt,
+}
+
+impl SubtitleFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            SubtitleFormat::Srt => "srt",
+            SubtitleFormat::WebVtt => "vtt",
+        }
+    }
+
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            SubtitleFormat::Srt => "application/x-subrip",
+            SubtitleFormat::WebVtt => "text/vtt",
+        }
+    }
+}
+
+/// 一条字幕 (时间单位：秒)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cue {
+    pub start: f32,
+    pub end: f32,
+    pub lines: Vec<String>,
+}
+
+/// 不能出现在行首的标点
+fn no_line_start(c: char) -> bool {
+    "，。、！？；：」』）》〉】〕…—～·,.!?;:)]}%'\"”’".contains(c)
+}
+
+/// 不能留在行尾的标点
+fn no_line_end(c: char) -> bool {
+    "「『（《〈【〔([{“‘".contains(c)
+}
+
+/// 中日韩文字及全角符号：显示宽度为 2，可在其前后换行
+fn is_wide(c: char) -> bool {
+    matches!(c as u32,
+        0x1100..=0x115F
+        | 0x2E80..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x20000..=0x3FFFD)
+}
+
+fn char_width(c: char) -> usize {
+    if is_wide(c) {
+        2
+    } else {
+        1
+    }
+}
+
+fn text_width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+/// 不可再拆的排版单元：一个汉字 (连同粘在它前后的标点)，或一个英文单词
+struct Unit {
+    text: String,
+    /// 原文中前面是否有空格
+    space_before: bool,
+}
+
+fn split_units(text: &str) -> Vec<Unit> {
+    let mut units: Vec<Unit> = Vec::new();
+    let mut space = false;
+    // 上一个字符是左括号等，下一个字符必须和它粘在一起
+    let mut glue_next = false;
+    for c in text.chars() {
+        if c.is_whitespace() {
+            space = true;
+            continue;
+        }
+        let joins_last = match units.last() {
+            None => false,
+            Some(_) if glue_next => true,
+            Some(_) if space => false,
+            Some(_) if no_line_start(c) => true,
+            // 英文、数字连续时组成一个单词
+            Some(last) => {
+                !is_wide(c)
+                    && !no_line_end(c)
+                    && last.text.chars().last().is_some_and(|l| !is_wide(l))
+            }
+        };
+        match units.last_mut() {
+            Some(last) if joins_last => last.text.push(c),
+            _ => units.push(Unit {
+                text: c.to_string(),
+                space_before: space,
+            }),
+        }
+        glue_next = no_line_end(c);
+        space = false;
+    }
+    units
+}
+
+/// 按最大显示宽度折行
+pub fn wrap_lines(text: &str, max_width: usize) -> Vec<String> {
+    let max_width = max_width.max(2);
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut width = 0;
+    for unit in split_units(text) {
+        let unit_width = text_width(&unit.text);
+        let sep = usize::from(unit.space_before && !line.is_empty());
+        if !line.is_empty() && width + sep + unit_width > max_width {
+            lines.push(std::mem::take(&mut line));
+            width = 0;
+        } else if sep == 1 {
+            line.push(' ');
+            width += 1;
+        }
+        // 单个单元超宽 (如很长的网址) 时按字符硬切
+        for c in unit.text.chars() {
+            let w = char_width(c);
+            if !line.is_empty() && width + w > max_width {
+                lines.push(std::mem::take(&mut line));
+                width = 0;
+            }
+            line.push(c);
+            width += w;
+        }
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// 把带时间戳的句子排成字幕：折行后每两行一条，超过两行的句子按宽度比例分配时长
+pub fn build_cues<'a>(
+    sentences: impl IntoIterator<Item = (&'a str, f32, f32)>,
+    max_width: usize,
+) -> Vec<Cue> {
+    let mut cues = Vec::new();
+    for (text, start, end) in sentences {
+        let lines = wrap_lines(text, max_width);
+        let total = lines.iter().map(|l| text_width(l)).sum::<usize>().max(1) as f32;
+        let mut offset = 0;
+        for group in lines.chunks(MAX_LINES) {
+            let from = start + (end - start) * offset as f32 / total;
+            offset += group.iter().map(|l| text_width(l)).sum::<usize>();
+            let to = start + (end - start) * offset as f32 / total;
+            cues.push(Cue {
+                start: from,
+                end: to,
+                lines: group.to_vec(),
+            });
+        }
+    }
+    cues
+}
+
+/// `HH:MM:SS` 加毫秒，SRT 用逗号分隔毫秒，WebVTT 用句点
+fn timestamp(secs: f32, format: SubtitleFormat) -> String {
+    let ms = (secs.max(0.0) * 1000.0).round() as u64;
+    let sep = match format {
+        SubtitleFormat::Srt => ',',
+        SubtitleFormat::WebVtt => '.',
+    };
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        ms / 3_600_000,
+        ms / 60_000 % 60,
+        ms / 1000 % 60,
+        sep,
+        ms % 1000
+    )
+}
+
+/// 渲染字幕文件
+pub fn render(cues: &[Cue], format: SubtitleFormat) -> String {
+    let mut out = String::new();
+    if format == SubtitleFormat::WebVtt {
+        out.push_str("WEBVTT\n\n");
+    }
+    for (i, cue) in cues.iter().enumerate() {
+        if format == SubtitleFormat::Srt {
+            out.push_str(&format!("{}\n", i + 1));
+        }
+        out.push_str(&format!(
+            "{} --> {}\n",
+            timestamp(cue.start, format),
+            timestamp(cue.end, format)
+        ));
+        for line in &cue.lines {
+            out.push_str(line);
+            out.push('\n');
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn cjk_counts_double_width() {
+        assert_eq!(text_width("中文ab，"), 8);
+        assert_eq!(
+            wrap_lines("今天天气很好，我们去公园散步吧。", 10),
+            lines(&["今天天气很", "好，我们去", "公园散步", "吧。"])
+        );
+    }
+
+    #[test]
+    fn english_breaks_at_spaces_and_long_words_are_cut() {
+        assert_eq!(
+            wrap_lines("the quick brown fox jumps", 10),
+            lines(&["the quick", "brown fox", "jumps"])
+        );
+        assert_eq!(
+            wrap_lines("abcdefghijkl", 5),
+            lines(&["abcde", "fghij", "kl"])
+        );
+    }
+
+    #[test]
+    fn brackets_stay_with_their_text() {
+        // 「 不留在行尾，」不放在行首
+        assert_eq!(
+            wrap_lines("他说「你好」", 6),
+            lines(&["他说", "「你", "好」"])
+        );
+    }
+
+    #[test]
+    fn long_sentences_split_into_cues_by_width() {
+        let cues = build_cues(
+            [
+                ("今天天气很好，我们去公园散步吧。", 0.0, 6.0),
+                ("Hi", 6.0, 7.5),
+            ],
+            10,
+        );
+        assert_eq!(
+            cues,
+            vec![
+                Cue {
+                    start: 0.0,
+                    end: 3.75,
+                    lines: lines(&["今天天气很", "好，我们去"]),
+                },
+                Cue {
+                    start: 3.75,
+                    end: 6.0,
+                    lines: lines(&["公园散步", "吧。"]),
+                },
+                Cue {
+                    start: 6.0,
+                    end: 7.5,
+                    lines: lines(&["Hi"]),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn renders_srt_and_webvtt() {
+        let cues = [
+            Cue {
+                start: -0.2,
+                end: 1.5,
+                lines: lines(&["你好"]),
+            },
+            Cue {
+                start: 61.25,
+                end: 3661.5,
+                lines: lines(&["second", "line"]),
+            },
+        ];
+        assert_eq!(
+            render(&cues, SubtitleFormat::Srt),
+            "1\n00:00:00,000 --> 00:00:01,500\n你好\n\n\
+             2\n00:01:01,250 --> 01:01:01,500\nsecond\nline\n\n"
+        );
+        assert_eq!(
+            render(&cues, SubtitleFormat::WebVtt),
+            "WEBVTT\n\n00:00:00.000 --> 00:00:01.500\n你好\n\n\
+             00:01:01.250 --> 01:01:01.500\nsecond\nline\n\n"
+        );
+    }
+}
@@ -0,0 +1,179 @@
+//! 主题：明暗模式 (浅色 / 深色 / 跟随系统) 与配色方案
+//!
+//! 主题通过 `<html data-mode=.. data-theme=..>` 生效，样式见 `style/tailwind.css`。
+//! SSR 时从 Cookie (或用户保存的设置) 读取并直接写进 `<html>`，首屏不会闪烁；
+//! 跟随系统时由 CSS 媒体查询决定明暗，同样不需要脚本。
+use crate::api::theme::save_theme;
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 保存主题的 Cookie，值形如 `dark.ocean`
+pub const THEME_COOKIE: &str = "eardo_theme";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ThemeMode {
+    Light,
+    Dark,
+    #[default]
+    System,
+}
+
+impl ThemeMode {
+    pub const ALL: [ThemeMode; 3] = [ThemeMode::Light, ThemeMode::Dark, ThemeMode::System];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            ThemeMode::Light => "light",
+            ThemeMode::Dark => "dark",
+            ThemeMode::System => "system",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<ThemeMode> {
+        ThemeMode::ALL.into_iter().find(|mode| mode.code() == code)
+    }
+
+    pub fn icon(self) -> &'static str {
+        match self {
+            ThemeMode::Light => "fa fa-sun-o",
+            ThemeMode::Dark => "fa fa-moon-o",
+            ThemeMode::System => "fa fa-desktop",
+        }
+    }
+
+    /// 切换按钮依次循环的下一个模式
+    pub fn next(self) -> ThemeMode {
+        match self {
+            ThemeMode::Light => ThemeMode::Dark,
+            ThemeMode::Dark => ThemeMode::System,
+            ThemeMode::System => ThemeMode::Light,
+        }
+    }
+}
+
+/// 配色方案
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Palette {
+    /// 夏日：明黄 + 青柠
+    #[default]
+    Summer,
+    /// 海洋：天蓝 + 青绿
+    Ocean,
+}
+
+impl Palette {
+    pub const ALL: [Palette; 2] = [Palette::Summer, Palette::Ocean];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Palette::Summer => "summer",
+            Palette::Ocean => "ocean",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Palette> {
+        Palette::ALL
+            .into_iter()
+            .find(|palette| palette.code() == code)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Theme {
+    pub mode: ThemeMode,
+    pub palette: Palette,
+}
+
+impl Theme {
+    pub fn to_cookie(self) -> String {
+        format!("{}.{}", self.mode.code(), self.palette.code())
+    }
+
+    pub fn from_cookie(value: &str) -> Option<Theme> {
+        let (mode, palette) = value.split_once('.')?;
+        Some(Theme {
+            mode: ThemeMode::from_code(mode)?,
+            palette: Palette::from_code(palette)?,
+        })
+    }
+}
+
+/// 当前请求或页面的主题
+///
+/// SSR：优先 Cookie，其次当前用户保存的设置；浏览器端：读取 SSR 写入的 `<html>` 属性
+pub fn request_theme() -> Theme {
+    #[cfg(feature = "ssr")]
+    {
+        let Some(parts) = use_context::<http::request::Parts>() else {
+            return Theme::default();
+        };
+        crate::session::read_cookie(&parts.headers, THEME_COOKIE)
+            .and_then(|value| Theme::from_cookie(&value))
+            .or_else(|| crate::api::theme::stored_theme(&parts.headers))
+            .unwrap_or_default()
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let root = web_sys::window()
+            .and_then(|w| w.document())
+            .and_then(|d| d.document_element());
+        let attr = |name: &str| root.as_ref().and_then(|el| el.get_attribute(name));
+        Theme {
+            mode: attr("data-mode")
+                .and_then(|code| ThemeMode::from_code(&code))
+                .unwrap_or_default(),
+            palette: attr("data-theme")
+                .and_then(|code| Palette::from_code(&code))
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// 通过 context 共享的当前主题
+#[derive(Clone, Copy)]
+pub struct ThemeContext {
+    theme: RwSignal<Theme>,
+}
+
+impl ThemeContext {
+    pub fn get(&self) -> Theme {
+        self.theme.get()
+    }
+
+    pub fn get_untracked(&self) -> Theme {
+        self.theme.get_untracked()
+    }
+
+    /// 切换主题：立即更新 `<html>` 属性，再保存到用户设置 (同时写入 Cookie)
+    pub fn set(&self, theme: Theme) {
+        self.theme.set(theme);
+        #[cfg(target_arch = "wasm32")]
+        {
+            if let Some(root) = web_sys::window()
+                .and_then(|w| w.document())
+                .and_then(|d| d.document_element())
+            {
+                let _ = root.set_attribute("data-mode", theme.mode.code());
+                let _ = root.set_attribute("data-theme", theme.palette.code());
+            }
+        }
+        leptos::task::spawn_local(async move {
+            if let Err(e) = save_theme(theme).await {
+                leptos::logging::warn!("保存主题失败: {}", e);
+            }
+        });
+    }
+}
+
+/// 在应用根部提供主题 context
+pub fn provide_theme() -> ThemeContext {
+    let context = ThemeContext {
+        theme: RwSignal::new(request_theme()),
+    };
+    provide_context(context);
+    context
+}
+
+pub fn use_theme() -> ThemeContext {
+    expect_context::<ThemeContext>()
+}
@@ -1,9 +1,31 @@
 use app::*;
-use axum::Router;
+use axum::{
+    body::Body,
+    extract::{Path, Request, State},
+    http::{header, HeaderValue, Method, StatusCode},
+    middleware::{self, Next},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+    routing::get,
+    Router,
+};
 use dotenv::dotenv;
+use futures::{stream, Stream, StreamExt};
 use leptos::logging::log;
 use leptos::prelude::*;
 use leptos_axum::{generate_route_list, LeptosRoutes};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+use tower::{Layer, ServiceBuilder, ServiceExt};
+use tower_http::{
+    cors::{AllowOrigin, CorsLayer},
+    limit::RequestBodyLimitLayer,
+    set_header::SetResponseHeaderLayer,
+};
 
 #[tokio::main]
 async fn main() {
@@ -15,18 +37,146 @@ async fn main() {
     let routes = generate_route_list(App);
 
     let app = Router::new()
+        // 生成任务的进度推送 (SSE)
+        .route("/api/jobs/{id}/events", get(job_events))
+        // 服务端生成的音频片段
+        .route("/api/clips/{id}", get(clip_audio))
         .leptos_routes(&leptos_options, routes, {
             let leptos_options = leptos_options.clone();
             move || shell(leptos_options.clone())
         })
         .fallback(leptos_axum::file_and_error_handler(shell))
-        .with_state(leptos_options);
+        .with_state(leptos_options)
+        // 后添加的中间件在外层，请求先经过它们
+        .layer(middleware::from_fn(limit_body))
+        .layer(middleware::from_fn_with_state(cors_layer(), rest_cors))
+        .layer(middleware::from_fn(frame_options))
+        // 页面的 CSP 由 shell 按请求生成 (带 nonce)，这里只给接口和静态文件一个兜底策略
+        .layer(SetResponseHeaderLayer::if_not_present(
+            header::CONTENT_SECURITY_POLICY,
+            HeaderValue::from_static("default-src 'none'; frame-ancestors 'none'"),
+        ))
+        .layer(SetResponseHeaderLayer::if_not_present(
+            header::STRICT_TRANSPORT_SECURITY,
+            HeaderValue::from_static("max-age=31536000; includeSubDomains"),
+        ))
+        .layer(SetResponseHeaderLayer::if_not_present(
+            header::X_CONTENT_TYPE_OPTIONS,
+            HeaderValue::from_static("nosniff"),
+        ))
+        .layer(SetResponseHeaderLayer::if_not_present(
+            header::REFERRER_POLICY,
+            HeaderValue::from_static("strict-origin-when-cross-origin"),
+        ));
 
     // run our app with hyper
     // `axum::Server` is a re-export of `hyper::Server`
     log!("listening on http://{}", &addr);
     let listener = tokio::net::TcpListener::bind(&addr).await.unwrap();
-    axum::serve(listener, app.into_make_service())
+    // 带上连接的对端地址，分享密码的错误次数按客户端分别计数
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    .unwrap();
+}
+
+/// 按路径限制请求体大小：音频上传放宽，其余请求使用较小的上限。
+/// Content-Length 超限的请求直接返回 413，没有 Content-Length 的请求在读取时截断
+async fn limit_body(req: Request, next: Next) -> Response {
+    let limit = if req.uri().path() == security::UPLOAD_PATH {
+        security::UPLOAD_BODY_LIMIT_BYTES
+    } else {
+        security::BODY_LIMIT_BYTES
+    };
+    ServiceBuilder::new()
+        .layer(RequestBodyLimitLayer::new(limit))
+        .map_request(|req: Request<_>| req.map(Body::new))
+        .service(next)
+        .oneshot(req)
+        .await
+        .map(IntoResponse::into_response)
+        .unwrap_or_else(|never| match never {})
+}
+
+/// REST 接口的跨域策略，允许的来源由 `EARDO_CORS_ORIGINS` 配置 (逗号分隔，`*` 表示任意来源)；
+/// 未配置时不允许跨域。接口都是只读的，也不接受带凭据的跨域请求
+fn cors_layer() -> CorsLayer {
+    let layer = CorsLayer::new()
+        .allow_methods([Method::GET, Method::HEAD])
+        .max_age(Duration::from_secs(60 * 60));
+    match std::env::var("EARDO_CORS_ORIGINS") {
+        Ok(origins) if origins.trim() == "*" => layer.allow_origin(AllowOrigin::any()),
+        Ok(origins) => layer.allow_origin(AllowOrigin::list(
+            origins
+                .split(',')
+                .map(str::trim)
+                .filter(|origin| !origin.is_empty())
+                .filter_map(|origin| HeaderValue::from_str(origin).ok()),
+        )),
+        Err(_) => layer,
+    }
+}
+
+/// 对外开放的 REST 接口；服务端函数不在此列，始终只接受同源请求
+fn is_rest_api(path: &str) -> bool {
+    path.starts_with("/api/jobs/") || path.starts_with("/api/clips/") || path == "/api/oembed"
+}
+
+async fn rest_cors(State(cors): State<CorsLayer>, req: Request, next: Next) -> Response {
+    if !is_rest_api(req.uri().path()) {
+        return next.run(req).await;
+    }
+    cors.layer(next)
+        .oneshot(req)
         .await
-        .unwrap();
+        .unwrap_or_else(|never| match never {})
+}
+
+/// 除嵌入播放器外，禁止其他站点用 iframe 加载本站页面 (兼容不支持 CSP frame-ancestors 的浏览器)
+async fn frame_options(req: Request, next: Next) -> Response {
+    let embeddable = security::is_embeddable(req.uri().path());
+    let mut response = next.run(req).await;
+    if !embeddable {
+        response
+            .headers_mut()
+            .insert(header::X_FRAME_OPTIONS, HeaderValue::from_static("DENY"));
+    }
+    response
+}
+
+/// 先回放任务已发生的事件，再持续推送新事件，直到任务结束
+async fn job_events(
+    Path(id): Path<String>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, StatusCode> {
+    let (history, receiver) = jobs::subscribe(&id).ok_or(StatusCode::NOT_FOUND)?;
+
+    let live = stream::unfold(receiver, |receiver| async move {
+        let mut receiver = receiver?;
+        loop {
+            match receiver.recv().await {
+                // 收到结束事件后不再等待
+                Ok(event) if event.is_terminal() => return Some((event, None)),
+                Ok(event) => return Some((event, Some(receiver))),
+                // 订阅者太慢丢了事件，继续接收最新的即可
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    let events = stream::iter(history)
+        .chain(live)
+        .map(|event| Ok(Event::default().json_data(&event).unwrap_or_default()));
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+async fn clip_audio(Path(id): Path<String>) -> Result<impl IntoResponse, StatusCode> {
+    let clip = clips::get(&id).ok_or(StatusCode::NOT_FOUND)?;
+    Ok((
+        [(header::CONTENT_TYPE, clip.content_type)],
+        clip.bytes.clone(),
+    ))
 }